@@ -7,16 +7,25 @@ pub mod types;
 
 use self::northbound::{NorthboundAdapterInterface, NorthboundControllerInterface};
 use self::scheduling::SchedulerAdapterInterface;
-use self::southbound::SouthboundAdapterInterface;
+use self::southbound::{SouthboundAdapterInterface, SouthboundControllerInterface};
 use self::storage::StorageAdapterInterface;
-use self::topology::{TopologyAdapterInterface, TopologyControllerInterface};
+use self::topology::{
+    canonical_mac, PathComputation, TopologyAdapterInterface, TopologyChanges,
+    TopologyControllerInterface,
+};
 use self::types::computation::ComputationType;
+use self::types::frer_types::FrerConfiguration;
 use self::types::notification_types::{self, NotificationContent};
-use self::types::uni_types::{self, compute_streams, Stream};
+use self::types::sched_types::ConfigurableGateParameterTableEntry;
+use self::types::scheduling::{Config, PortConfiguration, Schedule};
+use self::types::topology::{LinkEvent, NodeType, Topology};
+use self::types::uni_types::{self, compute_streams, Stream, StreamStatus};
 use self::types::{FailedInterfaces, FailedStream, StreamRequest};
-use std::borrow::BorrowMut;
-use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, RwLock, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 // these are helper types to make the code more readable
 pub type NorthboundRef = Arc<dyn NorthboundAdapterInterface + Send + Sync>;
@@ -24,28 +33,99 @@ pub type SouthboundRef = Arc<dyn SouthboundAdapterInterface + Send + Sync>;
 pub type StorageRef = Arc<dyn StorageAdapterInterface + Send + Sync>;
 pub type TopologyRef = Arc<dyn TopologyAdapterInterface + Send + Sync>;
 pub type SchedulerRef = Arc<dyn SchedulerAdapterInterface + Send + Sync>;
+pub type TimerCallback = Arc<dyn Fn(&Arc<Cnc>) + Send + Sync>;
 // ----
 
 /// Message to display if CNC is not present
 pub const CNC_NOT_PRESENT: &'static str = "CNC is not present exiting...";
 
+/// Events that are processed sequentially by the main loop of the CNC.
+pub enum CncEvent {
+    /// computation of the streams determined by the computation_type
+    Compute(ComputationType),
+
+    /// the topology-component detected a change of the network
+    TopologyChanged,
+
+    /// a bridge reported that a port went down or up
+    LinkChanged(LinkEvent),
+
+    /// the notifications of a bridge are received (again), see handle_subscription
+    Subscribed(u32),
+
+    /// a timer registered with add_timer expired
+    Timer(TimerCallback),
+
+    /// stops the main loop. Events that were sent before are still processed.
+    Shutdown,
+}
+
 /// This is the main CNC struct. It holds all Components and is responsible for the main operation of the CNC.
 pub struct Cnc {
     pub id: u32,
     pub domain: String,
-    schedule_computation_sender: Sender<ComputationType>,
+    event_sender: Sender<CncEvent>,
+
+    /// topology that was used for the last computation
+    last_topology: RwLock<Option<Topology>>,
+
+    /// ports that the bridges reported as down by node_id and port name. Their connections are
+    /// removed from the topology until the ports are up again.
+    down_ports: RwLock<HashSet<(u32, String)>>,
     northbound: NorthboundRef,
     southbound: SouthboundRef,
     storage: StorageRef,
     topology: TopologyRef,
     scheduler: SchedulerRef,
-    operating: Arc<RwLock<bool>>,
+}
+
+/// This handle is returned by Cnc::start and is used to control the running CNC.
+pub struct CncHandle {
+    cnc: Arc<Cnc>,
+    main_loop: JoinHandle<()>,
+}
+
+impl CncHandle {
+    /// the running CNC
+    pub fn cnc(&self) -> Arc<Cnc> {
+        self.cnc.clone()
+    }
+
+    /// requests the CNC to stop. All events that were sent before are still processed.
+    pub fn shutdown(&self) {
+        self.cnc.shutdown();
+    }
+
+    /// blocks until the main loop of the CNC stopped.
+    pub fn join(self) {
+        drop(self.cnc);
+        if self.main_loop.join().is_err() {
+            eprintln!("[CNC] main loop panicked");
+        }
+    }
 }
 
 impl Cnc {
-    /// This runs the CNC continuosly unless Components stop running.
+    /// This runs the CNC continuosly until it gets shut down.
     /// This function call is blocking until the CNC stops operation.
     pub fn run(
+        id: u32,
+        domain: String,
+        northbound: NorthboundRef,
+        southbound: SouthboundRef,
+        storage: StorageRef,
+        topology: TopologyRef,
+        scheduler: SchedulerRef,
+    ) {
+        Cnc::start(
+            id, domain, northbound, southbound, storage, topology, scheduler,
+        )
+        .join();
+    }
+
+    /// This configures and starts all Components and runs the CNC in a new thread.
+    /// The returned handle can be used to shut the CNC down.
+    pub fn start(
         id: u32,
         domain: String,
         mut northbound: NorthboundRef,
@@ -53,12 +133,10 @@ impl Cnc {
         mut storage: StorageRef,
         mut topology: TopologyRef,
         mut scheduler: SchedulerRef,
-    ) {
-        // Channel for starting a computation
-        let (schedule_computation_sender, schedule_computation_receiver): (
-            Sender<ComputationType>,
-            Receiver<ComputationType>,
-        ) = mpsc::channel();
+    ) -> CncHandle {
+        // Channel for all events of the main loop
+        let (event_sender, event_receiver): (Sender<CncEvent>, Receiver<CncEvent>) =
+            mpsc::channel();
 
         let cnc: Arc<Self> = Arc::new_cyclic(|my_weak_ref: &Weak<Self>| {
             // configure all components
@@ -85,13 +163,14 @@ impl Cnc {
             Self {
                 id,
                 domain,
-                schedule_computation_sender,
+                event_sender,
+                last_topology: RwLock::new(None),
+                down_ports: RwLock::new(HashSet::new()),
                 northbound,
                 southbound,
                 storage,
                 topology,
                 scheduler,
-                operating: Arc::new(RwLock::new(true)),
             }
         });
         println!(
@@ -103,37 +182,135 @@ impl Cnc {
         cnc.storage.configure_storage();
         cnc.topology.run();
         cnc.northbound.run();
+        cnc.southbound.run();
+
+        let loop_cnc = cnc.clone();
+        let main_loop = thread::spawn(move || Cnc::main_loop(loop_cnc, event_receiver));
 
-        // wait for computation-requests
-        loop {
-            match schedule_computation_receiver.try_recv() {
-                Ok(computation_type) => {
+        CncHandle { cnc, main_loop }
+    }
+
+    /// waits for events and processes them one after another until a shutdown is requested.
+    fn main_loop(cnc: Arc<Cnc>, event_receiver: Receiver<CncEvent>) {
+        // the cnc holds a sender itself, so the channel never disconnects while cnc is alive
+        for event in event_receiver.iter() {
+            match event {
+                CncEvent::Compute(computation_type) => {
                     Cnc::execute_computation(cnc.clone(), computation_type);
                 }
-                Err(TryRecvError::Empty) => {}
-                Err(TryRecvError::Disconnected) => {
-                    println!("[CNC] got disconnected from scheduler");
-                    cnc.set_operating(false);
-                }
+                CncEvent::TopologyChanged => cnc.handle_topology_change(),
+                CncEvent::LinkChanged(event) => cnc.handle_link_change(event),
+                CncEvent::Subscribed(node_id) => cnc.handle_subscription(node_id),
+                CncEvent::Timer(callback) => callback(&cnc),
+                CncEvent::Shutdown => break,
             }
+        }
+
+        println!("[CNC] stopped...");
+    }
 
-            // check id cnc should stop operating
-            if !(*cnc.operating.read().unwrap()) {
+    /// This stops the CNC after all events that were sent before are processed.
+    pub fn shutdown(&self) {
+        if self.event_sender.send(CncEvent::Shutdown).is_err() {
+            eprintln!("[CNC] already stopped");
+        }
+    }
+
+    /// The callback gets executed periodically in the main loop of the CNC until it stops.
+    pub fn add_timer(&self, interval: Duration, callback: TimerCallback) {
+        let sender = self.event_sender.clone();
+
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+
+            // the main loop stopped
+            if sender.send(CncEvent::Timer(callback.clone())).is_err() {
                 break;
             }
-        }
+        });
+    }
 
-        println!("[CNC] stopped...");
-        drop(cnc);
+    /// Checks the configuration of the bridges periodically for drift, e.g. gate-parameters that
+    /// were changed by hand. If remediate is set, the stored configuration is re-applied.
+    pub fn start_drift_detection(&self, interval: Duration, remediate: bool) {
+        self.add_timer(
+            interval,
+            Arc::new(move |cnc: &Arc<Cnc>| cnc.check_configuration_drift(remediate)),
+        );
     }
 
-    /// This function sets the operating status of the CNC.
-    /// This can be used to stop the CNC from running by setting operating to false.
-    fn set_operating(&self, value: bool) {
-        let o = self.operating.clone();
-        let mut o_lock = o.write().unwrap();
-        **(o_lock.borrow_mut()) = value;
-        drop(o_lock);
+    /// This compares the gate-parameters on the bridges with the stored configurations.
+    ///
+    /// The affected streams of drifted interfaces are reported to the Northbound. If remediate is
+    /// set, the stored configuration of these interfaces is deployed again and the result is
+    /// reported instead.
+    pub fn check_configuration_drift(&self, remediate: bool) {
+        let configs = self.storage.get_all_configs();
+        if configs.is_empty() {
+            return;
+        }
+
+        let topology = match self.last_topology.read().unwrap().clone() {
+            Some(topology) => topology,
+            None => self.current_topology(),
+        };
+
+        let drifted = self.southbound.detect_drift(&topology, &configs);
+        if drifted.interfaces.is_empty() {
+            println!("[CNC] no configuration drift detected");
+            return;
+        }
+
+        println!(
+            "[CNC] configuration drift detected on {} interfaces",
+            drifted.interfaces.len()
+        );
+
+        let affected_streams: HashSet<String> = drifted
+            .interfaces
+            .iter()
+            .flat_map(|i| i.affected_streams.iter().cloned())
+            .collect();
+
+        // only the streams of drifted interfaces are reported
+        let mut domains = self.storage.get_all_domains();
+        for domain in domains.iter_mut() {
+            for cuc in domain.cuc.iter_mut() {
+                cuc.stream
+                    .retain(|s| affected_streams.contains(&s.stream_id));
+            }
+            domain.cuc.retain(|c| !c.stream.is_empty());
+        }
+        domains.retain(|d| !d.cuc.is_empty());
+
+        let failed_interfaces = if remediate {
+            let schedule = Schedule {
+                configs: configs
+                    .into_iter()
+                    .filter(|c| {
+                        drifted.interfaces.iter().any(|i| {
+                            i.node_id == c.node_id && i.interface.interface_name == c.port.name
+                        })
+                    })
+                    .collect(),
+                trees: HashMap::new(),
+            };
+
+            println!(
+                "[CNC] re-applying the configuration of {} interfaces",
+                schedule.configs.len()
+            );
+            self.southbound.configure_network(&topology, &schedule)
+        } else {
+            drifted
+        };
+
+        self.storage
+            .set_streams_configured(&domains, &failed_interfaces);
+
+        let notification: NotificationContent =
+            create_configuration_notification(&domains, &failed_interfaces);
+        self.northbound.configure_streams_completed(notification);
     }
 
     /// This function is used to execute a computation.
@@ -143,14 +320,34 @@ impl Cnc {
     fn execute_computation(cnc: Arc<Cnc>, computation_type: ComputationType) {
         println!("[Scheduler] preparing computation...");
 
-        let topology = cnc.topology.get_topology();
+        let topology = cnc.current_topology();
         let domains = cnc.get_domains_to_compute(computation_type);
 
         println!("[Scheduler] computing schedule now...");
 
-        let computation_result = cnc.scheduler.compute_schedule(&topology, &domains);
-        cnc.storage
-            .set_configs(&computation_result.schedule.configs);
+        let metric = cnc.topology.get_path_metric();
+        let computation_result = cnc.scheduler.compute_schedule(&topology, metric, &domains);
+        *cnc.last_topology.write().unwrap() = Some(topology.clone());
+        let computed_streams: HashSet<&String> = domains
+            .iter()
+            .flat_map(|d| d.cuc.iter())
+            .flat_map(|c| c.stream.iter())
+            .map(|s| &s.stream_id)
+            .collect();
+        let other_streams: HashSet<String> = cnc
+            .storage
+            .get_all_domains()
+            .iter()
+            .flat_map(|d| d.cuc.iter())
+            .flat_map(|c| c.stream.iter())
+            .map(|s| s.stream_id.clone())
+            .filter(|stream_id| !computed_streams.contains(stream_id))
+            .collect();
+        let schedule = release_unused_forwarding(
+            &computation_result.schedule,
+            &cnc.storage.get_all_configs(),
+            &other_streams,
+        );
 
         if computation_result.failed_streams.len() > 0 {
             println!(
@@ -170,9 +367,7 @@ impl Cnc {
 
         println!("[Scheduler] configuring now...");
 
-        let failed_interfaces = cnc
-            .southbound
-            .configure_network(&topology, &computation_result.schedule);
+        let failed_interfaces = cnc.southbound.configure_network(&topology, &schedule);
 
         if failed_interfaces.interfaces.len() > 0 {
             println!(
@@ -183,14 +378,66 @@ impl Cnc {
             println!("[Scheduler] configuration finished ");
         }
 
+        // failed and rolled back interfaces keep their previous config on the bridges
+        cnc.storage.set_configs(&deployed_configs(
+            &computation_result.schedule.configs,
+            &failed_interfaces,
+        ));
+        // the configs of the released ports follow the computed ones
+        let released_configs: Vec<Config> =
+            schedule.configs[computation_result.schedule.configs.len()..].to_vec();
+        cnc.storage
+            .remove_configs(&deployed_configs(&released_configs, &failed_interfaces));
         cnc.storage
             .set_streams_configured(&domains, &failed_interfaces);
+        cnc.release_failed_streams(
+            &domains,
+            &computation_result.failed_streams,
+            &failed_interfaces,
+        );
 
         let notification: NotificationContent =
             create_configuration_notification(&domains, &failed_interfaces);
         cnc.northbound.configure_streams_completed(notification);
     }
 
+    /// releases the resources and stream handles, that the scheduler assigned to the streams that
+    /// failed in the computation or the configuration. Only configured streams keep them, so failed
+    /// streams don't exhaust the pools.
+    fn release_failed_streams(
+        &self,
+        domains: &Vec<uni_types::Domain>,
+        failed_streams: &Vec<FailedStream>,
+        failed_interfaces: &FailedInterfaces,
+    ) {
+        let failed: HashSet<&String> = failed_streams
+            .iter()
+            .map(|f| &f.stream_id)
+            .chain(
+                failed_interfaces
+                    .interfaces
+                    .iter()
+                    .flat_map(|i| i.affected_streams.iter()),
+            )
+            .collect();
+
+        for domain in domains.iter() {
+            let stream_ids: Vec<String> = domain
+                .cuc
+                .iter()
+                .flat_map(|c| c.stream.iter())
+                .map(|s| &s.stream_id)
+                .filter(|stream_id| failed.contains(stream_id))
+                .cloned()
+                .collect();
+
+            if stream_ids.len() > 0 {
+                self.storage
+                    .release_stream_resources(&domain.domain_id, &stream_ids);
+            }
+        }
+    }
+
     /// This function returns the domains to compute based on the computation_type.
     fn get_domains_to_compute(&self, computation: ComputationType) -> Vec<uni_types::Domain> {
         let domains: Vec<uni_types::Domain> = match computation {
@@ -203,11 +450,451 @@ impl Cnc {
                 .get_planned_and_modified_streams_in_domains(request_domains),
 
             ComputationType::List(request_domains) => {
-                self.storage.get_streams_in_domains(request_domains)
+                let mut stream_lists: HashMap<(String, String), Vec<String>> = HashMap::new();
+                for domain in request_domains.iter() {
+                    for cuc in domain.cuc.iter() {
+                        if let Some(stream_list) = &cuc.stream_list {
+                            stream_lists.insert(
+                                (domain.domain_id.clone(), cuc.cuc_id.clone()),
+                                stream_list.clone(),
+                            );
+                        }
+                    }
+                }
+
+                let mut domains = self.storage.get_streams_in_domains(request_domains);
+
+                // only the listed streams are computed. Without a list all streams of the cuc are.
+                for domain in domains.iter_mut() {
+                    for cuc in domain.cuc.iter_mut() {
+                        let key = (domain.domain_id.clone(), cuc.cuc_id.clone());
+                        if let Some(stream_list) = stream_lists.get(&key) {
+                            cuc.stream.retain(|s| stream_list.contains(&s.stream_id));
+                        }
+                    }
+                }
+
+                domains
             }
         };
 
-        return domains;
+        return self.add_streams_on_shared_ports(domains);
+    }
+
+    /// adds the configured streams that share a port with the streams of the domains.
+    ///
+    /// The gate control lists of the ports are computed from all streams in the computation, so
+    /// the configured streams on the same ports have to be computed again. Otherwise their
+    /// windows are missing when the new gate control lists are deployed. If a stream isn't
+    /// configured yet, its ports are unknown and all configured streams of the requested domains
+    /// are added.
+    fn add_streams_on_shared_ports(
+        &self,
+        mut domains: Vec<uni_types::Domain>,
+    ) -> Vec<uni_types::Domain> {
+        let configs = self.storage.get_all_configs();
+        let mut stream_ids: HashSet<String> = domains
+            .iter()
+            .flat_map(|d| d.cuc.iter())
+            .flat_map(|c| c.stream.iter())
+            .map(|s| s.stream_id.clone())
+            .collect();
+
+        let configured: HashSet<&String> = configs
+            .iter()
+            .flat_map(|c| c.affected_streams.iter())
+            .collect();
+        let has_unconfigured_stream = stream_ids.iter().any(|id| !configured.contains(id));
+        let requested_domains: HashSet<String> =
+            domains.iter().map(|d| d.domain_id.clone()).collect();
+
+        // the streams on the ports of the added streams are added as well, until no port is left
+        let mut added = stream_ids.clone();
+        while !has_unconfigured_stream && !added.is_empty() {
+            let shared: HashSet<String> = configs
+                .iter()
+                .filter(|c| c.affected_streams.iter().any(|id| added.contains(id)))
+                .flat_map(|c| c.affected_streams.iter().cloned())
+                .collect();
+
+            added = shared.difference(&stream_ids).cloned().collect();
+            stream_ids.extend(added.iter().cloned());
+        }
+
+        for domain in self.storage.get_all_domains() {
+            if has_unconfigured_stream && !requested_domains.contains(&domain.domain_id) {
+                continue;
+            }
+
+            for cuc in domain.cuc {
+                let streams: Vec<Stream> = cuc
+                    .stream
+                    .into_iter()
+                    .filter(|s| s.stream_status == StreamStatus::Configured)
+                    .filter(|s| has_unconfigured_stream || stream_ids.contains(&s.stream_id))
+                    .filter(|s| {
+                        !domains.iter().any(|d| {
+                            d.cuc
+                                .iter()
+                                .any(|c| c.stream.iter().any(|o| o.stream_id == s.stream_id))
+                        })
+                    })
+                    .collect();
+
+                if streams.is_empty() {
+                    continue;
+                }
+
+                let index = match domains.iter().position(|d| d.domain_id == domain.domain_id) {
+                    Some(index) => index,
+                    None => {
+                        domains.push(uni_types::Domain {
+                            domain_id: domain.domain_id.clone(),
+                            cnc_enabled: domain.cnc_enabled,
+                            cuc: Vec::new(),
+                        });
+                        domains.len() - 1
+                    }
+                };
+
+                let target = &mut domains[index];
+                match target.cuc.iter_mut().find(|c| c.cuc_id == cuc.cuc_id) {
+                    Some(target_cuc) => target_cuc.stream.extend(streams),
+                    None => target.cuc.push(uni_types::Cuc {
+                        cuc_id: cuc.cuc_id,
+                        stream: streams,
+                    }),
+                }
+            }
+        }
+
+        domains
+    }
+
+    /// The topology of the topology-component without the connections of ports that are down.
+    ///
+    /// The topology-component only notices a link change with its next discovery, the bridges
+    /// report it immediately.
+    fn current_topology(&self) -> Topology {
+        let mut topology = self.topology.get_topology();
+        let down_ports = self.down_ports.read().unwrap();
+
+        let connections = topology.connections.len();
+        topology.connections.retain(|c| {
+            !down_ports.contains(&(c.a.node_id, c.a.port_name.clone()))
+                && !down_ports.contains(&(c.b.node_id, c.b.port_name.clone()))
+        });
+
+        if topology.connections.len() != connections && topology.paths.is_some() {
+            let metric = self.topology.get_path_metric();
+            let paths = PathComputation::new(&topology, metric).compute_all_paths();
+            topology.paths = Some(paths);
+        }
+
+        topology
+    }
+
+    /// This updates the ports that are down and handles the link change like a topology change,
+    /// so the streams over the port are recomputed.
+    fn handle_link_change(&self, event: LinkEvent) {
+        println!(
+            "[CNC] port {} of node {} is {}",
+            event.port_name,
+            event.node_id,
+            if event.up { "up" } else { "down" }
+        );
+
+        let port = (event.node_id, event.port_name);
+        let changed = match event.up {
+            true => self.down_ports.write().unwrap().remove(&port),
+            false => self.down_ports.write().unwrap().insert(port),
+        };
+
+        if changed {
+            self.handle_topology_change();
+        }
+    }
+
+    /// This forgets the ports of the node that were reported down.
+    ///
+    /// While the node wasn't subscribed, e.g. after a lost session or while it left the topology,
+    /// the ports could have come up without a notification. Ports that are still down are found
+    /// by the next discovery of the topology-component or reported again.
+    fn handle_subscription(&self, node_id: u32) {
+        let mut down_ports = self.down_ports.write().unwrap();
+        let count = down_ports.len();
+        down_ports.retain(|(down_node_id, _)| *down_node_id != node_id);
+        let changed = down_ports.len() != count;
+        drop(down_ports);
+
+        if changed {
+            println!(
+                "[CNC] node {} was subscribed again, its ports are no longer seen as down",
+                node_id
+            );
+            self.handle_topology_change();
+        }
+    }
+
+    /// This compares the current topology with the one used for the last computation.
+    ///
+    /// All streams whose paths traverse a changed node or connection are set to Modified and
+    /// get recomputed. The CUCs are notified about the result by the recomputation.
+    fn handle_topology_change(&self) {
+        let new_topology = self.current_topology();
+        let mut last_topology = self.last_topology.write().unwrap();
+
+        let old_topology = match last_topology.as_ref() {
+            Some(old_topology) => old_topology,
+            None => {
+                println!("[CNC] got notified about TopologyChange. But nothing is computed yet...");
+                return;
+            }
+        };
+
+        let changes = old_topology.diff(&new_topology);
+        if changes.is_empty() {
+            println!("[CNC] got notified about TopologyChange. But nothing changed...");
+            return;
+        }
+
+        let affected_streams = self.get_affected_streams(&changes, old_topology, &new_topology);
+        *last_topology = Some(new_topology);
+        drop(last_topology);
+
+        println!(
+            "[CNC] topology changed - {} nodes and {} connections, {} streams affected",
+            changes.nodes.len(),
+            changes.connections.len(),
+            affected_streams.len()
+        );
+
+        if affected_streams.is_empty() {
+            return;
+        }
+
+        let mut modified_domains: Vec<uni_types::Domain> = Vec::new();
+        let mut request: compute_streams::Input = Vec::new();
+
+        for domain in self.storage.get_all_domains() {
+            let mut modified_domain = uni_types::Domain {
+                domain_id: domain.domain_id.clone(),
+                cnc_enabled: domain.cnc_enabled,
+                cuc: Vec::new(),
+            };
+            let mut request_domain = compute_streams::Domain {
+                domain_id: domain.domain_id.clone(),
+                cuc: Vec::new(),
+            };
+
+            for cuc in domain.cuc {
+                let streams: Vec<Stream> = cuc
+                    .stream
+                    .into_iter()
+                    .filter(|s| affected_streams.contains(&s.stream_id))
+                    .map(|mut s| {
+                        s.stream_status = StreamStatus::Modified;
+                        s
+                    })
+                    .collect();
+
+                if streams.is_empty() {
+                    continue;
+                }
+
+                request_domain.cuc.push(compute_streams::CucElement {
+                    cuc_id: cuc.cuc_id.clone(),
+                    stream_list: Some(streams.iter().map(|s| s.stream_id.clone()).collect()),
+                });
+                modified_domain.cuc.push(uni_types::Cuc {
+                    cuc_id: cuc.cuc_id,
+                    stream: streams,
+                });
+            }
+
+            if !request_domain.cuc.is_empty() {
+                modified_domains.push(modified_domain);
+                request.push(request_domain);
+            }
+        }
+
+        self.storage.modify_streams(&modified_domains);
+        self.compute_streams(ComputationType::List(request));
+    }
+
+    /// returns the ids of all streams that are configured on a changed node or port.
+    /// Also streams with a talker or listener on a changed end station are affected.
+    fn get_affected_streams(
+        &self,
+        changes: &TopologyChanges,
+        old_topology: &Topology,
+        new_topology: &Topology,
+    ) -> HashSet<String> {
+        let mut affected_streams: HashSet<String> = HashSet::new();
+
+        for config in self.storage.get_all_configs() {
+            let port_changed = changes.nodes.contains(&config.node_id)
+                || changes
+                    .connections
+                    .iter()
+                    .any(|c| c.is_attached_to(config.node_id, &config.port.name));
+
+            if port_changed {
+                affected_streams.extend(config.affected_streams);
+            }
+        }
+
+        // end stations are not configured, so they are matched by the mac-addresses of the streams
+        let mut changed_stations: HashSet<u32> = changes.nodes.clone();
+        for connection in changes.connections.iter() {
+            changed_stations.insert(connection.a.node_id);
+            changed_stations.insert(connection.b.node_id);
+        }
+
+        let changed_mac_addresses: HashSet<String> = old_topology
+            .nodes
+            .iter()
+            .chain(new_topology.nodes.iter())
+            .filter(|n| n.endstation == NodeType::EndStation && changed_stations.contains(&n.id))
+            .flat_map(|n| n.mac_addresses_interfaces.iter())
+            .map(|mac| canonical_mac(mac))
+            .collect();
+
+        for domain in self.storage.get_all_domains() {
+            for cuc in domain.cuc.iter() {
+                for stream in cuc.stream.iter() {
+                    // planned streams are not configured in the network yet
+                    if stream.stream_status == StreamStatus::Planned {
+                        continue;
+                    }
+
+                    let talker = stream.talker.group_talker.end_station_interfaces.iter();
+                    let listeners = stream
+                        .listener
+                        .iter()
+                        .flat_map(|l| l.group_listener.end_station_interfaces.iter());
+
+                    if talker
+                        .chain(listeners)
+                        .any(|i| changed_mac_addresses.contains(&canonical_mac(&i.mac_address)))
+                    {
+                        affected_streams.insert(stream.stream_id.clone());
+                    }
+                }
+            }
+        }
+
+        affected_streams
+    }
+}
+
+/// returns the configs, that were deployed without failure
+fn deployed_configs(configs: &Vec<Config>, failed_interfaces: &FailedInterfaces) -> Vec<Config> {
+    configs
+        .iter()
+        .filter(|c| {
+            !failed_interfaces
+                .interfaces
+                .iter()
+                .any(|i| i.node_id == c.node_id && i.interface.interface_name == c.port.name)
+        })
+        .cloned()
+        .collect()
+}
+
+/// returns the schedule with the forwarding entries that the ports had in their previous configs,
+/// but don't need anymore. The southbound resets them.
+///
+/// Ports that aren't in the schedule anymore are appended with a config that releases all of their
+/// entries and disables their gates. The schedule only covers the ports of the computed streams,
+/// so ports whose previous config affects one of the other stored streams are kept.
+fn release_unused_forwarding(
+    schedule: &Schedule,
+    previous_configs: &Vec<Config>,
+    other_streams: &HashSet<String>,
+) -> Schedule {
+    let mut schedule = schedule.clone();
+
+    let unused_ports: Vec<Config> = previous_configs
+        .iter()
+        .filter(|p| {
+            !schedule
+                .configs
+                .iter()
+                .any(|c| c.node_id == p.node_id && c.port.name == p.port.name)
+                && !p
+                    .affected_streams
+                    .iter()
+                    .any(|stream_id| other_streams.contains(stream_id))
+        })
+        .map(released_config)
+        .collect();
+
+    for config in schedule.configs.iter_mut() {
+        let previous = match previous_configs
+            .iter()
+            .find(|p| p.node_id == config.node_id && p.port.name == config.port.name)
+        {
+            Some(previous) => previous,
+            None => continue,
+        };
+
+        let port = &mut config.port;
+        port.released_static_filtering = previous
+            .port
+            .static_filtering
+            .iter()
+            .filter(|e| {
+                !port
+                    .static_filtering
+                    .iter()
+                    .any(|n| n.address.eq_ignore_ascii_case(&e.address) && n.vid == e.vid)
+            })
+            .cloned()
+            .collect();
+        port.released_vlan_registration = previous
+            .port
+            .vlan_registration
+            .iter()
+            .filter(|e| !port.vlan_registration.iter().any(|n| n.vid == e.vid))
+            .cloned()
+            .collect();
+    }
+
+    schedule.configs.extend(unused_ports);
+    schedule
+}
+
+/// the config of a port that no stream crosses anymore. Its gates are disabled and all forwarding
+/// entries of the previous config are released.
+fn released_config(previous: &Config) -> Config {
+    Config {
+        node_id: previous.node_id,
+        affected_streams: Vec::new(),
+        port: PortConfiguration {
+            name: previous.port.name.clone(),
+            mac_address: previous.port.mac_address.clone(),
+            port_number: previous.port.port_number,
+            config: ConfigurableGateParameterTableEntry {
+                gate_enable: false,
+                admin_gate_states: 255,
+                admin_control_list: Vec::new(),
+                // not used while the gates are disabled
+                admin_cycle_time: (1, 1000),
+                admin_cycle_time_extension: 0,
+                admin_base_time: (0, 0),
+                config_change: true,
+            },
+            frame_preemption: Vec::new(),
+            fqtss: Vec::new(),
+            psfp: Vec::new(),
+            stream_identification: Vec::new(),
+            frer: FrerConfiguration::default(),
+            static_filtering: Vec::new(),
+            vlan_registration: Vec::new(),
+            released_static_filtering: previous.port.static_filtering.clone(),
+            released_vlan_registration: previous.port.vlan_registration.clone(),
+        },
     }
 }
 
@@ -238,8 +925,8 @@ fn create_computation_notification(
                         && x.domain_id == domain.domain_id
                 });
 
-                if failed_in_computation.is_some() {
-                    failure_code = 1;
+                if let Some(failed_stream) = failed_in_computation {
+                    failure_code = u8::try_from(failed_stream.failure_code).unwrap_or(1).max(1);
                 }
 
                 let notification_stream = notification_types::Stream {
@@ -284,8 +971,10 @@ fn create_configuration_notification(
                     .iter()
                     .find(|x| x.affected_streams.contains(&stream.stream_id));
 
-                if failed_with_some_interface.is_some() {
-                    failure_code = 1;
+                if let Some(failed_interface) = failed_with_some_interface {
+                    failure_code = u8::try_from(failed_interface.failure_code)
+                        .unwrap_or(1)
+                        .max(1);
                 }
 
                 let notification_stream = notification_types::Stream {
@@ -309,7 +998,7 @@ impl NorthboundControllerInterface for Cnc {
         &self,
         computation: ComputationType,
     ) -> types::uni_types::compute_streams::Output {
-        return match self.schedule_computation_sender.send(computation) {
+        return match self.event_sender.send(CncEvent::Compute(computation)) {
             Ok(_) => String::from("Success"),
             Err(e) => e.to_string(),
         };
@@ -366,7 +1055,6 @@ impl NorthboundControllerInterface for Cnc {
                     },
                 },
                 listener: vec![types::uni_types::Listener {
-                    index: 0,
                     group_listener: requested_stream.listeners[0].clone(),
                     group_status_talker_listener: types::tsn_types::GroupStatusTalkerListener {
                         accumulated_latency: 0,
@@ -404,8 +1092,24 @@ impl NorthboundControllerInterface for Cnc {
     }
 }
 
+impl SouthboundControllerInterface for Cnc {
+    fn notify_link_changed(&self, event: LinkEvent) {
+        if let Err(e) = self.event_sender.send(CncEvent::LinkChanged(event)) {
+            eprintln!("[CNC] couldnt queue link change {e:?}");
+        }
+    }
+
+    fn notify_subscribed(&self, node_id: u32) {
+        if let Err(e) = self.event_sender.send(CncEvent::Subscribed(node_id)) {
+            eprintln!("[CNC] couldnt queue subscription {e:?}");
+        }
+    }
+}
+
 impl TopologyControllerInterface for Cnc {
     fn notify_topology_changed(&self) {
-        // println!("[CNC] got notified about TopologyChange. But doing nothing about it...");
+        if let Err(e) = self.event_sender.send(CncEvent::TopologyChanged) {
+            eprintln!("[CNC] couldnt queue topology change {e:?}");
+        }
     }
 }
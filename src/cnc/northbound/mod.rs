@@ -1,3 +1,4 @@
+use super::southbound::types::YangModule;
 use super::types::computation::ComputationType;
 use super::types::notification_types::NotificationContent;
 use super::types::tsn_types::{
@@ -14,6 +15,30 @@ use std::sync::Weak;
 use std::time::Duration;
 use std::{thread, vec};
 
+mod netconf_server;
+mod notifications;
+mod restconf;
+
+pub use self::netconf_server::NetconfServerAdapter;
+pub use self::notifications::{
+    Notification, NotificationEncoding, NotificationKind, NotificationSubscriptions,
+};
+pub use self::restconf::RestconfAdapter;
+
+/// name of the yang-module implementing the TSN UNI
+pub const UNI_MODULE: &str = "ieee802-dot1q-tsn-config-uni";
+
+/// namespace of the yang-module implementing the TSN UNI
+pub const UNI_NAMESPACE: &str = "urn:ieee:std:802.1Q:yang:ieee802-dot1q-tsn-config-uni";
+
+/// all yang-models needed to validate the data exchanged over the UNI.
+pub const UNI_YANG_MODULES: &[YangModule] = &[
+    YangModule::new("ietf-inet-types", "2013-07-15"),
+    YangModule::new("ieee802-types", "2020-10-23"),
+    YangModule::new("ieee802-dot1q-tsn-types", "2022-10-29"),
+    YangModule::new("ieee802-dot1q-tsn-config-uni", "2022-10-29"),
+];
+
 /// # Northbound Interface
 /// This Trait has to be implemented to use the Component as a Northbound-Interface in the CNC.
 ///
@@ -657,7 +682,7 @@ impl NorthboundAdapterInterface for MockInsertStreamAdapter {
         thread::spawn(move || {
             // set stream-data
             cnc.set_streams(&cuc_id, MockInsertStreamAdapter::get_example_add_stream());
-            cnc.set_operating(false);
+            cnc.shutdown();
         });
     }
 }
@@ -686,7 +711,7 @@ impl NorthboundAdapterInterface for MockRemoveStreamAdapter {
             // remove stream
             let res = cnc.remove_streams(&cuc_id, vec![String::from("00-00-00-00-00-01:00-01")]);
             println!("[Northbound] response to remove_streams {res:?}", res = res);
-            cnc.set_operating(false);
+            cnc.shutdown();
         });
     }
 }
@@ -721,7 +746,7 @@ impl NorthboundAdapterInterface for MockComputeStreamAdapter {
                 }],
             }];
             cnc.compute_streams(ComputationType::All(domain));
-            cnc.set_operating(false);
+            cnc.shutdown();
         });
     }
 }
@@ -753,7 +778,7 @@ impl NorthboundAdapterInterface for MockGetStreamAdapter {
             // this tests for the correct streams for the executed test
             assert_eq!(res.cuc[0].stream.len(), 3);
             println!("[Northbound] response to get_streams {res:?}", res = res);
-            cnc.set_operating(false);
+            cnc.shutdown();
         });
     }
 }
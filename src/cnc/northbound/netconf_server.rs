@@ -0,0 +1,995 @@
+use super::notifications::{
+    Notification, NotificationEncoding, NotificationKind, NotificationSubscriptions,
+};
+use super::{NorthboundAdapterInterface, NorthboundControllerInterface, UNI_MODULE, UNI_NAMESPACE};
+use crate::cnc::types::computation::ComputationType;
+use crate::cnc::types::notification_types::NotificationContent;
+use crate::cnc::types::tsn_types::{
+    DataFrameSpecificationElement, DataFrameSpecificationElementType, GroupIeee802MacAddress,
+    GroupIeee802VlanTag, GroupInterfaceCapabilities, GroupInterfaceId, GroupIpv4Tuple,
+    GroupIpv6Tuple, GroupListener, GroupTalker, GroupUserToNetworkRequirements,
+    StreamRankContainer, TimeAwareContainer, TrafficSpecificationContainer,
+    TrafficSpecificationInterval,
+};
+use crate::cnc::types::uni_types::{compute_streams, request_free_stream_id};
+use crate::cnc::types::StreamRequest;
+use crate::cnc::{Cnc, CNC_NOT_PRESENT};
+use std::io::{Error, ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream};
+use std::str::FromStr;
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use yang2::context::Context;
+use yang2::data::{
+    Data, DataFormat, DataNodeRef, DataOperation, DataParserFlags, DataTree, DataValidationFlags,
+};
+
+/// end-of-message delimiter of the NETCONF 1.0 framing (RFC 6242 4.3)
+const DELIMITER: &str = "]]>]]>";
+
+/// sessions that send a larger message are closed
+const MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+/// namespace of the NETCONF base protocol (RFC 6241)
+const NETCONF_BASE_NAMESPACE: &str = "urn:ietf:params:xml:ns:netconf:base:1.0";
+
+/// capability of the NETCONF base protocol version 1.0
+const NETCONF_BASE_CAPABILITY: &str = "urn:ietf:params:netconf:base:1.0";
+
+/// capability for event notifications (RFC 5277 3.1)
+const NETCONF_NOTIFICATION_CAPABILITY: &str = "urn:ietf:params:netconf:capability:notification:1.0";
+
+/// revision of the UNI yang-module that is advertised in the <hello>
+const UNI_REVISION: &str = "2022-10-29";
+
+/// # NETCONF Northbound
+///
+/// This Component implements the UNI of the fully centralized model as a NETCONF (RFC 6241)
+/// server. All requests of the CUC's are validated against the ieee802-dot1q-tsn-config-uni model
+/// and routed to the corresponding endpoints of the CNC.
+///
+/// The server speaks NETCONF 1.0 with the end-of-message framing on plain TCP. It doesn't
+/// implement the SSH transport of RFC 6242 itself: authentication and encryption are left to a
+/// sshd that exposes the server as its netconf-subsystem and forwards to the configured address.
+/// The server should therefore only listen on a loopback address.
+///
+/// # Operations
+/// | Operation                              | Forwarded to                 |
+/// |----------------------------------------|------------------------------|
+/// | edit-config (running) on tsn-uni       | set_streams                  |
+/// | action remove-streams on tsn-uni/cuc   | remove_streams               |
+/// | compute-streams                        | compute_streams (List)       |
+/// | compute-planned-and-modified-streams   | compute_streams (PlannedAndModified) |
+/// | compute-all-streams                    | compute_streams (All)        |
+/// | request-domain-id                      | request_domain_id            |
+/// | request-free-stream-id                 | request_free_stream_id       |
+/// | create-subscription                    | -                            |
+/// | close-session                          | -                            |
+///
+/// Streams can't be deleted by edit-config. The action remove-streams has to be used instead.
+///
+/// # Notifications
+/// A CUC subscribes with <create-subscription> (RFC 5277). The CUC identifies itself with a
+/// subtree-filter on its cuc-id:
+/// ```xml
+/// <create-subscription xmlns="urn:ietf:params:xml:ns:netconf:notification:1.0">
+///     <filter type="subtree">
+///         <cuc-id xmlns="urn:ieee:std:802.1Q:yang:ieee802-dot1q-tsn-config-uni">cuc</cuc-id>
+///     </filter>
+/// </create-subscription>
+/// ```
+/// Notifications for a CUC without active session are buffered until it subscribes.
+pub struct NetconfServerAdapter {
+    cnc: Weak<Cnc>,
+    address: String,
+    subscriptions: Arc<NotificationSubscriptions>,
+}
+
+impl NetconfServerAdapter {
+    /// The server will listen on the provided address. Example: "127.0.0.1:8830"
+    pub fn new(address: String) -> Self {
+        Self {
+            cnc: Weak::default(),
+            address,
+            subscriptions: Arc::new(NotificationSubscriptions::new()),
+        }
+    }
+}
+
+impl NorthboundAdapterInterface for NetconfServerAdapter {
+    fn compute_streams_completed(&self, notification: NotificationContent) {
+        self.subscriptions
+            .publish(NotificationKind::ComputeStreamsCompleted, notification);
+    }
+    fn configure_streams_completed(&self, notification: NotificationContent) {
+        self.subscriptions
+            .publish(NotificationKind::ConfigureStreamsCompleted, notification);
+    }
+    fn remove_streams_completed(&self, notification: NotificationContent) {
+        self.subscriptions
+            .publish(NotificationKind::RemoveStreamsCompleted, notification);
+    }
+    fn set_cnc_ref(&mut self, cnc: Weak<Cnc>) {
+        self.cnc = cnc;
+    }
+
+    fn run(&self) {
+        // these get moved to the new thread
+        let cnc = self.cnc.upgrade().expect(CNC_NOT_PRESENT).clone();
+        let subscriptions = self.subscriptions.clone();
+        let listener = TcpListener::bind(&self.address)
+            .expect("[Northbound] couldnt bind NETCONF server to the provided address");
+
+        println!("[Northbound] NETCONF server listening on {}", self.address);
+        if !listener
+            .local_addr()
+            .is_ok_and(|address| address.ip().is_loopback())
+        {
+            eprintln!(
+                "[Northbound] NETCONF server is reachable without SSH on {}, it should only listen on a loopback address behind a sshd",
+                self.address
+            );
+        }
+        thread::spawn(move || {
+            let mut session_id: u32 = 0;
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        session_id += 1;
+                        let cnc = cnc.clone();
+                        let subscriptions = subscriptions.clone();
+                        thread::spawn(move || {
+                            handle_session(cnc, subscriptions, stream, session_id)
+                        });
+                    }
+                    Err(e) => eprintln!("[Northbound] error while accepting connection {e:?}"),
+                }
+            }
+        });
+    }
+}
+
+/// error that is reported to the client as <rpc-error> (RFC 6241 4.3)
+struct RpcError {
+    error_type: &'static str,
+    error_tag: &'static str,
+    message: String,
+}
+
+impl RpcError {
+    fn new(error_type: &'static str, error_tag: &'static str, message: &str) -> Self {
+        Self {
+            error_type,
+            error_tag,
+            message: message.to_string(),
+        }
+    }
+
+    /// the provided data couldn't be parsed or validated by the yang-models
+    fn invalid_data(e: yang2::Error) -> Self {
+        Self::new("application", "invalid-value", &e.to_string())
+    }
+}
+
+/// what the session has to do after the <rpc-reply> is sent
+enum SessionAction {
+    Continue,
+    Close,
+
+    /// start pushing the notifications of the CUC with the provided cuc_id
+    Subscribe(String),
+}
+
+/// handles one NETCONF session until the client closes it.
+fn handle_session(
+    cnc: Arc<Cnc>,
+    subscriptions: Arc<NotificationSubscriptions>,
+    mut stream: TcpStream,
+    session_id: u32,
+) {
+    // replies and notifications are written from different threads
+    let writer = match stream.try_clone() {
+        Ok(writer) => Arc::new(Mutex::new(writer)),
+        Err(e) => {
+            eprintln!("[Northbound] couldnt clone NETCONF connection {e:?}");
+            return;
+        }
+    };
+
+    if let Err(e) = send_message(&mut writer.lock().unwrap(), &server_hello(session_id)) {
+        eprintln!("[Northbound] couldnt send NETCONF <hello> {e:?}");
+        return;
+    }
+
+    let mut buffer: Vec<u8> = Vec::new();
+
+    // the first message of the client has to be its <hello>
+    match read_message(&mut stream, &mut buffer) {
+        Ok(Some(hello)) => {
+            let supports_base = find_element(&hello, "hello")
+                .map(|hello| hello.content.contains(NETCONF_BASE_CAPABILITY))
+                .unwrap_or(false);
+
+            if !supports_base {
+                eprintln!("[Northbound] NETCONF client doesnt support {NETCONF_BASE_CAPABILITY}");
+                return;
+            }
+        }
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("[Northbound] couldnt read NETCONF <hello> {e:?}");
+            return;
+        }
+    }
+
+    let yang_ctx = subscriptions.yang_ctx();
+    let mut subscription: Option<(String, u64)> = None;
+
+    loop {
+        let message = match read_message(&mut stream, &mut buffer) {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("[Northbound] error while reading NETCONF message {e:?}");
+                break;
+            }
+        };
+
+        let (reply, action) = handle_rpc(&cnc, &yang_ctx, &message, subscription.is_some());
+
+        if let Err(e) = send_message(&mut writer.lock().unwrap(), &reply) {
+            eprintln!("[Northbound] couldnt send NETCONF <rpc-reply> {e:?}");
+            break;
+        }
+
+        match action {
+            SessionAction::Continue => {}
+            SessionAction::Close => break,
+            SessionAction::Subscribe(cuc_id) => {
+                // notifications are only sent after the <rpc-reply> (RFC 5277 2.1.1)
+                let (session, receiver) = subscriptions.subscribe(&cuc_id);
+                let subscriptions = subscriptions.clone();
+                let writer = writer.clone();
+                let forwarded_cuc_id = cuc_id.clone();
+                thread::spawn(move || {
+                    forward_notifications(
+                        subscriptions,
+                        writer,
+                        forwarded_cuc_id,
+                        session,
+                        receiver,
+                    )
+                });
+
+                subscription = Some((cuc_id, session));
+            }
+        }
+    }
+
+    // stops the forwarding and buffers all following notifications
+    if let Some((cuc_id, session)) = subscription {
+        subscriptions.disconnect(&cuc_id, session, None);
+    }
+}
+
+/// pushes the notifications of a subscription to the client until the session ends.
+fn forward_notifications(
+    subscriptions: Arc<NotificationSubscriptions>,
+    writer: Arc<Mutex<TcpStream>>,
+    cuc_id: String,
+    session: u64,
+    receiver: Receiver<Notification>,
+) {
+    for notification in receiver {
+        let message = match subscriptions.encode(&notification, NotificationEncoding::Xml) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("[Northbound] couldnt encode NETCONF <notification>: {e}");
+                continue;
+            }
+        };
+
+        if let Err(e) = send_message(&mut writer.lock().unwrap(), &message) {
+            eprintln!("[Northbound] couldnt send NETCONF <notification> {e:?}");
+            subscriptions.disconnect(&cuc_id, session, Some(notification));
+            return;
+        }
+    }
+}
+
+fn server_hello(session_id: u32) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+        <hello xmlns=\"{NETCONF_BASE_NAMESPACE}\">\
+        <capabilities>\
+        <capability>{NETCONF_BASE_CAPABILITY}</capability>\
+        <capability>{NETCONF_NOTIFICATION_CAPABILITY}</capability>\
+        <capability>{UNI_NAMESPACE}?module={UNI_MODULE}&amp;revision={UNI_REVISION}</capability>\
+        </capabilities>\
+        <session-id>{session_id}</session-id>\
+        </hello>"
+    )
+}
+
+/// reads the next message of the NETCONF 1.0 framing. None is returned if the client closed the
+/// connection, an error if the message exceeds MAX_MESSAGE_SIZE.
+fn read_message(stream: &mut TcpStream, buffer: &mut Vec<u8>) -> Result<Option<String>, Error> {
+    loop {
+        if let Some(position) = buffer
+            .windows(DELIMITER.len())
+            .position(|window| window == DELIMITER.as_bytes())
+        {
+            let message: Vec<u8> = buffer
+                .drain(..position + DELIMITER.len())
+                .take(position)
+                .collect();
+            return Ok(Some(String::from_utf8_lossy(&message).to_string()));
+        }
+
+        if buffer.len() > MAX_MESSAGE_SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("message exceeds {MAX_MESSAGE_SIZE} bytes"),
+            ));
+        }
+
+        let mut chunk = [0u8; 4096];
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn send_message(stream: &mut TcpStream, message: &str) -> Result<(), Error> {
+    stream.write_all(message.as_bytes())?;
+    stream.write_all(DELIMITER.as_bytes())?;
+    stream.flush()
+}
+
+/// dispatches the <rpc> to the corresponding operation. Returns the <rpc-reply> and what the
+/// session has to do afterwards.
+fn handle_rpc(
+    cnc: &Arc<Cnc>,
+    yang_ctx: &Arc<Context>,
+    message: &str,
+    subscribed: bool,
+) -> (String, SessionAction) {
+    let rpc = match find_element(message, "rpc") {
+        Some(rpc) => rpc,
+        None => {
+            let error = RpcError::new("rpc", "malformed-message", "expected <rpc>");
+            return (rpc_reply(None, &rpc_error(error)), SessionAction::Continue);
+        }
+    };
+    let message_id = attribute(rpc.start_tag, "message-id");
+
+    let operation = match first_child(rpc.content) {
+        Some(operation) => operation,
+        None => {
+            let error = RpcError::new("rpc", "missing-element", "<rpc> contains no operation");
+            return (
+                rpc_reply(message_id, &rpc_error(error)),
+                SessionAction::Continue,
+            );
+        }
+    };
+
+    let result = match operation.name {
+        "close-session" => return (rpc_reply(message_id, "<ok/>"), SessionAction::Close),
+        "create-subscription" => {
+            return match create_subscription(&operation, subscribed) {
+                Ok(cuc_id) => (
+                    rpc_reply(message_id, "<ok/>"),
+                    SessionAction::Subscribe(cuc_id),
+                ),
+                Err(error) => (
+                    rpc_reply(message_id, &rpc_error(error)),
+                    SessionAction::Continue,
+                ),
+            }
+        }
+        "edit-config" => edit_config(cnc, yang_ctx, &operation),
+        "action" => action(cnc, yang_ctx, &operation),
+        "compute-streams"
+        | "compute-planned-and-modified-streams"
+        | "compute-all-streams"
+        | "request-domain-id"
+        | "request-free-stream-id" => uni_rpc(cnc, yang_ctx, &operation),
+        _ => Err(RpcError::new(
+            "protocol",
+            "operation-not-supported",
+            &format!("<{}> is not supported", operation.name),
+        )),
+    };
+
+    let content = match result {
+        Ok(content) => content,
+        Err(error) => rpc_error(error),
+    };
+
+    (rpc_reply(message_id, &content), SessionAction::Continue)
+}
+
+fn rpc_reply(message_id: Option<String>, content: &str) -> String {
+    match message_id {
+        Some(message_id) => format!(
+            "<rpc-reply message-id=\"{}\" xmlns=\"{NETCONF_BASE_NAMESPACE}\">{content}</rpc-reply>",
+            escape(&message_id)
+        ),
+        None => format!("<rpc-reply xmlns=\"{NETCONF_BASE_NAMESPACE}\">{content}</rpc-reply>"),
+    }
+}
+
+fn rpc_error(error: RpcError) -> String {
+    format!(
+        "<rpc-error>\
+        <error-type>{}</error-type>\
+        <error-tag>{}</error-tag>\
+        <error-severity>error</error-severity>\
+        <error-message>{}</error-message>\
+        </rpc-error>",
+        error.error_type,
+        error.error_tag,
+        escape(&error.message)
+    )
+}
+
+/// a leaf of the output of an UNI operation
+fn output_leaf(name: &str, value: &str) -> String {
+    format!(
+        "<{name} xmlns=\"{UNI_NAMESPACE}\">{}</{name}>",
+        escape(value)
+    )
+}
+
+/// <create-subscription> (RFC 5277 2.1.1). Returns the cuc_id of the filter.
+fn create_subscription(operation: &Element, subscribed: bool) -> Result<String, RpcError> {
+    if subscribed {
+        return Err(RpcError::new(
+            "protocol",
+            "in-use",
+            "a subscription is already active for this session",
+        ));
+    }
+
+    if let Some(stream) = find_element(operation.content, "stream") {
+        if stream.content.trim() != "NETCONF" {
+            return Err(RpcError::new(
+                "application",
+                "invalid-value",
+                "only the stream NETCONF is supported",
+            ));
+        }
+    }
+
+    let cuc_id = find_element(operation.content, "filter")
+        .and_then(|filter| find_element(filter.content, "cuc-id"))
+        .map(|cuc_id| cuc_id.content.trim().to_string())
+        .filter(|cuc_id| !cuc_id.is_empty());
+
+    cuc_id.ok_or(RpcError::new(
+        "protocol",
+        "missing-element",
+        "the subscription has to be filtered by the cuc-id of the CUC",
+    ))
+}
+
+/// <edit-config> on the running datastore. All streams in the tsn-uni tree are inserted or
+/// replaced for their cuc.
+fn edit_config(
+    cnc: &Arc<Cnc>,
+    yang_ctx: &Arc<Context>,
+    operation: &Element,
+) -> Result<String, RpcError> {
+    let target = find_element(operation.content, "target")
+        .and_then(|target| first_child(target.content))
+        .map(|datastore| datastore.name);
+
+    if target != Some("running") {
+        return Err(RpcError::new(
+            "protocol",
+            "invalid-value",
+            "only the running datastore is supported",
+        ));
+    }
+
+    let config = find_element(operation.content, "config").ok_or(RpcError::new(
+        "protocol",
+        "missing-element",
+        "<edit-config> contains no <config>",
+    ))?;
+
+    if has_operation(config.content, "delete") || has_operation(config.content, "remove") {
+        return Err(RpcError::new(
+            "protocol",
+            "operation-not-supported",
+            "streams have to be removed with the action remove-streams",
+        ));
+    }
+
+    let dtree = DataTree::parse_string(
+        yang_ctx,
+        config.content.trim(),
+        DataFormat::XML,
+        DataParserFlags::NO_STATE,
+        DataValidationFlags::NO_STATE | DataValidationFlags::PRESENT,
+    )
+    .map_err(RpcError::invalid_data)?;
+
+    // collect everything first so nothing is stored if a single stream is invalid
+    let mut requests: Vec<(String, Vec<StreamRequest>)> = Vec::new();
+    let cuc_path = format!("/{UNI_MODULE}:tsn-uni/domain/cuc");
+
+    for cuc in dtree
+        .find_xpath(&cuc_path)
+        .map_err(RpcError::invalid_data)?
+    {
+        let domain_id = cuc
+            .parent()
+            .and_then(|domain| leaf(&domain, "domain-id"))
+            .unwrap_or_default();
+        if domain_id != cnc.domain {
+            return Err(RpcError::new(
+                "application",
+                "invalid-value",
+                &format!("unknown domain {domain_id}"),
+            ));
+        }
+
+        let mut streams: Vec<StreamRequest> = Vec::new();
+        for stream in children(&cuc, "stream") {
+            streams.push(stream_request(&stream)?);
+        }
+
+        requests.push((leaf(&cuc, "cuc-id").unwrap_or_default(), streams));
+    }
+
+    for (cuc_id, streams) in requests {
+        if !streams.is_empty() {
+            cnc.set_streams(&cuc_id, streams);
+        }
+    }
+
+    Ok(String::from("<ok/>"))
+}
+
+/// <action> (RFC 7950 7.15.2). The only action of the UNI is remove-streams.
+fn action(
+    cnc: &Arc<Cnc>,
+    yang_ctx: &Arc<Context>,
+    operation: &Element,
+) -> Result<String, RpcError> {
+    let dtree = DataTree::parse_op_string(
+        yang_ctx,
+        operation.content.trim(),
+        DataFormat::XML,
+        DataOperation::RpcYang,
+    )
+    .map_err(RpcError::invalid_data)?;
+
+    let cuc_path = format!("/{UNI_MODULE}:tsn-uni/domain/cuc");
+    for cuc in dtree
+        .find_xpath(&cuc_path)
+        .map_err(RpcError::invalid_data)?
+    {
+        if let Ok(remove_streams) = cuc.find_path("remove-streams") {
+            let domain_id = cuc
+                .parent()
+                .and_then(|domain| leaf(&domain, "domain-id"))
+                .unwrap_or_default();
+            if domain_id != cnc.domain {
+                return Err(RpcError::new(
+                    "application",
+                    "invalid-value",
+                    &format!("unknown domain {domain_id}"),
+                ));
+            }
+
+            let cuc_id = leaf(&cuc, "cuc-id").unwrap_or_default();
+            let stream_ids: Vec<String> = leaf_list(&remove_streams, "stream-id");
+            let result = cnc.remove_streams(&cuc_id, stream_ids);
+
+            return Ok(output_leaf("result", &result));
+        }
+    }
+
+    Err(RpcError::new(
+        "protocol",
+        "operation-not-supported",
+        "the requested action is not supported",
+    ))
+}
+
+/// rpcs of the ieee802-dot1q-tsn-config-uni model
+fn uni_rpc(
+    cnc: &Arc<Cnc>,
+    yang_ctx: &Arc<Context>,
+    operation: &Element,
+) -> Result<String, RpcError> {
+    let dtree = DataTree::parse_op_string(
+        yang_ctx,
+        operation.raw,
+        DataFormat::XML,
+        DataOperation::RpcYang,
+    )
+    .map_err(RpcError::invalid_data)?;
+
+    let input = dtree
+        .find_path(&format!("/{UNI_MODULE}:{}", operation.name))
+        .map_err(RpcError::invalid_data)?;
+
+    match operation.name {
+        "request-domain-id" => {
+            let cuc_id = leaf(&input, "cuc-id").unwrap_or_default();
+            let domain_id = cnc.request_domain_id(cuc_id);
+            Ok(output_leaf("domain-id", &domain_id))
+        }
+        "request-free-stream-id" => {
+            let stream_id = cnc.request_free_stream_id(request_free_stream_id::Input {
+                domain_id: leaf(&input, "domain-id").unwrap_or_default(),
+                cuc_id: leaf(&input, "cuc-id").unwrap_or_default(),
+            });
+            Ok(output_leaf("stream-id", &stream_id))
+        }
+        _ => {
+            let domains: compute_streams::Input = children(&input, "domain")
+                .iter()
+                .map(|domain| compute_streams::Domain {
+                    domain_id: leaf(domain, "domain-id").unwrap_or_default(),
+                    cuc: children(domain, "cuc")
+                        .iter()
+                        .map(|cuc| compute_streams::CucElement {
+                            cuc_id: leaf(cuc, "cuc-id").unwrap_or_default(),
+                            stream_list: match operation.name {
+                                "compute-streams" => Some(leaf_list(cuc, "stream-list")),
+                                _ => None,
+                            },
+                        })
+                        .collect(),
+                })
+                .collect();
+
+            let computation = match operation.name {
+                "compute-streams" => ComputationType::List(domains),
+                "compute-planned-and-modified-streams" => {
+                    ComputationType::PlannedAndModified(domains)
+                }
+                _ => ComputationType::All(domains),
+            };
+
+            let result = cnc.compute_streams(computation);
+            Ok(output_leaf("result", &result))
+        }
+    }
+}
+
+/// converts a validated stream-node of the tsn-uni tree into a StreamRequest
+fn stream_request(stream: &DataNodeRef) -> Result<StreamRequest, RpcError> {
+    let stream_id = leaf(stream, "stream-id").unwrap_or_default();
+    let talker = match stream.find_path("talker") {
+        Ok(talker) => group_talker(&talker)?,
+        Err(_) => {
+            return Err(RpcError::new(
+                "application",
+                "missing-element",
+                &format!("stream {stream_id} contains no talker"),
+            ))
+        }
+    };
+
+    let listeners: Vec<GroupListener> = children(stream, "listener")
+        .iter()
+        .map(|listener| GroupListener {
+            index: leaf_or(listener, "index", 0),
+            end_station_interfaces: children(listener, "end-station-interfaces")
+                .iter()
+                .map(group_interface_id)
+                .collect(),
+            user_to_network_requirements: group_user_to_network_requirements(listener),
+            interface_capabilities: group_interface_capabilities(listener),
+        })
+        .collect();
+
+    Ok(StreamRequest {
+        stream_id,
+        talker,
+        listeners,
+    })
+}
+
+/// the talker is rejected without an interval, since the streams can't be scheduled without it
+fn group_talker(talker: &DataNodeRef) -> Result<GroupTalker, RpcError> {
+    let numerator = required_leaf(talker, "traffic-specification/interval/numerator")?;
+    let denominator = required_leaf(talker, "traffic-specification/interval/denominator")?;
+    if denominator == 0 {
+        return Err(RpcError::new(
+            "application",
+            "invalid-value",
+            "the denominator of the interval must not be 0",
+        ));
+    }
+
+    Ok(GroupTalker {
+        stream_rank: StreamRankContainer {
+            rank: leaf_or(talker, "stream-rank/rank", 1),
+        },
+        end_station_interfaces: children(talker, "end-station-interfaces")
+            .iter()
+            .map(group_interface_id)
+            .collect(),
+        data_frame_specification: children(talker, "data-frame-specification")
+            .iter()
+            .filter_map(|element| {
+                Some(DataFrameSpecificationElement {
+                    index: leaf_or(element, "index", 0),
+                    field: data_frame_field(element)?,
+                })
+            })
+            .collect(),
+        traffic_specification: TrafficSpecificationContainer {
+            interval: TrafficSpecificationInterval {
+                numerator,
+                denominator,
+            },
+            max_frames_per_interval: leaf_or(
+                talker,
+                "traffic-specification/max-frames-per-interval",
+                0,
+            ),
+            max_frame_size: leaf_or(talker, "traffic-specification/max-frame-size", 0),
+            transmission_selection: leaf_or(
+                talker,
+                "traffic-specification/transmission-selection",
+                0,
+            ),
+            time_aware: TimeAwareContainer {
+                earliest_transmit_offset: leaf_or(
+                    talker,
+                    "traffic-specification/time-aware/earliest-transmit-offset",
+                    0,
+                ),
+                latest_transmit_offset: leaf_or(
+                    talker,
+                    "traffic-specification/time-aware/latest-transmit-offset",
+                    0,
+                ),
+                jitter: leaf_or(talker, "traffic-specification/time-aware/jitter", 0),
+            },
+        },
+        user_to_network_requirements: group_user_to_network_requirements(talker),
+        interface_capabilities: group_interface_capabilities(talker),
+    })
+}
+
+fn group_interface_id(interface: &DataNodeRef) -> GroupInterfaceId {
+    GroupInterfaceId {
+        mac_address: leaf(interface, "mac-address").unwrap_or_default(),
+        interface_name: leaf(interface, "interface-name").unwrap_or_default(),
+    }
+}
+
+fn group_user_to_network_requirements(parent: &DataNodeRef) -> GroupUserToNetworkRequirements {
+    GroupUserToNetworkRequirements {
+        num_seemless_trees: leaf_or(parent, "user-to-network-requirements/num-seamless-trees", 1),
+        max_latency: leaf_or(parent, "user-to-network-requirements/max-latency", 0),
+    }
+}
+
+fn group_interface_capabilities(parent: &DataNodeRef) -> GroupInterfaceCapabilities {
+    GroupInterfaceCapabilities {
+        vlan_tag_capable: leaf_or(parent, "interface-capabilities/vlan-tag-capable", false),
+        cb_stream_iden_type_list: leaf_list(
+            parent,
+            "interface-capabilities/cb-stream-iden-type-list",
+        ),
+        cb_sequence_type_list: leaf_list(parent, "interface-capabilities/cb-sequence-type-list"),
+    }
+}
+
+/// the case of the choice field of a data-frame-specification entry
+fn data_frame_field(element: &DataNodeRef) -> Option<DataFrameSpecificationElementType> {
+    if let Ok(field) = element.find_path("ieee802-mac-addresses") {
+        return Some(DataFrameSpecificationElementType::Ieee802MacAddresses(
+            GroupIeee802MacAddress {
+                destination_mac_adress: leaf(&field, "destination-mac-address").unwrap_or_default(),
+                source_mac_adress: leaf(&field, "source-mac-address").unwrap_or_default(),
+            },
+        ));
+    }
+
+    if let Ok(field) = element.find_path("ieee802-vlan-tag") {
+        return Some(DataFrameSpecificationElementType::Ieee802VlanTag(
+            GroupIeee802VlanTag {
+                priority_code_point: leaf_or(&field, "priority-code-point", 0),
+                vlan_id: leaf_or(&field, "vlan-id", 0),
+            },
+        ));
+    }
+
+    if let Ok(field) = element.find_path("ipv4-tuple") {
+        return Some(DataFrameSpecificationElementType::Ipv4Tuple(
+            GroupIpv4Tuple {
+                source_ip_adress: leaf_or(&field, "source-ip-address", Ipv4Addr::UNSPECIFIED),
+                destination_ip_adress: leaf_or(
+                    &field,
+                    "destination-ip-address",
+                    Ipv4Addr::UNSPECIFIED,
+                ),
+                dscp: leaf_or(&field, "dscp", 0),
+                protocol: leaf_or(&field, "protocol", 0),
+                source_port: leaf_or(&field, "source-port", 0),
+                destination_port: leaf_or(&field, "destination-port", 0),
+            },
+        ));
+    }
+
+    if let Ok(field) = element.find_path("ipv6-tuple") {
+        return Some(DataFrameSpecificationElementType::Ipv6Tuple(
+            GroupIpv6Tuple {
+                source_ip_adress: leaf_or(&field, "source-ip-address", Ipv6Addr::UNSPECIFIED),
+                destination_ip_adress: leaf_or(
+                    &field,
+                    "destination-ip-address",
+                    Ipv6Addr::UNSPECIFIED,
+                ),
+                dscp: leaf_or(&field, "dscp", 0),
+                protocol: leaf_or(&field, "protocol", 0),
+                source_port: leaf_or(&field, "source-port", 0),
+                destination_port: leaf_or(&field, "destination-port", 0),
+            },
+        ));
+    }
+
+    None
+}
+
+/// canonical value of the leaf at the relative path
+fn leaf(dnode: &DataNodeRef, path: &str) -> Option<String> {
+    dnode
+        .find_path(path)
+        .ok()
+        .and_then(|leaf| leaf.value_canonical())
+}
+
+/// value of the leaf at the relative path or the default if it isnt present
+fn leaf_or<T: FromStr>(dnode: &DataNodeRef, path: &str, default: T) -> T {
+    leaf(dnode, path)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// value of the leaf at the relative path or a missing-element error if it isnt present
+fn required_leaf<T: FromStr>(dnode: &DataNodeRef, path: &str) -> Result<T, RpcError> {
+    leaf(dnode, path)
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| {
+            RpcError::new(
+                "application",
+                "missing-element",
+                &format!("{path} is required"),
+            )
+        })
+}
+
+/// all values of the leaf-list at the relative path
+fn leaf_list<T: FromStr>(dnode: &DataNodeRef, path: &str) -> Vec<T> {
+    match dnode.find_xpath(path) {
+        Ok(set) => set
+            .filter_map(|leaf| leaf.value_canonical())
+            .filter_map(|value| value.parse().ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// all nodes at the relative path
+fn children<'a>(dnode: &'a DataNodeRef, path: &str) -> Vec<DataNodeRef<'a>> {
+    match dnode.find_xpath(path) {
+        Ok(set) => set.collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// an element of a xml-message.
+///
+/// This is only used to extract the envelope of the NETCONF messages. The content itself is parsed
+/// by libyang.
+struct Element<'a> {
+    /// name without the prefix
+    name: &'a str,
+    start_tag: &'a str,
+    content: &'a str,
+    /// the whole element including its tags
+    raw: &'a str,
+}
+
+/// positions of all start-tags in the xml
+fn start_tags(xml: &str) -> Vec<usize> {
+    xml.match_indices('<')
+        .map(|(position, _)| position)
+        .filter(|position| {
+            !matches!(
+                xml[position + 1..].chars().next(),
+                Some('?' | '!' | '/') | None
+            )
+        })
+        .collect()
+}
+
+/// parses the element starting at the provided position. The end-tag is searched from the back,
+/// so this only works if the element isnt followed by elements with the same name.
+fn element_at(xml: &str, start: usize) -> Option<Element<'_>> {
+    let tag_end = start + xml[start..].find('>')?;
+    let start_tag = &xml[start..=tag_end];
+    let qualified_name_end =
+        1 + start_tag[1..].find(|c: char| c.is_whitespace() || c == '>' || c == '/')?;
+    let qualified_name = &start_tag[1..qualified_name_end];
+    let name = qualified_name.rsplit(':').next().unwrap_or(qualified_name);
+
+    if start_tag.ends_with("/>") {
+        return Some(Element {
+            name,
+            start_tag,
+            content: "",
+            raw: start_tag,
+        });
+    }
+
+    let end_tag = format!("</{qualified_name}");
+    let end_tag_start = tag_end + 1 + xml[tag_end + 1..].rfind(&end_tag)?;
+    let end_tag_end = end_tag_start + xml[end_tag_start..].find('>')?;
+
+    Some(Element {
+        name,
+        start_tag,
+        content: &xml[tag_end + 1..end_tag_start],
+        raw: &xml[start..=end_tag_end],
+    })
+}
+
+/// the first element with the provided name (without prefix)
+fn find_element<'a>(xml: &'a str, name: &str) -> Option<Element<'a>> {
+    start_tags(xml)
+        .into_iter()
+        .filter_map(|start| element_at(xml, start))
+        .find(|element| element.name == name)
+}
+
+/// the first child-element of the provided content
+fn first_child(xml: &str) -> Option<Element<'_>> {
+    start_tags(xml)
+        .first()
+        .and_then(|start| element_at(xml, *start))
+}
+
+/// value of an attribute in a start-tag
+fn attribute(start_tag: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let pattern = format!(" {name}={quote}");
+        if let Some(position) = start_tag.find(&pattern) {
+            let value_start = position + pattern.len();
+            let value_end = value_start + start_tag[value_start..].find(quote)?;
+            return Some(start_tag[value_start..value_end].to_string());
+        }
+    }
+
+    None
+}
+
+/// checks if any element in the xml uses the provided edit-operation (RFC 6241 7.2)
+fn has_operation(xml: &str, operation: &str) -> bool {
+    xml.contains(&format!("operation=\"{operation}\""))
+        || xml.contains(&format!("operation='{operation}'"))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,329 @@
+use super::{UNI_MODULE, UNI_YANG_MODULES};
+use crate::cnc::southbound::init_yang_ctx;
+use crate::cnc::types::notification_types::{Cuc, NotificationContent};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use yang2::context::Context;
+use yang2::data::{Data, DataFormat, DataPrinterFlags, DataTree};
+
+/// maximum number of notifications that are kept for a disconnected CUC. If more notifications
+/// arrive, the oldest ones are dropped.
+const MAX_BUFFERED_NOTIFICATIONS: usize = 1000;
+
+/// namespace of the <notification> envelope (RFC 5277 4)
+const NETCONF_NOTIFICATION_NAMESPACE: &str = "urn:ietf:params:xml:ns:netconf:notification:1.0";
+
+/// the notifications specified in the ieee802-dot1q-tsn-config-uni model
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationKind {
+    ComputeStreamsCompleted,
+    ConfigureStreamsCompleted,
+    RemoveStreamsCompleted,
+}
+
+impl NotificationKind {
+    /// name of the notification in the yang-model
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotificationKind::ComputeStreamsCompleted => "compute-streams-completed",
+            NotificationKind::ConfigureStreamsCompleted => "configure-streams-completed",
+            NotificationKind::RemoveStreamsCompleted => "remove-streams-completed",
+        }
+    }
+}
+
+/// encoding of the notifications as used by the transport
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationEncoding {
+    /// XML as used by NETCONF (RFC 5277)
+    Xml,
+
+    /// JSON (RFC 7951) as used by RESTCONF (RFC 8040 6.4)
+    Json,
+}
+
+/// a notification for exactly one CUC.
+#[derive(Debug)]
+pub struct Notification {
+    pub kind: NotificationKind,
+
+    /// date-and-time when the notification was generated
+    pub event_time: String,
+    pub domain_id: String,
+    pub cuc: Cuc,
+}
+
+/// registration of a CUC. If no transport is connected, the notifications are buffered.
+struct Subscription {
+    session: Option<(u64, Sender<Notification>)>,
+    buffer: VecDeque<Notification>,
+}
+
+/// # Notification Subscriptions
+///
+/// Each CUC registers with its cuc_id for the notifications of the UNI. The notifications are
+/// split up per CUC, so every CUC only receives the status of its own streams.
+///
+/// The transport of the northbound gets a Receiver for each subscribed CUC and pushes the
+/// received notifications to the CUC. While a CUC has no transport connected, either because it
+/// didn't subscribe yet or because its transport disconnected, all notifications are buffered and
+/// delivered as soon as the CUC subscribes.
+pub struct NotificationSubscriptions {
+    yang_ctx: Arc<Context>,
+    subscriptions: Mutex<HashMap<String, Subscription>>,
+    next_session: Mutex<u64>,
+}
+
+impl NotificationSubscriptions {
+    /// loads the yang-models of the UNI which are used to encode the notifications.
+    pub fn new() -> Self {
+        Self {
+            yang_ctx: init_yang_ctx(&UNI_YANG_MODULES.to_vec()),
+            subscriptions: Mutex::new(HashMap::new()),
+            next_session: Mutex::new(0),
+        }
+    }
+
+    /// yang-context with all models of the UNI loaded
+    pub fn yang_ctx(&self) -> Arc<Context> {
+        self.yang_ctx.clone()
+    }
+
+    /// registers the transport of a CUC. All buffered notifications are delivered first.
+    ///
+    /// An already existing transport of this CUC gets replaced. The returned session-id has to be
+    /// provided when the transport disconnects.
+    pub fn subscribe(&self, cuc_id: &str) -> (u64, Receiver<Notification>) {
+        let mut next_session = self.next_session.lock().unwrap();
+        *next_session += 1;
+        let session = *next_session;
+        drop(next_session);
+
+        let (sender, receiver) = mpsc::channel();
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let subscription =
+            subscriptions
+                .entry(cuc_id.to_string())
+                .or_insert_with(|| Subscription {
+                    session: None,
+                    buffer: VecDeque::new(),
+                });
+
+        for notification in subscription.buffer.drain(..) {
+            // the receiver is still in scope, so this cant fail
+            let _ = sender.send(notification);
+        }
+        subscription.session = Some((session, sender));
+
+        println!("[Northbound] CUC {cuc_id} subscribed for notifications");
+        (session, receiver)
+    }
+
+    /// the transport of the session disconnected. Notifications are buffered from now on.
+    ///
+    /// A notification that couldn't be delivered by the transport can be provided to buffer it
+    /// again.
+    pub fn disconnect(&self, cuc_id: &str, session: u64, undelivered: Option<Notification>) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if let Some(subscription) = subscriptions.get_mut(cuc_id) {
+            if let Some(notification) = undelivered {
+                subscription.buffer.push_front(notification);
+            }
+
+            let is_current_session =
+                matches!(&subscription.session, Some((id, _)) if *id == session);
+            if is_current_session {
+                subscription.session = None;
+                println!("[Northbound] CUC {cuc_id} disconnected, buffering notifications");
+            }
+        }
+    }
+
+    /// splits the content per CUC and delivers or buffers it. The notifications of a CUC that
+    /// never subscribed are buffered until it subscribes for the first time.
+    pub fn publish(&self, kind: NotificationKind, content: NotificationContent) {
+        let event_time = event_time();
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+
+        for domain in content {
+            for cuc in domain.cucs {
+                let subscription =
+                    subscriptions
+                        .entry(cuc.cuc_id.clone())
+                        .or_insert_with(|| Subscription {
+                            session: None,
+                            buffer: VecDeque::new(),
+                        });
+
+                let notification = Notification {
+                    kind,
+                    event_time: event_time.clone(),
+                    domain_id: domain.domain_id.clone(),
+                    cuc,
+                };
+
+                let undelivered = match &subscription.session {
+                    Some((_, sender)) => match sender.send(notification) {
+                        Ok(_) => None,
+                        Err(e) => Some(e.0),
+                    },
+                    None => Some(notification),
+                };
+
+                if let Some(notification) = undelivered {
+                    subscription.session = None;
+                    buffer_notification(&mut subscription.buffer, notification);
+                }
+            }
+        }
+    }
+
+    /// encodes the notification including its envelope. An error is returned if the ids can't be
+    /// encoded, e.g. because they contain both kinds of quotes.
+    ///
+    /// # XML
+    /// ```xml
+    /// <notification xmlns="urn:ietf:params:xml:ns:netconf:notification:1.0">
+    ///     <eventTime>2024-01-01T00:00:00.000000Z</eventTime>
+    ///     <compute-streams-completed xmlns="urn:ieee:std:802.1Q:yang:ieee802-dot1q-tsn-config-uni">
+    ///         ...
+    ///     </compute-streams-completed>
+    /// </notification>
+    /// ```
+    ///
+    /// # JSON
+    /// ```json
+    /// {
+    ///     "ietf-restconf:notification": {
+    ///         "eventTime": "2024-01-01T00:00:00.000000Z",
+    ///         "ieee802-dot1q-tsn-config-uni:compute-streams-completed": { ... }
+    ///     }
+    /// }
+    /// ```
+    pub fn encode(
+        &self,
+        notification: &Notification,
+        encoding: NotificationEncoding,
+    ) -> Result<String, String> {
+        let mut dtree = DataTree::new(&self.yang_ctx);
+        let cuc_path = format!(
+            "/{}:{}/domain[domain-id={}]/cuc[cuc-id={}]",
+            UNI_MODULE,
+            notification.kind.name(),
+            xpath_literal(&notification.domain_id)?,
+            xpath_literal(&notification.cuc.cuc_id)?
+        );
+
+        dtree
+            .new_path(&cuc_path, None, false)
+            .map_err(|e| format!("couldnt create notification in dtree: {e}"))?;
+
+        for stream in notification.cuc.streams.iter() {
+            let failure_code_path = format!(
+                "{}/stream[stream-id={}]/failure-code",
+                cuc_path,
+                xpath_literal(&stream.stream_id)?
+            );
+            dtree
+                .new_path(
+                    &failure_code_path,
+                    Some(&stream.failure_code.to_string()),
+                    false,
+                )
+                .map_err(|e| format!("couldnt add stream to notification in dtree: {e}"))?;
+        }
+
+        let format = match encoding {
+            NotificationEncoding::Xml => DataFormat::XML,
+            NotificationEncoding::Json => DataFormat::JSON,
+        };
+        let content = dtree
+            .print_string(format, DataPrinterFlags::WITH_SIBLINGS)
+            .map_err(|e| format!("couldnt print notification: {e}"))?
+            .unwrap_or_default();
+
+        let message = match encoding {
+            NotificationEncoding::Xml => format!(
+                "<notification xmlns=\"{}\"><eventTime>{}</eventTime>{}</notification>",
+                NETCONF_NOTIFICATION_NAMESPACE, notification.event_time, content
+            ),
+            NotificationEncoding::Json => {
+                let mut body: serde_json::Map<String, Value> =
+                    serde_json::from_str(&content).unwrap_or_default();
+                body.insert(
+                    String::from("eventTime"),
+                    Value::String(notification.event_time.clone()),
+                );
+
+                serde_json::json!({ "ietf-restconf:notification": body }).to_string()
+            }
+        };
+
+        Ok(message)
+    }
+}
+
+/// the value as literal of a xpath predicate. XPath 1.0 has no escaping, so the value is quoted
+/// with the kind of quotes it doesn't contain.
+fn xpath_literal(value: &str) -> Result<String, String> {
+    if !value.contains('\'') {
+        Ok(format!("'{value}'"))
+    } else if !value.contains('"') {
+        Ok(format!("\"{value}\""))
+    } else {
+        Err(format!("{value} contains both kinds of quotes"))
+    }
+}
+
+fn buffer_notification(buffer: &mut VecDeque<Notification>, notification: Notification) {
+    if buffer.len() >= MAX_BUFFERED_NOTIFICATIONS {
+        if let Some(dropped) = buffer.pop_front() {
+            eprintln!(
+                "[Northbound] notification buffer of CUC {} is full, dropping <{}>",
+                dropped.cuc.cuc_id,
+                dropped.kind.name()
+            );
+        }
+    }
+
+    buffer.push_back(notification);
+}
+
+/// current time as date-and-time (RFC 3339) in UTC
+fn event_time() -> String {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let seconds_of_day = seconds % 86400;
+
+    // civil date from the days since epoch (http://howardhinnant.github.io/date_algorithms.html)
+    let days = (seconds / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        (seconds_of_day % 3600) / 60,
+        seconds_of_day % 60,
+        since_epoch.subsec_micros()
+    )
+}
@@ -0,0 +1,667 @@
+use super::notifications::{NotificationEncoding, NotificationKind, NotificationSubscriptions};
+use super::{NorthboundAdapterInterface, NorthboundControllerInterface, UNI_MODULE};
+use crate::cnc::types::computation::ComputationType;
+use crate::cnc::types::notification_types::NotificationContent;
+use crate::cnc::types::uni_types::{compute_streams, request_free_stream_id};
+use crate::cnc::types::StreamRequest;
+use crate::cnc::{Cnc, CNC_NOT_PRESENT};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Weak};
+use std::thread;
+use std::time::Duration;
+
+/// root of the RESTCONF API (RFC 8040 3.1)
+const RESTCONF_ROOT: &str = "/restconf";
+
+/// media type for all responses (RFC 8040 11.3.2)
+const YANG_DATA_JSON: &str = "application/yang-data+json";
+
+/// root of the notification event streams (RFC 8040 6.3)
+const STREAMS_ROOT: &str = "/restconf/streams";
+
+/// requests with a larger body are rejected without reading it
+const MAX_CONTENT_LENGTH: usize = 1024 * 1024;
+
+/// clients that don't send their complete request within this time are disconnected
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// # RESTCONF Northbound
+///
+/// This Component implements the UNI of the fully centralized model as a RESTCONF (RFC 8040)
+/// server. All requests of the CUC's are routed to the corresponding endpoints of the CNC.
+///
+/// The resources follow the tree of the ieee802-dot1q-tsn-config-uni model. The payloads are JSON
+/// encoded as described in RFC 7951 with the member names of the model, the top-level members are
+/// module-qualified. The members of groupings and the cases of choices appear directly in their
+/// parent, like in the storage-component.
+///
+/// # Resources
+/// | Method          | Path                                                     |
+/// |-----------------|----------------------------------------------------------|
+/// | GET             | /restconf/data/ieee802-dot1q-tsn-config-uni:tsn-uni/domain={domain-id}/cuc={cuc-id} |
+/// | POST            | /restconf/data/ieee802-dot1q-tsn-config-uni:tsn-uni/domain={domain-id}/cuc={cuc-id} |
+/// | GET/PUT/DELETE  | /restconf/data/ieee802-dot1q-tsn-config-uni:tsn-uni/domain={domain-id}/cuc={cuc-id}/stream={stream-id} |
+/// | POST            | /restconf/data/ieee802-dot1q-tsn-config-uni:tsn-uni/domain={domain-id}/cuc={cuc-id}/remove-streams |
+/// | POST            | /restconf/operations/ieee802-dot1q-tsn-config-uni:compute-streams |
+/// | POST            | /restconf/operations/ieee802-dot1q-tsn-config-uni:compute-planned-and-modified-streams |
+/// | POST            | /restconf/operations/ieee802-dot1q-tsn-config-uni:compute-all-streams |
+/// | POST            | /restconf/operations/ieee802-dot1q-tsn-config-uni:request-domain-id |
+/// | POST            | /restconf/operations/ieee802-dot1q-tsn-config-uni:request-free-stream-id |
+/// | GET             | /restconf/streams/ieee802-dot1q-tsn-config-uni/json?cuc-id={cuc-id} |
+/// | GET             | /restconf/streams/ieee802-dot1q-tsn-config-uni/xml?cuc-id={cuc-id} |
+///
+/// # Notifications
+/// The notifications are delivered as server-sent events (RFC 8040 6.4). Each CUC subscribes to
+/// the event stream with its cuc-id and only receives the status of its own streams. While the
+/// CUC isnt connected, its notifications are buffered.
+pub struct RestconfAdapter {
+    cnc: Weak<Cnc>,
+    address: String,
+    subscriptions: Arc<NotificationSubscriptions>,
+}
+
+impl RestconfAdapter {
+    /// The server will listen on the provided address. Example: "0.0.0.0:8040"
+    pub fn new(address: String) -> Self {
+        Self {
+            cnc: Weak::default(),
+            address,
+            subscriptions: Arc::new(NotificationSubscriptions::new()),
+        }
+    }
+}
+
+impl NorthboundAdapterInterface for RestconfAdapter {
+    fn compute_streams_completed(&self, notification: NotificationContent) {
+        self.subscriptions
+            .publish(NotificationKind::ComputeStreamsCompleted, notification);
+    }
+    fn configure_streams_completed(&self, notification: NotificationContent) {
+        self.subscriptions
+            .publish(NotificationKind::ConfigureStreamsCompleted, notification);
+    }
+    fn remove_streams_completed(&self, notification: NotificationContent) {
+        self.subscriptions
+            .publish(NotificationKind::RemoveStreamsCompleted, notification);
+    }
+    fn set_cnc_ref(&mut self, cnc: Weak<Cnc>) {
+        self.cnc = cnc;
+    }
+
+    fn run(&self) {
+        // these get moved to the new thread
+        let cnc = self.cnc.upgrade().expect(CNC_NOT_PRESENT).clone();
+        let subscriptions = self.subscriptions.clone();
+        let listener = TcpListener::bind(&self.address)
+            .expect("[Northbound] couldnt bind RESTCONF server to the provided address");
+
+        println!("[Northbound] RESTCONF server listening on {}", self.address);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let cnc = cnc.clone();
+                        let subscriptions = subscriptions.clone();
+                        thread::spawn(move || handle_connection(cnc, subscriptions, stream));
+                    }
+                    Err(e) => eprintln!("[Northbound] error while accepting connection {e:?}"),
+                }
+            }
+        });
+    }
+}
+
+/// a parsed HTTP/1.1 request. Only the parts needed for RESTCONF are kept.
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: String,
+    body: String,
+}
+
+/// a HTTP response with an optional JSON body.
+struct HttpResponse {
+    status: u16,
+    body: Option<Value>,
+}
+
+impl HttpResponse {
+    fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            body: Some(body),
+        }
+    }
+
+    fn no_content() -> Self {
+        Self {
+            status: 204,
+            body: None,
+        }
+    }
+
+    /// error reporting as described in RFC 8040 7.1
+    fn error(status: u16, error_tag: &str, message: &str) -> Self {
+        Self {
+            status,
+            body: Some(json!({
+                "ietf-restconf:errors": {
+                    "error": [{
+                        "error-type": "application",
+                        "error-tag": error_tag,
+                        "error-message": message,
+                    }]
+                }
+            })),
+        }
+    }
+}
+
+/// resources of the tsn-uni tree that can be addressed by a request
+enum UniResource {
+    Cuc {
+        domain_id: String,
+        cuc_id: String,
+    },
+    Stream {
+        domain_id: String,
+        cuc_id: String,
+        stream_id: String,
+    },
+    RemoveStreams {
+        domain_id: String,
+        cuc_id: String,
+    },
+}
+
+/// handles exactly one request per connection and closes it afterwards.
+///
+/// Requests for an event stream keep the connection open as long as the client is connected.
+fn handle_connection(
+    cnc: Arc<Cnc>,
+    subscriptions: Arc<NotificationSubscriptions>,
+    mut stream: TcpStream,
+) {
+    if let Err(e) = stream.set_read_timeout(Some(READ_TIMEOUT)) {
+        eprintln!("[Northbound] couldnt set the RESTCONF read timeout {e:?}");
+        return;
+    }
+
+    let response = match read_request(&stream) {
+        Ok(request) => match request.path.strip_prefix(STREAMS_ROOT) {
+            Some(event_stream) if request.method == "GET" => {
+                match parse_event_stream(event_stream, &request.query) {
+                    Ok((cuc_id, encoding)) => {
+                        return stream_notifications(&subscriptions, stream, &cuc_id, encoding)
+                    }
+                    Err(response) => response,
+                }
+            }
+            _ => route(&cnc, request),
+        },
+        Err(response) => response,
+    };
+
+    if let Err(e) = write_response(&mut stream, response) {
+        eprintln!("[Northbound] couldnt send RESTCONF response {e:?}");
+    }
+}
+
+/// reads the request. Requests that are malformed, too big or not received within the
+/// READ_TIMEOUT are answered with the returned error.
+fn read_request(stream: &TcpStream) -> Result<HttpRequest, HttpResponse> {
+    let malformed = |e: Error| HttpResponse::error(400, "malformed-message", &e.to_string());
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).map_err(malformed)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+
+    if method.is_empty() || target.is_empty() {
+        return Err(malformed(Error::new(
+            ErrorKind::InvalidData,
+            "invalid request-line",
+        )));
+    }
+
+    let mut content_length: usize = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).map_err(malformed)? == 0 {
+            break;
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| {
+                    malformed(Error::new(ErrorKind::InvalidData, "invalid content-length"))
+                })?;
+            }
+        }
+    }
+
+    if content_length > MAX_CONTENT_LENGTH {
+        return Err(HttpResponse::error(
+            413,
+            "too-big",
+            &format!("the body exceeds {MAX_CONTENT_LENGTH} bytes"),
+        ));
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).map_err(malformed)?;
+
+    // query parameters are only used for the event streams
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), query.to_string()),
+        None => (target, String::new()),
+    };
+
+    Ok(HttpRequest {
+        method,
+        path,
+        query,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+/// checks the requested event stream like "/ieee802-dot1q-tsn-config-uni/json" and returns the
+/// cuc_id of the query and the requested encoding.
+fn parse_event_stream(
+    event_stream: &str,
+    query: &str,
+) -> Result<(String, NotificationEncoding), HttpResponse> {
+    let encoding = match event_stream.strip_prefix(&format!("/{}/", UNI_MODULE)) {
+        Some("json") => NotificationEncoding::Json,
+        Some("xml") => NotificationEncoding::Xml,
+        _ => return Err(HttpResponse::error(404, "invalid-value", "unknown stream")),
+    };
+
+    let cuc_id = query
+        .split('&')
+        .filter_map(|parameter| parameter.split_once('='))
+        .find(|(name, _)| *name == "cuc-id")
+        .map(|(_, value)| percent_decode(value))
+        .filter(|cuc_id| !cuc_id.is_empty());
+
+    cuc_id
+        .map(|cuc_id| (cuc_id, encoding))
+        .ok_or(HttpResponse::error(
+            400,
+            "missing-attribute",
+            "the query parameter cuc-id is required",
+        ))
+}
+
+/// pushes the notifications of the CUC as server-sent events until the client disconnects.
+fn stream_notifications(
+    subscriptions: &NotificationSubscriptions,
+    mut stream: TcpStream,
+    cuc_id: &str,
+    encoding: NotificationEncoding,
+) {
+    let content_type = match encoding {
+        NotificationEncoding::Json => "application/yang-data+json",
+        NotificationEncoding::Xml => "application/yang-data+xml",
+    };
+    let headers = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nX-Event-Data-Type: {content_type}\r\nConnection: keep-alive\r\n\r\n"
+    );
+    if let Err(e) = stream.write_all(headers.as_bytes()) {
+        eprintln!("[Northbound] couldnt open RESTCONF event stream {e:?}");
+        return;
+    }
+
+    let (session, receiver) = subscriptions.subscribe(cuc_id);
+    for notification in receiver {
+        let message = match subscriptions.encode(&notification, encoding) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("[Northbound] couldnt encode RESTCONF notification: {e}");
+                continue;
+            }
+        };
+
+        // every line of the notification is sent as data-field of the event
+        let mut event: String = message
+            .lines()
+            .map(|line| format!("data: {line}\n"))
+            .collect();
+        event.push('\n');
+
+        if let Err(e) = stream
+            .write_all(event.as_bytes())
+            .and_then(|_| stream.flush())
+        {
+            eprintln!("[Northbound] couldnt send RESTCONF notification {e:?}");
+            subscriptions.disconnect(cuc_id, session, Some(notification));
+            return;
+        }
+    }
+}
+
+fn write_response(stream: &mut TcpStream, response: HttpResponse) -> Result<(), Error> {
+    let reason = match response.status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    };
+
+    let body = match response.body {
+        Some(body) => body.to_string(),
+        None => String::new(),
+    };
+
+    let mut message = format!("HTTP/1.1 {} {}\r\n", response.status, reason);
+    if !body.is_empty() {
+        message.push_str(&format!("Content-Type: {}\r\n", YANG_DATA_JSON));
+    }
+    message.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    message.push_str("Connection: close\r\n\r\n");
+    message.push_str(&body);
+
+    stream.write_all(message.as_bytes())?;
+    stream.flush()
+}
+
+/// dispatches the request to the datastore- or operation-resource
+fn route(cnc: &Arc<Cnc>, request: HttpRequest) -> HttpResponse {
+    let data_prefix = format!("{}/data/", RESTCONF_ROOT);
+    let operations_prefix = format!("{}/operations/", RESTCONF_ROOT);
+
+    if let Some(resource_path) = request.path.strip_prefix(&data_prefix) {
+        return match parse_data_path(resource_path) {
+            Some(resource) => handle_data(cnc, &request, resource),
+            None => HttpResponse::error(404, "invalid-value", "unknown data resource"),
+        };
+    }
+
+    if let Some(operation) = request.path.strip_prefix(&operations_prefix) {
+        if request.method != "POST" {
+            return HttpResponse::error(405, "operation-not-supported", "operations require POST");
+        }
+        return handle_operation(cnc, operation, &request.body);
+    }
+
+    HttpResponse::error(404, "invalid-value", "unknown resource")
+}
+
+/// parses a data resource path like
+/// "ieee802-dot1q-tsn-config-uni:tsn-uni/domain=d/cuc=c/stream=00-00-00-00-00-01%3A00-01"
+fn parse_data_path(path: &str) -> Option<UniResource> {
+    let segments: Vec<String> = path
+        .trim_end_matches('/')
+        .split('/')
+        .map(percent_decode)
+        .collect();
+
+    if segments.len() < 3 || segments[0] != format!("{}:tsn-uni", UNI_MODULE) {
+        return None;
+    }
+
+    let domain_id = segments[1].strip_prefix("domain=")?.to_string();
+    let cuc_id = segments[2].strip_prefix("cuc=")?.to_string();
+
+    match segments.len() {
+        3 => Some(UniResource::Cuc { domain_id, cuc_id }),
+        4 => {
+            let action = format!("{}:remove-streams", UNI_MODULE);
+            if segments[3] == "remove-streams" || segments[3] == action {
+                return Some(UniResource::RemoveStreams { domain_id, cuc_id });
+            }
+
+            let stream_id = segments[3].strip_prefix("stream=")?.to_string();
+            Some(UniResource::Stream {
+                domain_id,
+                cuc_id,
+                stream_id,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// decodes the percent-encoded key-values of the resource path (RFC 8040 3.5.3)
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(value) = u8::from_str_radix(hex, 16) {
+                decoded.push(value);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn handle_data(cnc: &Arc<Cnc>, request: &HttpRequest, resource: UniResource) -> HttpResponse {
+    match resource {
+        UniResource::Cuc { domain_id, cuc_id } => {
+            if domain_id != cnc.domain {
+                return HttpResponse::error(404, "invalid-value", "unknown domain");
+            }
+
+            match request.method.as_str() {
+                "GET" => {
+                    let domain = cnc.get_streams(&cuc_id);
+                    match domain.cuc.first() {
+                        Some(cuc) => {
+                            HttpResponse::ok(json!({ format!("{}:cuc", UNI_MODULE): [cuc] }))
+                        }
+                        None => HttpResponse::error(404, "invalid-value", "unknown cuc"),
+                    }
+                }
+                "POST" => {
+                    let streams: Vec<StreamRequest> =
+                        match parse_body(&request.body, &format!("{}:stream", UNI_MODULE)) {
+                            Ok(streams) => streams,
+                            Err(response) => return response,
+                        };
+
+                    cnc.set_streams(&cuc_id, streams);
+                    HttpResponse {
+                        status: 201,
+                        body: None,
+                    }
+                }
+                _ => HttpResponse::error(405, "operation-not-supported", "use GET or POST"),
+            }
+        }
+
+        UniResource::Stream {
+            domain_id,
+            cuc_id,
+            stream_id,
+        } => {
+            if domain_id != cnc.domain {
+                return HttpResponse::error(404, "invalid-value", "unknown domain");
+            }
+
+            match request.method.as_str() {
+                "GET" => {
+                    let domain = cnc.get_streams(&cuc_id);
+                    let stream = domain
+                        .cuc
+                        .iter()
+                        .flat_map(|cuc| cuc.stream.iter())
+                        .find(|stream| stream.stream_id == stream_id);
+
+                    match stream {
+                        Some(stream) => {
+                            HttpResponse::ok(json!({ format!("{}:stream", UNI_MODULE): [stream] }))
+                        }
+                        None => HttpResponse::error(404, "invalid-value", "unknown stream"),
+                    }
+                }
+                "PUT" => {
+                    let mut streams: Vec<StreamRequest> =
+                        match parse_body(&request.body, &format!("{}:stream", UNI_MODULE)) {
+                            Ok(streams) => streams,
+                            Err(response) => return response,
+                        };
+
+                    if streams.len() != 1 || streams[0].stream_id != stream_id {
+                        return HttpResponse::error(
+                            400,
+                            "invalid-value",
+                            "the body has to contain exactly the addressed stream",
+                        );
+                    }
+
+                    cnc.set_streams(&cuc_id, vec![streams.remove(0)]);
+                    HttpResponse::no_content()
+                }
+                "DELETE" => {
+                    cnc.remove_streams(&cuc_id, vec![stream_id]);
+                    HttpResponse::no_content()
+                }
+                _ => HttpResponse::error(405, "operation-not-supported", "use GET, PUT or DELETE"),
+            }
+        }
+
+        UniResource::RemoveStreams { domain_id, cuc_id } => {
+            if domain_id != cnc.domain {
+                return HttpResponse::error(404, "invalid-value", "unknown domain");
+            }
+            if request.method != "POST" {
+                return HttpResponse::error(405, "operation-not-supported", "actions require POST");
+            }
+
+            let input: RemoveStreamsInput = match parse_input(&request.body) {
+                Ok(input) => input,
+                Err(response) => return response,
+            };
+
+            let result = cnc.remove_streams(&cuc_id, input.stream_id);
+            output(json!({ "result": result }))
+        }
+    }
+}
+
+/// input of the action remove-streams
+#[derive(Deserialize)]
+struct RemoveStreamsInput {
+    #[serde(rename = "stream-id")]
+    stream_id: Vec<String>,
+}
+
+/// input of the rpcs compute-streams, compute-planned-and-modified-streams and compute-all-streams
+#[derive(Deserialize)]
+struct ComputeStreamsInput {
+    domain: compute_streams::Input,
+}
+
+/// input of the rpc request-domain-id
+#[derive(Deserialize)]
+struct RequestDomainIdInput {
+    #[serde(rename = "cuc-id")]
+    cuc_id: String,
+}
+
+/// input of the rpc request-free-stream-id
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RequestFreeStreamIdInput {
+    domain_id: String,
+    cuc_id: String,
+}
+
+fn handle_operation(cnc: &Arc<Cnc>, operation: &str, body: &str) -> HttpResponse {
+    let operation = match operation.strip_prefix(&format!("{}:", UNI_MODULE)) {
+        Some(operation) => operation,
+        None => return HttpResponse::error(404, "invalid-value", "unknown module"),
+    };
+
+    match operation {
+        "compute-streams" | "compute-planned-and-modified-streams" | "compute-all-streams" => {
+            let input: ComputeStreamsInput = match parse_input(body) {
+                Ok(input) => input,
+                Err(response) => return response,
+            };
+
+            let computation = match operation {
+                "compute-streams" => ComputationType::List(input.domain),
+                "compute-planned-and-modified-streams" => {
+                    ComputationType::PlannedAndModified(input.domain)
+                }
+                _ => ComputationType::All(input.domain),
+            };
+
+            let result = cnc.compute_streams(computation);
+            output(json!({ "result": result }))
+        }
+        "request-domain-id" => {
+            let input: RequestDomainIdInput = match parse_input(body) {
+                Ok(input) => input,
+                Err(response) => return response,
+            };
+
+            let domain_id = cnc.request_domain_id(input.cuc_id);
+            output(json!({ "domain-id": domain_id }))
+        }
+        "request-free-stream-id" => {
+            let input: RequestFreeStreamIdInput = match parse_input(body) {
+                Ok(input) => input,
+                Err(response) => return response,
+            };
+
+            let stream_id = cnc.request_free_stream_id(request_free_stream_id::Input {
+                domain_id: input.domain_id,
+                cuc_id: input.cuc_id,
+            });
+            output(json!({ "stream-id": stream_id }))
+        }
+        _ => HttpResponse::error(404, "invalid-value", "unknown operation"),
+    }
+}
+
+/// wraps the output of an operation (RFC 8040 3.6.2)
+fn output(content: Value) -> HttpResponse {
+    HttpResponse::ok(json!({ format!("{}:output", UNI_MODULE): content }))
+}
+
+/// parses the input of an operation (RFC 8040 3.6.1)
+fn parse_input<T: for<'de> Deserialize<'de>>(body: &str) -> Result<T, HttpResponse> {
+    parse_body(body, &format!("{}:input", UNI_MODULE))
+}
+
+/// parses the member with the provided name of a json body
+fn parse_body<T: for<'de> Deserialize<'de>>(body: &str, member: &str) -> Result<T, HttpResponse> {
+    let mut value: Value = serde_json::from_str(body)
+        .map_err(|e| HttpResponse::error(400, "malformed-message", &e.to_string()))?;
+
+    let content = value.get_mut(member).map(Value::take).ok_or_else(|| {
+        HttpResponse::error(
+            400,
+            "missing-element",
+            &format!("body has to contain the member {member}"),
+        )
+    })?;
+
+    serde_json::from_value(content)
+        .map_err(|e| HttpResponse::error(400, "invalid-value", &e.to_string()))
+}
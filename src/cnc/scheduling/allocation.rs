@@ -0,0 +1,324 @@
+use super::super::types::allocation::{DomainResources, StreamResources};
+use super::super::types::failure_codes::INSUFFICIENT_BRIDGE_RESOURCES;
+use super::super::types::tsn_types::{
+    DataFrameSpecificationElement, DataFrameSpecificationElementType, GroupIeee802MacAddress,
+    GroupIeee802VlanTag,
+};
+use super::super::types::uni_types::{Domain, Stream};
+use super::super::types::FailedStream;
+use super::super::Cnc;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::Weak;
+
+/// Stream identification type "Active Destination MAC and VLAN Stream identification" of IEEE Std
+/// 802.1CB in cb-stream-iden-type-list (OUI 00-80-C2, type 3)
+const ACTIVE_DESTINATION_MAC_AND_VLAN: u32 = 0x0080_C203;
+
+/// pools of the network-side resources, that are assigned to the streams of a domain
+#[derive(Clone, Debug)]
+pub struct ResourcePool {
+    /// VLAN IDs for streams of vlan-tag-capable end stations
+    pub vlan_ids: RangeInclusive<u16>,
+
+    /// priority code points of the assigned VLAN tags by stream rank. The first is used for rank 0
+    /// (emergency traffic), the second for rank 1.
+    pub priorities: [u8; 2],
+
+    /// destination MAC addresses as 48 bit integers for streams of end stations, that support
+    /// Active Destination MAC and VLAN Stream identification
+    pub destination_macs: RangeInclusive<u64>,
+}
+
+impl Default for ResourcePool {
+    /// all VLAN IDs except the default VLAN, PCP 6 for rank 0 and PCP 5 for rank 1, and the
+    /// dynamic allocation pool of MAAP (IEEE Std 1722) as destination MAC addresses
+    fn default() -> Self {
+        Self {
+            vlan_ids: 2..=4094,
+            priorities: [6, 5],
+            destination_macs: 0x91E0_F000_0000..=0x91E0_F000_FDFF,
+        }
+    }
+}
+
+/// assigns the network-side resources of the pool to the streams, that haven't failed yet. The
+/// resources are unique within a domain and kept in the storage of the CNC, so streams keep them
+/// when they are computed again.
+///
+/// A VLAN tag is only assigned if the talker and all listeners are vlan-tag-capable, a destination
+/// MAC address if they all support Active Destination MAC and VLAN Stream identification. Streams
+/// fail if the pool is exhausted.
+pub fn allocate_stream_resources(
+    cnc: &Weak<Cnc>,
+    pool: &ResourcePool,
+    domains: &mut Vec<Domain>,
+    failed_streams: &mut Vec<FailedStream>,
+) -> HashMap<String, DomainResources> {
+    let cnc = cnc.upgrade();
+    let mut resources: HashMap<String, DomainResources> = HashMap::new();
+
+    for domain in domains.iter_mut() {
+        let mut domain_resources = match &cnc {
+            Some(cnc) => cnc.storage.get_stream_resources(&domain.domain_id),
+            None => DomainResources::new(),
+        };
+
+        for cuc in domain.cuc.iter_mut() {
+            for stream in cuc.stream.iter_mut() {
+                if failed_streams
+                    .iter()
+                    .any(|f| f.stream_id == stream.stream_id)
+                {
+                    continue;
+                }
+
+                match assign_resources(pool, stream, &domain_resources) {
+                    Some(stream_resources) => {
+                        domain_resources.insert(stream.stream_id.clone(), stream_resources);
+                    }
+                    None => {
+                        println!(
+                            "[Scheduler] no resources left in the pool for stream {}",
+                            stream.stream_id
+                        );
+                        domain_resources.remove(&stream.stream_id);
+
+                        stream.group_status_stream.status_info.failure_code =
+                            INSUFFICIENT_BRIDGE_RESOURCES as i32;
+                        failed_streams.push(FailedStream {
+                            stream_id: stream.stream_id.clone(),
+                            cuc_id: cuc.cuc_id.clone(),
+                            domain_id: domain.domain_id.clone(),
+                            failure_code: INSUFFICIENT_BRIDGE_RESOURCES,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(cnc) = &cnc {
+            cnc.storage
+                .set_stream_resources(&domain.domain_id, &domain_resources);
+        }
+        resources.insert(domain.domain_id.clone(), domain_resources);
+    }
+
+    resources
+}
+
+/// assigns a stream handle to each of the streams, that refers to the stream in the PSFP and FRER
+/// functions of the bridges. The handles are unique over all domains and kept in the storage of
+/// the CNC, so streams keep their handle when they are computed again and the entries of other
+/// streams on the bridges stay valid. New streams get the lowest free handle.
+pub fn allocate_stream_handles(cnc: &Weak<Cnc>, stream_ids: &Vec<String>) -> HashMap<String, u32> {
+    let cnc = cnc.upgrade();
+    let mut handles = match &cnc {
+        Some(cnc) => cnc.storage.get_stream_handles(),
+        None => HashMap::new(),
+    };
+    let mut used: HashSet<u32> = handles.values().cloned().collect();
+    let mut next_handle: u32 = 1;
+
+    for stream_id in stream_ids.iter() {
+        if handles.contains_key(stream_id) {
+            continue;
+        }
+
+        while used.contains(&next_handle) {
+            next_handle += 1;
+        }
+        handles.insert(stream_id.clone(), next_handle);
+        used.insert(next_handle);
+    }
+
+    if let Some(cnc) = &cnc {
+        cnc.storage.set_stream_handles(&handles);
+    }
+
+    handles
+}
+
+/// the resources of the stream. Resources that were assigned before are kept if they are still in
+/// the pool. None if the pool is exhausted.
+fn assign_resources(
+    pool: &ResourcePool,
+    stream: &Stream,
+    domain_resources: &DomainResources,
+) -> Option<StreamResources> {
+    let previous = domain_resources.get(&stream.stream_id);
+    let others: Vec<&StreamResources> = domain_resources
+        .iter()
+        .filter(|(stream_id, _)| *stream_id != &stream.stream_id)
+        .map(|(_, resources)| resources)
+        .collect();
+
+    let vlan_tag = match is_vlan_tag_capable(stream) {
+        true => {
+            let vlan_id = previous
+                .and_then(|r| r.vlan_tag.as_ref())
+                .map(|tag| tag.vlan_id)
+                .filter(|vlan_id| pool.vlan_ids.contains(vlan_id))
+                .or_else(|| {
+                    pool.vlan_ids.clone().find(|vlan_id| {
+                        !others
+                            .iter()
+                            .any(|r| r.vlan_tag.as_ref().map(|t| t.vlan_id) == Some(*vlan_id))
+                    })
+                })?;
+            let rank = stream.talker.group_talker.stream_rank.rank as usize;
+
+            Some(GroupIeee802VlanTag {
+                priority_code_point: pool.priorities[rank.min(1)],
+                vlan_id,
+            })
+        }
+        false => None,
+    };
+
+    let destination_mac = match supports_active_destination_mac(stream) {
+        true => {
+            let previous_mac = previous.and_then(|r| r.destination_mac.clone());
+            let mac = previous_mac
+                .filter(|mac| pool.destination_macs.contains(&mac_to_u64(mac)))
+                .or_else(|| {
+                    pool.destination_macs
+                        .clone()
+                        .map(mac_to_string)
+                        .find(|mac| {
+                            !others
+                                .iter()
+                                .any(|r| r.destination_mac.as_ref() == Some(mac))
+                        })
+                })?;
+            Some(mac)
+        }
+        false => None,
+    };
+
+    Some(StreamResources {
+        vlan_tag,
+        destination_mac,
+    })
+}
+
+fn is_vlan_tag_capable(stream: &Stream) -> bool {
+    stream
+        .talker
+        .group_talker
+        .interface_capabilities
+        .vlan_tag_capable
+        && stream
+            .listener
+            .iter()
+            .all(|l| l.group_listener.interface_capabilities.vlan_tag_capable)
+}
+
+fn supports_active_destination_mac(stream: &Stream) -> bool {
+    let supports = |types: &Vec<u32>| types.contains(&ACTIVE_DESTINATION_MAC_AND_VLAN);
+
+    supports(
+        &stream
+            .talker
+            .group_talker
+            .interface_capabilities
+            .cb_stream_iden_type_list,
+    ) && stream.listener.iter().all(|l| {
+        supports(
+            &l.group_listener
+                .interface_capabilities
+                .cb_stream_iden_type_list,
+        )
+    })
+}
+
+/// the MAC address in canonical format, e.g. 91-E0-F0-00-00-00
+fn mac_to_string(mac: u64) -> String {
+    (0..6)
+        .rev()
+        .map(|octet| format!("{:02X}", (mac >> (octet * 8)) & 0xff))
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+/// the MAC address as 48 bit integer. Invalid addresses are 0.
+fn mac_to_u64(mac: &str) -> u64 {
+    mac.split(['-', ':'])
+        .try_fold(0, |acc, octet| {
+            u8::from_str_radix(octet, 16).map(|octet| (acc << 8) | octet as u64)
+        })
+        .unwrap_or(0)
+}
+
+/// the domains as seen in the network: the VLAN tag and destination MAC address of the
+/// data-frame-specification of each talker are replaced by the resources assigned to the stream.
+/// Elements that the talker didn't specify are added.
+pub fn network_domains(
+    domains: &Vec<Domain>,
+    resources: &HashMap<String, DomainResources>,
+) -> Vec<Domain> {
+    let mut network = domains.clone();
+
+    for domain in network.iter_mut() {
+        let domain_resources = match resources.get(&domain.domain_id) {
+            Some(domain_resources) => domain_resources,
+            None => continue,
+        };
+
+        for stream in domain.cuc.iter_mut().flat_map(|c| c.stream.iter_mut()) {
+            if let Some(stream_resources) = domain_resources.get(&stream.stream_id) {
+                apply_resources(stream, stream_resources);
+            }
+        }
+    }
+
+    network
+}
+
+fn apply_resources(stream: &mut Stream, resources: &StreamResources) {
+    let source_mac = stream
+        .talker
+        .group_talker
+        .end_station_interfaces
+        .first()
+        .map(|i| i.mac_address.clone())
+        .unwrap_or_default();
+    let specification = &mut stream.talker.group_talker.data_frame_specification;
+    let mut next_index = specification.iter().map(|s| s.index + 1).max().unwrap_or(0);
+
+    if let Some(vlan_tag) = &resources.vlan_tag {
+        let element = specification.iter_mut().find_map(|s| match &mut s.field {
+            DataFrameSpecificationElementType::Ieee802VlanTag(x) => Some(x),
+            _ => None,
+        });
+        match element {
+            Some(x) => *x = vlan_tag.clone(),
+            None => {
+                specification.push(DataFrameSpecificationElement {
+                    index: next_index,
+                    field: DataFrameSpecificationElementType::Ieee802VlanTag(vlan_tag.clone()),
+                });
+                next_index += 1;
+            }
+        }
+    }
+
+    if let Some(destination_mac) = &resources.destination_mac {
+        let element = specification.iter_mut().find_map(|s| match &mut s.field {
+            DataFrameSpecificationElementType::Ieee802MacAddresses(x) => Some(x),
+            _ => None,
+        });
+        match element {
+            Some(x) => x.destination_mac_adress = destination_mac.clone(),
+            None => specification.push(DataFrameSpecificationElement {
+                index: next_index,
+                field: DataFrameSpecificationElementType::Ieee802MacAddresses(
+                    GroupIeee802MacAddress {
+                        destination_mac_adress: destination_mac.clone(),
+                        source_mac_adress: source_mac,
+                    },
+                ),
+            }),
+        }
+    }
+}
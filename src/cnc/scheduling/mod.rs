@@ -1,20 +1,36 @@
+use super::topology::{PathComputation, PathMetric};
+use super::types::failure_codes::INSUFFICIENT_BRIDGE_RESOURCES;
+use super::types::frer_types::FrerConfiguration;
 use super::types::sched_types::{
     ConfigurableGateParameterTableEntry, GateControlEntry, GateControlOperation,
 };
 use super::types::scheduling::{Config, PortConfiguration, Schedule};
-use super::types::topology::Topology;
+use super::types::topology::{Topology, Tree};
 use super::types::tsn_types::{
-    ConfigListElement, ConfigValue, DataFrameSpecificationElementType, InterfaceListElement,
+    ConfigListElement, ConfigValue, DataFrameSpecificationElement,
+    DataFrameSpecificationElementType, InterfaceListElement,
 };
 use super::types::uni_types::Domain;
-use super::types::ComputationResult;
+use super::types::{ComputationResult, FailedStream};
 use super::Cnc;
+use std::collections::HashMap;
 use std::sync::Weak;
 
+mod allocation;
+mod tas;
+pub use self::allocation::{allocate_stream_resources, network_domains, ResourcePool};
+pub use self::tas::TasScheduler;
+
 pub trait SchedulerAdapterInterface {
     /// This function will compute the schedule for the given topology and domains.
+    /// The routes of the streams are computed with the metric of the topology.
     /// This can take some time, depending on what algorithms are used.
-    fn compute_schedule(&self, topology: &Topology, domains: &Vec<Domain>) -> ComputationResult;
+    fn compute_schedule(
+        &self,
+        topology: &Topology,
+        metric: PathMetric,
+        domains: &Vec<Domain>,
+    ) -> ComputationResult;
 
     /// # CNC Configuration
     /// Minimum requirement:
@@ -24,22 +40,134 @@ pub trait SchedulerAdapterInterface {
     fn set_cnc_ref(&mut self, cnc: Weak<Cnc>);
 }
 
+/// computes the maximally disjoint trees of all streams as requested by num_seemless_trees, with
+/// the metric of the topology.
+///
+/// Streams where the requested number of trees isn't possible in the topology get the failure_code
+/// set and are returned as failed. Streams with unknown or unreachable end stations get no trees.
+pub fn compute_stream_trees(
+    topology: &Topology,
+    metric: PathMetric,
+    domains: &mut Vec<Domain>,
+) -> (HashMap<String, Vec<Tree>>, Vec<FailedStream>) {
+    let path_computation = PathComputation::new(topology, metric);
+    let mut trees: HashMap<String, Vec<Tree>> = HashMap::new();
+    let mut failed_streams: Vec<FailedStream> = Vec::new();
+
+    for domain in domains.iter_mut() {
+        for cuc in domain.cuc.iter_mut() {
+            for stream in cuc.stream.iter_mut() {
+                let num_trees = stream
+                    .talker
+                    .group_talker
+                    .user_to_network_requirements
+                    .num_seemless_trees
+                    .max(1);
+
+                let stream_trees = match path_computation.compute_trees(stream, num_trees) {
+                    Some(stream_trees) => stream_trees,
+                    None => continue,
+                };
+
+                if stream_trees.len() < num_trees as usize {
+                    println!(
+                        "[Scheduler] stream {} requested {} trees but only {} are possible",
+                        stream.stream_id,
+                        num_trees,
+                        stream_trees.len()
+                    );
+
+                    stream.group_status_stream.status_info.failure_code =
+                        INSUFFICIENT_BRIDGE_RESOURCES as i32;
+                    failed_streams.push(FailedStream {
+                        stream_id: stream.stream_id.clone(),
+                        cuc_id: cuc.cuc_id.clone(),
+                        domain_id: domain.domain_id.clone(),
+                        failure_code: INSUFFICIENT_BRIDGE_RESOURCES,
+                    });
+                }
+
+                trees.insert(stream.stream_id.clone(), stream_trees);
+            }
+        }
+    }
+
+    (trees, failed_streams)
+}
+
+/// the config-list of an interface-configuration with the values of the data-frame-specification
+pub fn config_list(specification: &Vec<DataFrameSpecificationElement>) -> Vec<ConfigListElement> {
+    specification
+        .iter()
+        .map(|spec| ConfigListElement {
+            index: spec.index,
+            config_value: match &spec.field {
+                DataFrameSpecificationElementType::Ieee802MacAddresses(x) => {
+                    ConfigValue::Ieee802MacAddresses(x.clone())
+                }
+                DataFrameSpecificationElementType::Ieee802VlanTag(x) => {
+                    ConfigValue::Ieee802VlanTag(x.clone())
+                }
+                DataFrameSpecificationElementType::Ipv4Tuple(x) => {
+                    ConfigValue::Ipv4Tuple(x.clone())
+                }
+                DataFrameSpecificationElementType::Ipv6Tuple(x) => {
+                    ConfigValue::Ipv6Tuple(x.clone())
+                }
+            },
+        })
+        .collect()
+}
+
 pub struct MockTSNScheduler {
     cnc: Weak<Cnc>,
+    resource_pool: Option<ResourcePool>,
 }
 
 impl MockTSNScheduler {
     pub fn new() -> Self {
         Self {
             cnc: Weak::default(),
+            resource_pool: None,
         }
     }
 
+    /// lets the CNC assign VLAN IDs, priorities and destination MAC addresses from the pool
+    pub fn set_resource_pool(&mut self, pool: ResourcePool) {
+        self.resource_pool = Some(pool);
+    }
+
     /// returnes a fake configuration. All in there is hardcoded and specifically for the topology in the TopologyComponent.
-    pub fn compute_fake(&self, topology: &Topology, domains: &Vec<Domain>) -> ComputationResult {
+    pub fn compute_fake(
+        &self,
+        topology: &Topology,
+        metric: PathMetric,
+        domains: &Vec<Domain>,
+    ) -> ComputationResult {
         let acc_latency = 50000;
 
         let mut domains = domains.clone(); // copy since we do modifications to it
+        let (trees, mut failed_streams) = compute_stream_trees(topology, metric, &mut domains);
+
+        let network = match &self.resource_pool {
+            Some(pool) => {
+                let resources =
+                    allocate_stream_resources(&self.cnc, pool, &mut domains, &mut failed_streams);
+                network_domains(&domains, &resources)
+            }
+            None => domains.clone(),
+        };
+        let specifications: HashMap<&String, &Vec<DataFrameSpecificationElement>> = network
+            .iter()
+            .flat_map(|d| d.cuc.iter())
+            .flat_map(|c| c.stream.iter())
+            .map(|s| {
+                (
+                    &s.stream_id,
+                    &s.talker.group_talker.data_frame_specification,
+                )
+            })
+            .collect();
 
         for domain in domains.iter_mut() {
             for cuc in domain.cuc.iter_mut() {
@@ -59,41 +187,12 @@ impl MockTSNScheduler {
 
                     let interface_id = stream.talker.group_talker.end_station_interfaces[0].clone();
 
-                    let mut interface_list_element = InterfaceListElement {
+                    // copy interfaceconfigs, including the resources assigned by the CNC
+                    let interface_list_element = InterfaceListElement {
                         group_interface_id: interface_id,
-                        config_list: Vec::new(),
+                        config_list: config_list(specifications[&stream.stream_id]),
                     };
 
-                    // copy interfaceconfigs
-                    for config in stream.talker.group_talker.data_frame_specification.iter() {
-                        match &config.field {
-                            DataFrameSpecificationElementType::Ieee802MacAddresses(x) => {
-                                interface_list_element.config_list.push(ConfigListElement {
-                                    index: config.index,
-                                    config_value: ConfigValue::Ieee802MacAddresses(x.clone()),
-                                })
-                            }
-                            DataFrameSpecificationElementType::Ieee802VlanTag(x) => {
-                                interface_list_element.config_list.push(ConfigListElement {
-                                    index: config.index,
-                                    config_value: ConfigValue::Ieee802VlanTag(x.clone()),
-                                })
-                            }
-                            DataFrameSpecificationElementType::Ipv4Tuple(x) => {
-                                interface_list_element.config_list.push(ConfigListElement {
-                                    index: config.index,
-                                    config_value: ConfigValue::Ipv4Tuple(x.clone()),
-                                })
-                            }
-                            DataFrameSpecificationElementType::Ipv6Tuple(x) => {
-                                interface_list_element.config_list.push(ConfigListElement {
-                                    index: config.index,
-                                    config_value: ConfigValue::Ipv6Tuple(x.clone()),
-                                })
-                            }
-                        }
-                    }
-
                     stream
                         .talker
                         .group_status_talker_listener
@@ -141,10 +240,13 @@ impl MockTSNScheduler {
             }
         }
 
+        let mut schedule = self.parse_to_schedule(bridges, topology);
+        schedule.trees = trees;
+
         let result = ComputationResult {
-            schedule: self.parse_to_schedule(bridges, topology),
-            domains,                    // modified domains
-            failed_streams: Vec::new(), // no failed streams
+            schedule,
+            domains, // modified domains
+            failed_streams,
         };
 
         return result;
@@ -162,6 +264,7 @@ impl MockTSNScheduler {
                 port: PortConfiguration {
                     name: String::from("sw0p2"),
                     mac_address: String::from("00-60-65-82-c9-5b"),
+                    port_number: 0,
 
                     config: ConfigurableGateParameterTableEntry {
                         gate_enable: true,
@@ -176,6 +279,15 @@ impl MockTSNScheduler {
                         admin_base_time: (0, 0),
                         config_change: true,
                     },
+                    frame_preemption: Vec::new(),
+                    fqtss: Vec::new(),
+                    psfp: Vec::new(),
+                    stream_identification: Vec::new(),
+                    frer: FrerConfiguration::default(),
+                    static_filtering: Vec::new(),
+                    vlan_registration: Vec::new(),
+                    released_static_filtering: Vec::new(),
+                    released_vlan_registration: Vec::new(),
                 },
                 affected_streams: vec![
                     String::from("00-00-00-00-00-01:00-01"),
@@ -189,6 +301,7 @@ impl MockTSNScheduler {
                 port: PortConfiguration {
                     name: String::from("sw0p3"),
                     mac_address: String::from("00-60-65-82-c9-5c"),
+                    port_number: 0,
                     config: ConfigurableGateParameterTableEntry {
                         gate_enable: true,
                         admin_gate_states: 255,
@@ -202,6 +315,15 @@ impl MockTSNScheduler {
                         admin_base_time: (0, 0),
                         config_change: true,
                     },
+                    frame_preemption: Vec::new(),
+                    fqtss: Vec::new(),
+                    psfp: Vec::new(),
+                    stream_identification: Vec::new(),
+                    frer: FrerConfiguration::default(),
+                    static_filtering: Vec::new(),
+                    vlan_registration: Vec::new(),
+                    released_static_filtering: Vec::new(),
+                    released_vlan_registration: Vec::new(),
                 },
                 affected_streams: vec![
                     String::from("00-00-00-00-00-01:00-01"),
@@ -214,6 +336,7 @@ impl MockTSNScheduler {
                 port: PortConfiguration {
                     name: String::from("sw0p4"),
                     mac_address: String::from("00-60-65-82-c9-5d"),
+                    port_number: 0,
                     config: ConfigurableGateParameterTableEntry {
                         gate_enable: true,
                         admin_gate_states: 255,
@@ -227,6 +350,15 @@ impl MockTSNScheduler {
                         admin_base_time: (0, 0),
                         config_change: true,
                     },
+                    frame_preemption: Vec::new(),
+                    fqtss: Vec::new(),
+                    psfp: Vec::new(),
+                    stream_identification: Vec::new(),
+                    frer: FrerConfiguration::default(),
+                    static_filtering: Vec::new(),
+                    vlan_registration: Vec::new(),
+                    released_static_filtering: Vec::new(),
+                    released_vlan_registration: Vec::new(),
                 },
                 affected_streams: vec![
                     String::from("00-00-00-00-00-01:00-02"),
@@ -242,6 +374,7 @@ impl MockTSNScheduler {
                 port: PortConfiguration {
                     name: String::from("sw0p2"),
                     mac_address: String::from("00-10-02-00-02-02"),
+                    port_number: 0,
                     config: ConfigurableGateParameterTableEntry {
                         gate_enable: true,
                         admin_gate_states: 255,
@@ -255,6 +388,15 @@ impl MockTSNScheduler {
                         admin_base_time: (0, 0),
                         config_change: true,
                     },
+                    frame_preemption: Vec::new(),
+                    fqtss: Vec::new(),
+                    psfp: Vec::new(),
+                    stream_identification: Vec::new(),
+                    frer: FrerConfiguration::default(),
+                    static_filtering: Vec::new(),
+                    vlan_registration: Vec::new(),
+                    released_static_filtering: Vec::new(),
+                    released_vlan_registration: Vec::new(),
                 },
                 affected_streams: vec![
                     String::from("00-00-00-00-00-01:00-02"),
@@ -267,6 +409,7 @@ impl MockTSNScheduler {
                 port: PortConfiguration {
                     name: String::from("sw0p3"),
                     mac_address: String::from("00-10-02-00-02-03"),
+                    port_number: 0,
                     config: ConfigurableGateParameterTableEntry {
                         gate_enable: true,
                         admin_gate_states: 255,
@@ -280,6 +423,15 @@ impl MockTSNScheduler {
                         admin_base_time: (0, 0),
                         config_change: true,
                     },
+                    frame_preemption: Vec::new(),
+                    fqtss: Vec::new(),
+                    psfp: Vec::new(),
+                    stream_identification: Vec::new(),
+                    frer: FrerConfiguration::default(),
+                    static_filtering: Vec::new(),
+                    vlan_registration: Vec::new(),
+                    released_static_filtering: Vec::new(),
+                    released_vlan_registration: Vec::new(),
                 },
                 affected_streams: vec![
                     String::from("00-00-00-00-00-01:00-02"),
@@ -288,13 +440,21 @@ impl MockTSNScheduler {
             });
         }
 
-        return Schedule { configs };
+        return Schedule {
+            configs,
+            trees: HashMap::new(),
+        };
     }
 }
 
 impl SchedulerAdapterInterface for MockTSNScheduler {
-    fn compute_schedule(&self, topology: &Topology, domains: &Vec<Domain>) -> ComputationResult {
-        let result = self.compute_fake(topology, domains);
+    fn compute_schedule(
+        &self,
+        topology: &Topology,
+        metric: PathMetric,
+        domains: &Vec<Domain>,
+    ) -> ComputationResult {
+        let result = self.compute_fake(topology, metric, domains);
         return result;
     }
 
@@ -0,0 +1,1810 @@
+use super::allocation::{
+    allocate_stream_handles, allocate_stream_resources, network_domains, ResourcePool,
+};
+use super::{compute_stream_trees, config_list, SchedulerAdapterInterface};
+use crate::cnc::topology::PathMetric;
+use crate::cnc::types::bridge_types::{
+    StaticFilteringControl, StaticFilteringEntry, VlanRegistrationEntry, VlanTransmitted,
+};
+use crate::cnc::types::failure_codes::{
+    INSUFFICIENT_BANDWIDTH, INSUFFICIENT_BRIDGE_RESOURCES, MAX_LATENCY_EXCEEDED,
+};
+use crate::cnc::types::frer_types::{
+    FrerConfiguration, IpStreamIdentification, NullStreamIdentification, SequenceGenerationEntry,
+    SequenceIdentificationEntry, SequenceRecoveryAlgorithm, SequenceRecoveryEntry,
+    StreamIdentificationType, StreamIdentityEntry, VlanTagIdentification,
+};
+use crate::cnc::types::sched_types::{
+    ConfigurableGateParameterTableEntry, FlowMeterInstance, FqtssTrafficClassEntry,
+    FramePreemptionStatus, FramePreemptionStatusEntry, GateControlEntry, GateControlOperation,
+    PsfpEntry, RationalGrouping, StreamFilterInstance, StreamGateControlEntry, StreamGateInstance,
+    StreamGateState, TransmissionSelectionAlgorithm,
+};
+use crate::cnc::types::scheduling::{Config, PortConfiguration, Schedule};
+use crate::cnc::types::topology::{NodeType, Port, Topology, Tree};
+use crate::cnc::types::tsn_types::{
+    BridgePortDelays, ConfigListElement, ConfigValue, DataFrameSpecificationElement,
+    DataFrameSpecificationElementType, InterfaceListElement, ListenerStatus, TalkerStatus,
+};
+use crate::cnc::types::uni_types::{Domain, Stream};
+use crate::cnc::types::{ComputationResult, FailedStream};
+use crate::cnc::Cnc;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Weak;
+
+/// media-specific overhead of a frame in bytes:
+/// preamble and SFD (8), MAC header (14), VLAN tag (4), FCS (4) and interframe gap (12)
+const FRAME_OVERHEAD: u64 = 42;
+
+/// size of the largest best-effort frame. The best-effort gates close the transmission time of
+/// this frame before each scheduled window, so no frame is still transmitted when it opens.
+const GUARD_BAND_FRAME_SIZE: u64 = 1522;
+
+/// size of the largest remainder of a preempted frame that can't be preempted anymore
+/// (IEEE 802.3br). With frame preemption, the guard band only has to cover this fragment.
+const PREEMPTION_GUARD_BAND_FRAME_SIZE: u64 = 123;
+
+/// port speed in Mb/s if the port doesn't report its bridge-port-delays
+const DEFAULT_PORT_SPEED: u64 = 1000;
+
+/// traffic class of streams without a VLAN tag in the data-frame-specification
+const DEFAULT_TRAFFIC_CLASS: u8 = 7;
+
+/// transmission-selection of the talker (Table 8-6 of IEEE Std 802.1Q-2022) for streams that are
+/// shaped by the credit-based shaper instead of being scheduled
+const CREDIT_BASED_SHAPER: u8 = 1;
+
+/// share of the port speed in percent, that can be reserved for credit-based shaped streams.
+/// The rest is left for best-effort traffic.
+const MAX_RESERVED_BANDWIDTH: u64 = 75;
+
+/// time in ns the stream gates open before and stay open after the frames of a stream are
+/// expected on an ingress port. This covers deviations of the synchronized clocks.
+const STREAM_GATE_MARGIN: u64 = 1000;
+
+/// VLAN of streams without VLAN ID. Priority-tagged and untagged frames are assigned to the PVID of
+/// the ingress port, which is the default VLAN of the bridges.
+const DEFAULT_VID: u16 = 1;
+
+/// identifies a port by node_id and port_name
+type PortKey = (u32, String);
+
+/// a reduced fraction of seconds (numerator, denominator)
+type Rational = (u64, u64);
+
+/// time in which the frames of a stream are transmitted on a port in ns within the cycle.
+/// The end can be beyond the cycle time if the window wraps around.
+#[derive(Clone, Debug)]
+struct Window {
+    start: u64,
+    end: u64,
+    stream_id: String,
+    traffic_class: u8,
+}
+
+/// transmission of the frames of a stream on an egress port or their reception on an ingress
+/// port. The start is relative to the time-aware-offset of the talker.
+#[derive(Clone, Debug, PartialEq)]
+struct Transmission {
+    port: PortKey,
+    start: u64,
+    duration: u64,
+}
+
+/// cycle time and reserved windows of an egress port
+#[derive(Clone, Debug)]
+struct PortSchedule {
+    /// hyperperiod of the intervals of all streams crossing the port
+    cycle_time: Rational,
+    windows: Vec<Window>,
+}
+
+impl PortSchedule {
+    fn new(cycle_time: Rational) -> Self {
+        Self {
+            cycle_time,
+            windows: Vec::new(),
+        }
+    }
+
+    fn cycle_ns(&self) -> u64 {
+        to_ns(self.cycle_time)
+    }
+
+    /// extends the cycle to a multiple of the current one. The reserved windows are repeated in
+    /// the added cycles, since they repeat with an interval that is a divisor of the old cycle.
+    fn extend(&mut self, cycle_time: Rational) {
+        let old_cycle = self.cycle_ns();
+        let repetitions = to_ns(cycle_time) / old_cycle.max(1);
+        let windows = std::mem::take(&mut self.windows);
+
+        for repetition in 0..repetitions.max(1) {
+            for window in windows.iter() {
+                self.windows.push(Window {
+                    start: window.start + repetition * old_cycle,
+                    end: window.end + repetition * old_cycle,
+                    ..window.clone()
+                });
+            }
+        }
+        self.cycle_time = cycle_time;
+    }
+}
+
+/// bandwidth reserved on an egress port for credit-based shaped streams
+#[derive(Clone, Debug, Default)]
+struct PortBandwidth {
+    /// idle slope in bit/s per traffic class
+    idle_slopes: BTreeMap<u8, u64>,
+    streams: Vec<String>,
+}
+
+/// stream identification and FRER functions of the streams, that are configured with a port
+#[derive(Clone, Debug, Default)]
+struct PortFrer {
+    stream_identification: Vec<StreamIdentityEntry>,
+    frer: FrerConfiguration,
+    streams: Vec<String>,
+}
+
+/// static filtering and VLAN registration entries of the streams, that are configured with a port
+#[derive(Clone, Debug, Default)]
+struct PortForwarding {
+    static_filtering: Vec<StaticFilteringEntry>,
+    vlan_registration: Vec<VlanRegistrationEntry>,
+    streams: Vec<String>,
+}
+
+/// timing of a stream along all of its trees
+struct StreamTiming {
+    transmissions: Vec<Transmission>,
+
+    /// receptions on the ingress ports of all hops after the talker
+    receptions: Vec<Transmission>,
+
+    /// latency of each listener relative to the time-aware-offset of the talker
+    listener_latencies: Vec<u64>,
+}
+
+/// # Time-Aware Scheduler (IEEE 802.1Qbv)
+///
+/// Every stream gets an exclusive window on all egress ports along its trees. The windows are
+/// placed one after another without waiting in the bridges: a frame is forwarded as soon as it is
+/// received. The earliest possible time-aware-offset of the talker is used, where no window
+/// overlaps with the windows of already placed streams.
+///
+/// Every port uses the hyperperiod of the intervals of all streams crossing it as cycle time. The
+/// base time is the PTP epoch, so the cycles of all bridges are aligned with the intervals of the
+/// talkers. Streams that would exceed the supported-cycle-max or supported-list-max of a port fail.
+///
+/// Streams are placed by their rank first. Streams that can't be placed are returned as failed.
+///
+/// Streams with the credit-based shaper as transmission-selection aren't scheduled. Their bandwidth
+/// is reserved as idle slope of their traffic class on every egress port (IEEE 802.1Qav). A stream
+/// fails if more than 75% of the port speed would be reserved.
+///
+/// With frame preemption (IEEE 802.1Qbu), the scheduled traffic classes are express and all others
+/// preemptable. The MAC is held during the guard band and the windows (SetAndHoldMAC) and released
+/// for best-effort traffic (SetAndReleaseMAC), so the guard band is reduced to the largest
+/// fragment. This requires all bridges to support frame preemption.
+///
+/// Every bridge polices the streams it receives (IEEE 802.1Qci). The flow meter of a stream admits
+/// its max-frames-per-interval of max-frame-size, the stream filter discards larger frames and the
+/// stream gate is only open while the frames of a scheduled stream are expected. So a talker that
+/// sends more or at the wrong time can't disturb other streams.
+///
+/// The streams are identified in the bridges by their data-frame-specification (IEEE 802.1CB).
+/// Streams with more than one tree get a sequence number on the bridge where their trees split.
+/// Duplicates are discarded on the bridges where the trees merge again (FRER).
+///
+/// The bridges only forward the frames of a stream along its trees. Every egress port gets a static
+/// filtering entry for the destination MAC address and VLAN of the stream. Streams with a VLAN ID
+/// additionally register all ports along the trees as members of their VLAN.
+///
+/// With a resource pool, the CNC assigns the VLAN tag and destination MAC address of the streams
+/// (see [allocate_stream_resources]). The bridges are configured for the assigned resources and the
+/// end stations receive them in their interface-configuration.
+pub struct TasScheduler {
+    cnc: Weak<Cnc>,
+    frame_preemption: bool,
+    resource_pool: Option<ResourcePool>,
+}
+
+impl TasScheduler {
+    pub fn new() -> Self {
+        Self {
+            cnc: Weak::default(),
+            frame_preemption: false,
+            resource_pool: None,
+        }
+    }
+
+    pub fn new_with_frame_preemption() -> Self {
+        Self {
+            cnc: Weak::default(),
+            frame_preemption: true,
+            resource_pool: None,
+        }
+    }
+
+    /// lets the CNC assign VLAN IDs, priorities and destination MAC addresses from the pool
+    pub fn set_resource_pool(&mut self, pool: ResourcePool) {
+        self.resource_pool = Some(pool);
+    }
+}
+
+impl SchedulerAdapterInterface for TasScheduler {
+    fn compute_schedule(
+        &self,
+        topology: &Topology,
+        metric: PathMetric,
+        domains: &Vec<Domain>,
+    ) -> ComputationResult {
+        let mut domains = domains.clone(); // copy since we do modifications to it
+        let (trees, mut failed_streams) = compute_stream_trees(topology, metric, &mut domains);
+        fail_unreplicated_streams(topology, &mut domains, &trees, &mut failed_streams);
+
+        // the bridges see the streams with the resources assigned by the CNC
+        let network = match &self.resource_pool {
+            Some(pool) => {
+                let resources =
+                    allocate_stream_resources(&self.cnc, pool, &mut domains, &mut failed_streams);
+                network_domains(&domains, &resources)
+            }
+            None => domains.clone(),
+        };
+        let redundancy_failed: HashSet<String> =
+            failed_streams.iter().map(|f| f.stream_id.clone()).collect();
+
+        // more important streams (lower rank) are placed first
+        let mut order: Vec<&Stream> = network
+            .iter()
+            .flat_map(|d| d.cuc.iter())
+            .flat_map(|c| c.stream.iter())
+            .filter(|s| !redundancy_failed.contains(&s.stream_id))
+            .collect();
+        order.sort_by_key(|s| (s.talker.group_talker.stream_rank.rank, interval_ns(s)));
+
+        let mut ports: HashMap<PortKey, PortSchedule> = HashMap::new();
+        let mut bandwidth: HashMap<PortKey, PortBandwidth> = HashMap::new();
+        let mut results: HashMap<String, Result<(Option<u64>, Vec<u64>), u32>> = HashMap::new();
+        let mut placed: Vec<String> = Vec::new();
+
+        for stream in order {
+            // the bridges identify the streams for PSFP and FRER by their data-frame-specification
+            if stream_identification(stream).is_none() {
+                eprintln!(
+                    "[Scheduler] stream {} can't be identified without destination MAC address or IP tuple",
+                    stream.stream_id
+                );
+                results.insert(stream.stream_id.clone(), Err(INSUFFICIENT_BRIDGE_RESOURCES));
+                continue;
+            }
+
+            let placement = match trees.get(&stream.stream_id) {
+                Some(stream_trees) if is_credit_based(stream) => {
+                    reserve_bandwidth(topology, stream, stream_trees, &mut bandwidth)
+                        .map(|latencies| (None, latencies))
+                }
+                Some(stream_trees) => place_stream(topology, stream, stream_trees, &mut ports)
+                    .map(|(offset, latencies)| (Some(offset), latencies)),
+                None => {
+                    eprintln!(
+                        "[Scheduler] no route for stream {} in the topology",
+                        stream.stream_id
+                    );
+                    Err(INSUFFICIENT_BRIDGE_RESOURCES)
+                }
+            };
+
+            if placement.is_ok() {
+                placed.push(stream.stream_id.clone());
+            }
+            results.insert(stream.stream_id.clone(), placement);
+        }
+
+        // remove the least important streams from ports where the gate control list is too long
+        let configs = loop {
+            let configs = gate_control_lists(topology, &ports, self.frame_preemption);
+            let exceeded = configs.iter().find(|config| {
+                let list_max = port_information(topology, config.node_id, &config.port.name)
+                    .map(|p| p.supported_list_max)
+                    .unwrap_or(0);
+                list_max > 0 && config.port.config.admin_control_list.len() > list_max as usize
+            });
+
+            let stream_id = match exceeded {
+                Some(config) => placed
+                    .iter()
+                    .rev()
+                    .find(|id| config.affected_streams.contains(id))
+                    .cloned()
+                    .expect("ports with a gate control list have streams"),
+                None => break configs,
+            };
+
+            println!(
+                "[Scheduler] gate control list exceeds supported-list-max, removing stream {}",
+                stream_id
+            );
+            for port in ports.values_mut() {
+                port.windows.retain(|w| w.stream_id != stream_id);
+            }
+            placed.retain(|id| id != &stream_id);
+            results.insert(stream_id, Err(INSUFFICIENT_BRIDGE_RESOURCES));
+        };
+        let configs = add_fqtss_configs(topology, configs, &bandwidth);
+
+        // every placed stream gets a stream handle, that refers to the stream in the PSFP and FRER
+        // functions of the bridges
+        let placed_streams: Vec<String> = network
+            .iter()
+            .flat_map(|d| d.cuc.iter())
+            .flat_map(|c| c.stream.iter())
+            .filter(|s| matches!(results.get(&s.stream_id), Some(Ok(_))))
+            .map(|s| s.stream_id.clone())
+            .collect();
+        let handles = allocate_stream_handles(&self.cnc, &placed_streams);
+
+        let mut psfp: HashMap<PortKey, Vec<(String, PsfpEntry)>> = HashMap::new();
+        let mut frer: HashMap<PortKey, PortFrer> = HashMap::new();
+        let mut forwarding: HashMap<PortKey, PortForwarding> = HashMap::new();
+        let streams = network
+            .iter()
+            .flat_map(|d| d.cuc.iter())
+            .flat_map(|c| c.stream.iter());
+        for stream in streams {
+            if let (Some(Ok((offset, latencies))), Some(stream_trees), Some(&stream_handle)) = (
+                results.get(&stream.stream_id),
+                trees.get(&stream.stream_id),
+                handles.get(&stream.stream_id),
+            ) {
+                police_stream(
+                    topology,
+                    stream,
+                    stream_trees,
+                    *offset,
+                    stream_handle,
+                    &mut psfp,
+                );
+                identify_stream(
+                    topology,
+                    stream,
+                    stream_trees,
+                    stream_handle,
+                    latencies,
+                    &mut frer,
+                );
+                forward_stream(topology, stream, stream_trees, &mut forwarding);
+            }
+        }
+        let configs = add_psfp_configs(topology, configs, &psfp);
+        let configs = add_frer_configs(topology, configs, &frer);
+        let configs = add_forwarding_configs(topology, configs, &forwarding);
+
+        let specifications: HashMap<&String, &Vec<DataFrameSpecificationElement>> = network
+            .iter()
+            .flat_map(|d| d.cuc.iter())
+            .flat_map(|c| c.stream.iter())
+            .map(|s| {
+                (
+                    &s.stream_id,
+                    &s.talker.group_talker.data_frame_specification,
+                )
+            })
+            .collect();
+
+        for domain in domains.iter_mut() {
+            for cuc in domain.cuc.iter_mut() {
+                for stream in cuc.stream.iter_mut() {
+                    match results.get(&stream.stream_id) {
+                        Some(Ok((offset, latencies))) => {
+                            let specification = specifications[&stream.stream_id];
+                            set_stream_configuration(stream, specification, *offset, latencies)
+                        }
+                        Some(Err(failure_code)) => {
+                            println!(
+                                "[Scheduler] stream {} can't be scheduled - failure-code {}",
+                                stream.stream_id, failure_code
+                            );
+                            let status_info = &mut stream.group_status_stream.status_info;
+                            status_info.failure_code = *failure_code as i32;
+                            status_info.talker_status = TalkerStatus::Failed;
+                            status_info.listener_status = ListenerStatus::Failed;
+                            failed_streams.push(FailedStream {
+                                stream_id: stream.stream_id.clone(),
+                                cuc_id: cuc.cuc_id.clone(),
+                                domain_id: domain.domain_id.clone(),
+                                failure_code: *failure_code,
+                            });
+                        }
+                        None => (), // already failed by the trees or the resource allocation
+                    }
+                }
+            }
+        }
+
+        ComputationResult {
+            schedule: Schedule { configs, trees },
+            domains,
+            failed_streams,
+        }
+    }
+
+    fn set_cnc_ref(&mut self, cnc: Weak<Cnc>) {
+        self.cnc = cnc;
+    }
+}
+
+/// interval of the stream as reduced fraction of seconds. None if the interval is zero.
+fn interval(stream: &Stream) -> Option<Rational> {
+    let interval = &stream.talker.group_talker.traffic_specification.interval;
+    if interval.numerator == 0 || interval.denominator == 0 {
+        return None;
+    }
+    Some(reduce((
+        interval.numerator as u64,
+        interval.denominator as u64,
+    )))
+}
+
+/// interval of the stream in ns
+fn interval_ns(stream: &Stream) -> u64 {
+    interval(stream).map(to_ns).unwrap_or(0)
+}
+
+/// the time in ns. Fractions of ns are truncated.
+fn to_ns(time: Rational) -> u64 {
+    (time.0 as u128 * 1_000_000_000 / time.1 as u128) as u64
+}
+
+fn reduce(time: Rational) -> Rational {
+    let divisor = gcd(time.0, time.1).max(1);
+    (time.0 / divisor, time.1 / divisor)
+}
+
+/// least common multiple of two reduced fractions: lcm(numerators) / gcd(denominators)
+fn hyperperiod(a: Rational, b: Rational) -> Rational {
+    let numerator = a.0 / gcd(a.0, b.0) * b.0;
+    reduce((numerator, gcd(a.1, b.1)))
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// checks if the port supports the cycle time. The cycle time has to be representable as
+/// RationalGrouping, every time-interval-value of its gate control list has to fit into 32 bit ns
+/// and it must not exceed the supported-cycle-max if the port reports it.
+fn supports_cycle_time(topology: &Topology, port: &PortKey, cycle_time: Rational) -> bool {
+    if !fits_rational_grouping(cycle_time) || to_ns(cycle_time) > u32::MAX as u64 {
+        return false;
+    }
+
+    let cycle_max: RationalGrouping = match port_information(topology, port.0, &port.1) {
+        Some(port) => port.supported_cycle_max,
+        None => return true, // e.g. end stations
+    };
+    if cycle_max.0 <= 0 || cycle_max.1 <= 0 {
+        return true; // not reported
+    }
+
+    cycle_time.0 as u128 * cycle_max.1 as u128 <= cycle_max.0 as u128 * cycle_time.1 as u128
+}
+
+/// checks if the reduced fraction is representable as RationalGrouping
+fn fits_rational_grouping(time: Rational) -> bool {
+    let time = reduce(time);
+    time.0 <= i32::MAX as u64 && time.1 <= i32::MAX as u64
+}
+
+/// checks if the stream gate of a stream with this interval can be configured: the cycle time has
+/// to be representable as RationalGrouping and every time-interval-value has to fit into 32 bit ns
+fn supports_stream_gate(stream_interval: Rational) -> bool {
+    fits_rational_grouping(stream_interval) && to_ns(stream_interval) <= u32::MAX as u64
+}
+
+/// checks if the node is a bridge in the topology
+fn is_bridge(topology: &Topology, node_id: u32) -> bool {
+    topology
+        .nodes
+        .iter()
+        .any(|n| n.id == node_id && n.endstation == NodeType::Bridge)
+}
+
+/// the port of a bridge in the topology
+fn port_information<'t>(topology: &'t Topology, node_id: u32, port_name: &str) -> Option<&'t Port> {
+    topology
+        .nodes
+        .iter()
+        .find(|n| n.id == node_id && n.endstation == NodeType::Bridge)?
+        .ports
+        .iter()
+        .find(|p| p.name == port_name)
+}
+
+/// traffic class of the stream by the priority of its VLAN tag
+fn traffic_class(stream: &Stream) -> u8 {
+    stream
+        .talker
+        .group_talker
+        .data_frame_specification
+        .iter()
+        .find_map(|spec| match &spec.field {
+            DataFrameSpecificationElementType::Ieee802VlanTag(tag) => Some(tag.priority_code_point),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_TRAFFIC_CLASS)
+}
+
+fn is_credit_based(stream: &Stream) -> bool {
+    stream
+        .talker
+        .group_talker
+        .traffic_specification
+        .transmission_selection
+        == CREDIT_BASED_SHAPER
+}
+
+/// the bandwidth the stream needs in bit/s, including the media-specific overhead. None if the
+/// interval is zero.
+fn reserved_rate(stream: &Stream) -> Option<u64> {
+    let interval = interval(stream)?;
+    let traffic_specification = &stream.talker.group_talker.traffic_specification;
+    let bits_per_interval = traffic_specification.max_frames_per_interval as u64
+        * (traffic_specification.max_frame_size as u64 + FRAME_OVERHEAD)
+        * 8;
+
+    // bits / (numerator / denominator s) = bit/s
+    Some((bits_per_interval * interval.1).div_ceil(interval.0))
+}
+
+/// the delays of the port. If the port supports multiple speeds, the fastest one is used.
+fn port_delays<'t>(topology: &'t Topology, port: (u32, &str)) -> Option<&'t BridgePortDelays> {
+    topology
+        .nodes
+        .iter()
+        .find(|n| n.id == port.0)?
+        .ports
+        .iter()
+        .find(|p| p.name == port.1)?
+        .delays
+        .iter()
+        .max_by_key(|d| d.port_speed)
+}
+
+/// duration to transmit a single frame of the stream on the port in ns
+fn transmission_time(topology: &Topology, port: (u32, &str), frame_size: u64) -> u64 {
+    // bits / (Mb/s) = us
+    ((frame_size + FRAME_OVERHEAD) * 8 * 1000).div_ceil(port_speed(topology, port))
+}
+
+/// speed of the port in Mb/s
+fn port_speed(topology: &Topology, port: (u32, &str)) -> u64 {
+    port_delays(topology, port)
+        .map(|d| d.port_speed as u64)
+        .filter(|speed| *speed > 0)
+        .unwrap_or(DEFAULT_PORT_SPEED)
+}
+
+/// computes when the frames of the stream are transmitted on each egress port along the trees.
+fn stream_timing(topology: &Topology, stream: &Stream, trees: &Vec<Tree>) -> StreamTiming {
+    let traffic_specification = &stream.talker.group_talker.traffic_specification;
+    let frame_size = traffic_specification.max_frame_size as u64;
+    let frames = (traffic_specification.max_frames_per_interval as u64).max(1);
+
+    let mut timing = StreamTiming {
+        transmissions: Vec::new(),
+        receptions: Vec::new(),
+        listener_latencies: vec![0; stream.listener.len()],
+    };
+
+    for tree in trees.iter() {
+        for (listener, route) in tree.routes.iter().enumerate() {
+            let mut time: u64 = 0;
+
+            for (hop, next_hop) in route.hops.iter().zip(route.hops.iter().skip(1)) {
+                let egress_port = match &hop.egress_port {
+                    Some(egress_port) => egress_port,
+                    None => break,
+                };
+                let egress = (hop.node_id, egress_port.as_str());
+                let frame_time = transmission_time(topology, egress, frame_size);
+
+                let transmission = Transmission {
+                    port: (hop.node_id, egress_port.clone()),
+                    start: time,
+                    duration: frame_time * frames,
+                };
+                if !timing.transmissions.contains(&transmission) {
+                    timing.transmissions.push(transmission);
+                }
+
+                // store and forward: the next bridge starts when the first frame is received
+                let tx_delay = port_delays(topology, egress)
+                    .map(|d| d.independent_tx_delay_max)
+                    .unwrap_or(0);
+
+                if let Some(ingress_port) = &next_hop.ingress_port {
+                    let reception = Transmission {
+                        port: (next_hop.node_id, ingress_port.clone()),
+                        start: time + tx_delay,
+                        duration: frame_time * frames,
+                    };
+                    if !timing.receptions.contains(&reception) {
+                        timing.receptions.push(reception);
+                    }
+                }
+                let rx_delay = next_hop
+                    .ingress_port
+                    .as_ref()
+                    .and_then(|port| port_delays(topology, (next_hop.node_id, port.as_str())))
+                    .map(|d| d.independent_rx_delay_max + d.dependent_rx_delay_max)
+                    .unwrap_or(0);
+                let relay_delay = next_hop
+                    .egress_port
+                    .as_ref()
+                    .and_then(|port| port_delays(topology, (next_hop.node_id, port.as_str())))
+                    .map(|d| d.independent_rly_delay_max)
+                    .unwrap_or(0);
+
+                if next_hop.egress_port.is_none() {
+                    // the listener has received all frames
+                    time += frame_time * frames + tx_delay + rx_delay;
+                } else {
+                    time += frame_time + tx_delay + rx_delay + relay_delay;
+                }
+            }
+
+            let latency = &mut timing.listener_latencies[listener];
+            *latency = (*latency).max(time);
+        }
+    }
+
+    timing
+}
+
+/// finds the earliest time-aware-offset of the talker, where the transmissions of the stream don't
+/// overlap with other streams on any port in any interval of the port cycles.
+///
+/// The cycles of the crossed ports are extended to the hyperperiod including the interval of the
+/// stream. On success the cycles and windows are reserved and the offset with the latencies of all
+/// listeners is returned.
+fn place_stream(
+    topology: &Topology,
+    stream: &Stream,
+    trees: &Vec<Tree>,
+    ports: &mut HashMap<PortKey, PortSchedule>,
+) -> Result<(u64, Vec<u64>), u32> {
+    let stream_interval = match interval(stream) {
+        Some(stream_interval) => stream_interval,
+        None => return Err(INSUFFICIENT_BANDWIDTH),
+    };
+    let interval = to_ns(stream_interval);
+
+    // the stream gates on the ingress ports repeat with the interval of the stream
+    if !supports_stream_gate(stream_interval) {
+        println!(
+            "[Scheduler] interval {}/{} s of stream {} can't be configured in a stream gate",
+            stream_interval.0, stream_interval.1, stream.stream_id
+        );
+        return Err(INSUFFICIENT_BRIDGE_RESOURCES);
+    }
+
+    let time_aware = &stream.talker.group_talker.traffic_specification.time_aware;
+    let earliest = time_aware.earliest_transmit_offset as u64;
+    let latest = (time_aware.latest_transmit_offset as u64).max(earliest);
+    let max_latency = stream
+        .talker
+        .group_talker
+        .user_to_network_requirements
+        .max_latency as u64;
+
+    let timing = stream_timing(topology, stream, trees);
+
+    // the frames of one interval have to be transmitted before the next interval starts
+    if timing.transmissions.iter().any(|t| t.duration > interval) {
+        return Err(INSUFFICIENT_BANDWIDTH);
+    }
+
+    // the ports with the cycles extended by this stream. Only applied if the stream is placed.
+    let mut extended: HashMap<PortKey, PortSchedule> = HashMap::new();
+    for transmission in timing.transmissions.iter() {
+        if extended.contains_key(&transmission.port) {
+            continue;
+        }
+
+        let mut port = ports
+            .get(&transmission.port)
+            .cloned()
+            .unwrap_or_else(|| PortSchedule::new(stream_interval));
+        let cycle_time = hyperperiod(port.cycle_time, stream_interval);
+
+        if !supports_cycle_time(topology, &transmission.port, cycle_time) {
+            println!(
+                "[Scheduler] cycle time {}/{} s is not supported by port {} of node {}",
+                cycle_time.0, cycle_time.1, transmission.port.1, transmission.port.0
+            );
+            return Err(INSUFFICIENT_BRIDGE_RESOURCES);
+        }
+
+        port.extend(cycle_time);
+        extended.insert(transmission.port.clone(), port);
+    }
+
+    let mut offset = earliest;
+    'search: while offset <= latest {
+        // the latency is measured from the start of the interval
+        let latency = offset + timing.listener_latencies.iter().max().copied().unwrap_or(0);
+        if max_latency > 0 && latency > max_latency {
+            return Err(MAX_LATENCY_EXCEEDED);
+        }
+
+        for transmission in timing.transmissions.iter() {
+            let port = &extended[&transmission.port];
+            let cycle_time = port.cycle_ns();
+
+            for instance in 0..cycle_time / interval {
+                let start = (offset + transmission.start + instance * interval) % cycle_time;
+                let end = start + transmission.duration;
+
+                if let Some(shift) =
+                    conflict(&port.windows, &stream.stream_id, start, end, cycle_time)
+                {
+                    offset += shift;
+                    continue 'search;
+                }
+            }
+        }
+
+        // no conflicts
+        for transmission in timing.transmissions.iter() {
+            let port = extended.get_mut(&transmission.port).unwrap();
+            let cycle_time = port.cycle_ns();
+
+            for instance in 0..cycle_time / interval {
+                let start = (offset + transmission.start + instance * interval) % cycle_time;
+                port.windows.push(Window {
+                    start,
+                    end: start + transmission.duration,
+                    stream_id: stream.stream_id.clone(),
+                    traffic_class: traffic_class(stream),
+                });
+            }
+        }
+        ports.extend(extended);
+
+        let latencies = timing
+            .listener_latencies
+            .iter()
+            .map(|latency| offset + latency)
+            .collect();
+        return Ok((offset, latencies));
+    }
+
+    Err(INSUFFICIENT_BANDWIDTH)
+}
+
+/// reserves the bandwidth of a credit-based shaped stream on all egress ports along the trees.
+///
+/// On success the latencies of all listeners are returned. These don't include the time the
+/// frames wait for credit in the bridges.
+fn reserve_bandwidth(
+    topology: &Topology,
+    stream: &Stream,
+    trees: &Vec<Tree>,
+    bandwidth: &mut HashMap<PortKey, PortBandwidth>,
+) -> Result<Vec<u64>, u32> {
+    let idle_slope = match reserved_rate(stream) {
+        Some(idle_slope) => idle_slope,
+        None => return Err(INSUFFICIENT_BANDWIDTH),
+    };
+
+    let timing = stream_timing(topology, stream, trees);
+    let max_latency = stream
+        .talker
+        .group_talker
+        .user_to_network_requirements
+        .max_latency as u64;
+    let latency = timing.listener_latencies.iter().max().copied().unwrap_or(0);
+    if max_latency > 0 && latency > max_latency {
+        return Err(MAX_LATENCY_EXCEEDED);
+    }
+
+    let mut egress_ports: Vec<&PortKey> = timing.transmissions.iter().map(|t| &t.port).collect();
+    egress_ports.sort();
+    egress_ports.dedup();
+
+    for port in egress_ports.iter() {
+        let reserved: u64 = bandwidth
+            .get(*port)
+            .map(|b| b.idle_slopes.values().sum())
+            .unwrap_or(0);
+        let available =
+            port_speed(topology, (port.0, port.1.as_str())) * 1_000_000 * MAX_RESERVED_BANDWIDTH
+                / 100;
+
+        if reserved + idle_slope > available {
+            println!(
+                "[Scheduler] port {} of node {} has no bandwidth left for stream {}",
+                port.1, port.0, stream.stream_id
+            );
+            return Err(INSUFFICIENT_BANDWIDTH);
+        }
+    }
+
+    for port in egress_ports {
+        let port_bandwidth = bandwidth.entry(port.clone()).or_default();
+        *port_bandwidth
+            .idle_slopes
+            .entry(traffic_class(stream))
+            .or_default() += idle_slope;
+        port_bandwidth.streams.push(stream.stream_id.clone());
+    }
+
+    Ok(timing.listener_latencies)
+}
+
+/// derives the stream filter, stream gate and flow meter of the stream on every bridge it is
+/// received on. The instances of a bridge are identified by the stream handle.
+///
+/// A bridge can receive the stream on multiple ingress ports, if it has more than one tree. The
+/// instances belong to the bridge, so they are added only to the first of these ports and admit
+/// all copies of the stream.
+fn police_stream(
+    topology: &Topology,
+    stream: &Stream,
+    trees: &Vec<Tree>,
+    offset: Option<u64>,
+    stream_handle: u32,
+    psfp: &mut HashMap<PortKey, Vec<(String, PsfpEntry)>>,
+) {
+    let (stream_interval, rate) = match (interval(stream), reserved_rate(stream)) {
+        (Some(stream_interval), Some(rate)) => (stream_interval, rate),
+        _ => return,
+    };
+    let traffic_specification = &stream.talker.group_talker.traffic_specification;
+    let burst_size = traffic_specification.max_frames_per_interval as u64
+        * (traffic_specification.max_frame_size as u64 + FRAME_OVERHEAD);
+
+    let timing = stream_timing(topology, stream, trees);
+    let mut receptions: BTreeMap<u32, Vec<&Transmission>> = BTreeMap::new();
+    for reception in timing.receptions.iter() {
+        if is_bridge(topology, reception.port.0) {
+            receptions
+                .entry(reception.port.0)
+                .or_default()
+                .push(reception);
+        }
+    }
+
+    for receptions in receptions.into_values() {
+        let ingress_port = receptions
+            .iter()
+            .map(|r| &r.port)
+            .min()
+            .expect("bridges are only added with receptions")
+            .clone();
+        let copies = receptions.len() as u64;
+
+        let stream_gate = match offset {
+            Some(offset) => stream_gate(stream_handle, stream_interval, offset, &receptions),
+            None => open_stream_gate(stream_handle),
+        };
+
+        let entry = PsfpEntry {
+            stream_filter: StreamFilterInstance {
+                stream_filter_instance_id: stream_handle,
+                stream_handle: Some(stream_handle),
+                priority_spec: None,
+                max_sdu_size: traffic_specification.max_frame_size as u32,
+                stream_blocked_due_to_oversize_frame_enabled: false,
+                stream_gate_ref: stream_handle,
+                flow_meter_instance_id: Some(stream_handle),
+            },
+            stream_gate,
+            // the overhead is included, so frames of a conforming talker are never discarded
+            flow_meter: FlowMeterInstance {
+                flow_meter_instance_id: stream_handle,
+                committed_information_rate: rate * copies,
+                committed_burst_size: (burst_size * copies) as u32,
+                excess_information_rate: 0,
+                excess_burst_size: 0,
+                drop_on_yellow: true,
+                mark_all_frames_red_enable: false,
+            },
+        };
+        psfp.entry(ingress_port)
+            .or_default()
+            .push((stream.stream_id.clone(), entry));
+    }
+}
+
+/// stream gate of a scheduled stream. The gate repeats with the interval of the stream and is
+/// only open while the frames are received, extended by the STREAM_GATE_MARGIN.
+///
+/// Scheduled streams are only placed if their interval passes supports_stream_gate(...).
+fn stream_gate(
+    stream_gate_instance_id: u32,
+    stream_interval: Rational,
+    offset: u64,
+    receptions: &Vec<&Transmission>,
+) -> StreamGateInstance {
+    let stream_interval = reduce(stream_interval);
+    let cycle_time = to_ns(stream_interval);
+
+    let mut open: Vec<(u64, u64)> = Vec::new();
+    for reception in receptions.iter() {
+        if reception.duration + 2 * STREAM_GATE_MARGIN >= cycle_time {
+            return open_stream_gate(stream_gate_instance_id);
+        }
+
+        // shifted by one cycle, so the margin can't underflow
+        let start = offset + reception.start + cycle_time - STREAM_GATE_MARGIN;
+        let end = start + reception.duration + 2 * STREAM_GATE_MARGIN;
+        open.extend(split_at_cycle(start, end, cycle_time));
+    }
+
+    let mut boundaries: Vec<u64> = vec![0, cycle_time];
+    boundaries.extend(open.iter().flat_map(|(start, end)| [*start, *end]));
+    boundaries.sort();
+    boundaries.dedup();
+
+    let mut admin_control_list: Vec<StreamGateControlEntry> = Vec::new();
+    for segment in boundaries.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let gate_state_value = match open.iter().any(|(s, e)| *s <= start && end <= *e) {
+            true => StreamGateState::Open,
+            false => StreamGateState::Closed,
+        };
+
+        match admin_control_list.last_mut() {
+            Some(last) if last.gate_state_value == gate_state_value => {
+                last.time_interval_value += time_interval(start, end);
+            }
+            _ => admin_control_list.push(StreamGateControlEntry {
+                gate_state_value,
+                ipv_spec: None,
+                time_interval_value: time_interval(start, end),
+                interval_octet_max: None,
+            }),
+        }
+    }
+
+    StreamGateInstance {
+        stream_gate_instance_id,
+        gate_enable: true,
+        admin_gate_states: StreamGateState::Open,
+        admin_ipv: None,
+        admin_control_list,
+        admin_cycle_time: (
+            i32::try_from(stream_interval.0).expect("checked by supports_stream_gate"),
+            i32::try_from(stream_interval.1).expect("checked by supports_stream_gate"),
+        ),
+        // aligned with the PTP epoch like the gate control lists of the ports
+        admin_base_time: (0, 0),
+        config_change: true,
+    }
+}
+
+/// the time-interval-value of a stream gate control entry. The entries lie within the cycle time,
+/// which supports_stream_gate(...) limits to 32 bit ns.
+fn time_interval(start: u64, end: u64) -> u32 {
+    u32::try_from(end - start).expect("checked by supports_stream_gate")
+}
+
+/// stream gate that is always open, e.g. for credit-based shaped streams
+fn open_stream_gate(stream_gate_instance_id: u32) -> StreamGateInstance {
+    StreamGateInstance {
+        stream_gate_instance_id,
+        gate_enable: false,
+        admin_gate_states: StreamGateState::Open,
+        admin_ipv: None,
+        admin_control_list: Vec::new(),
+        // not used while the gate is disabled
+        admin_cycle_time: (1, 1000),
+        admin_base_time: (0, 0),
+        config_change: true,
+    }
+}
+
+/// derives the stream identification of the stream on every bridge it is received on. Like the
+/// PSFP instances, it is added to the first ingress port of the bridge.
+///
+/// If the stream has more than one tree, the FRER functions are added as well: the bridge where
+/// the trees split generates the sequence numbers and encodes them on its egress ports. The bridges
+/// where the trees merge decode the sequence numbers on their ingress ports and discard duplicates
+/// on their egress ports. If the trees already split at the talker, it has to generate the sequence
+/// numbers itself.
+fn identify_stream(
+    topology: &Topology,
+    stream: &Stream,
+    trees: &Vec<Tree>,
+    stream_handle: u32,
+    latencies: &Vec<u64>,
+    frer: &mut HashMap<PortKey, PortFrer>,
+) {
+    let identification =
+        stream_identification(stream).expect("unidentifiable streams aren't placed");
+
+    let tree_ports = tree_ports(trees);
+
+    let mut nodes: NodePorts = BTreeMap::new();
+    for (node_id, (ingress, egress)) in tree_ports.iter().flatten() {
+        let (all_ingress, all_egress) = nodes.entry(*node_id).or_default();
+        all_ingress.extend(ingress.iter().cloned());
+        all_egress.extend(egress.iter().cloned());
+    }
+
+    for (node_id, (ingress, _)) in nodes.iter() {
+        let first_ingress = match ingress.first() {
+            Some(port) if is_bridge(topology, *node_id) => port,
+            _ => continue, // the talker and end stations
+        };
+        port_frer(frer, *node_id, first_ingress, &stream.stream_id)
+            .stream_identification
+            .push(StreamIdentityEntry {
+                index: stream_handle,
+                handle: stream_handle,
+                in_facing_input_port_list: ingress.iter().cloned().collect(),
+                identification: identification.clone(),
+            });
+    }
+
+    if trees.len() < 2 {
+        return;
+    }
+
+    // the talker generates the sequence numbers itself if the trees split there, streams of
+    // talkers without FRER are failed before (see fail_unreplicated_streams)
+    match split_node(trees, &tree_ports) {
+        Some(node_id) if is_bridge(topology, node_id) => {
+            let (ingress, egress) = &nodes[&node_id];
+            let first_ingress = ingress.first().expect("bridges have an ingress port");
+            port_frer(frer, node_id, first_ingress, &stream.stream_id)
+                .frer
+                .sequence_generation
+                .push(SequenceGenerationEntry {
+                    index: stream_handle,
+                    stream_list: vec![stream_handle],
+                    direction_out_facing: false,
+                });
+            for port in egress.iter() {
+                port_frer(frer, node_id, port, &stream.stream_id)
+                    .frer
+                    .sequence_identification
+                    .push(SequenceIdentificationEntry {
+                        stream_list: vec![stream_handle],
+                        direction_out_facing: true,
+                        active: true,
+                    });
+            }
+        }
+        _ => (),
+    }
+
+    // the recovery has to remember the sequence numbers of all frames on the way
+    let traffic_specification = &stream.talker.group_talker.traffic_specification;
+    let interval = interval_ns(stream).max(1);
+    let latency = latencies.iter().max().copied().unwrap_or(0);
+    let history_length = (traffic_specification.max_frames_per_interval as u64
+        * (latency.div_ceil(interval) + 1))
+        .max(2) as u32;
+
+    // the trees merge at bridges, where they have different ingress ports
+    for (node_id, (ingress, egress)) in nodes.iter() {
+        let tree_ingress: BTreeSet<&BTreeSet<String>> = tree_ports
+            .iter()
+            .filter_map(|ports| ports.get(node_id).map(|(ingress, _)| ingress))
+            .collect();
+        if tree_ingress.len() < 2 || !is_bridge(topology, *node_id) {
+            continue;
+        }
+
+        for port in ingress.iter() {
+            port_frer(frer, *node_id, port, &stream.stream_id)
+                .frer
+                .sequence_identification
+                .push(SequenceIdentificationEntry {
+                    stream_list: vec![stream_handle],
+                    direction_out_facing: false,
+                    active: false,
+                });
+        }
+
+        let first_egress = match egress.first() {
+            Some(port) => port,
+            None => continue,
+        };
+        port_frer(frer, *node_id, first_egress, &stream.stream_id)
+            .frer
+            .sequence_recovery
+            .push(SequenceRecoveryEntry {
+                index: stream_handle,
+                stream_list: vec![stream_handle],
+                port_list: egress.iter().cloned().collect(),
+                direction_out_facing: true,
+                algorithm: SequenceRecoveryAlgorithm::Vector,
+                history_length,
+                // reset if no frame was received for two intervals
+                reset_timeout: (2 * interval).div_ceil(1_000_000) as u32,
+                take_no_sequence: false,
+                individual_recovery: false,
+            });
+    }
+}
+
+/// ingress and egress ports of every node by node_id
+type NodePorts = BTreeMap<u32, (BTreeSet<String>, BTreeSet<String>)>;
+
+/// ingress and egress ports of every node per tree
+fn tree_ports(trees: &Vec<Tree>) -> Vec<NodePorts> {
+    let mut tree_ports: Vec<NodePorts> = Vec::new();
+    for tree in trees.iter() {
+        let mut ports: NodePorts = BTreeMap::new();
+        for hop in tree.routes.iter().flat_map(|r| r.hops.iter()) {
+            let (ingress, egress) = ports.entry(hop.node_id).or_default();
+            ingress.extend(hop.ingress_port.iter().cloned());
+            egress.extend(hop.egress_port.iter().cloned());
+        }
+        tree_ports.push(ports);
+    }
+    tree_ports
+}
+
+/// the node where the trees split: the first node, where they have different egress ports. None
+/// if the stream has less than two trees.
+fn split_node(trees: &Vec<Tree>, tree_ports: &Vec<NodePorts>) -> Option<u32> {
+    let first_route = trees.first()?.routes.first()?;
+    first_route.hops.iter().map(|h| h.node_id).find(|node_id| {
+        let egress: Vec<Option<&BTreeSet<String>>> = tree_ports
+            .iter()
+            .map(|ports| ports.get(node_id).map(|(_, egress)| egress))
+            .collect();
+        egress.iter().any(|e| *e != egress[0])
+    })
+}
+
+/// fails the streams whose trees split at the talker, if the talker doesn't support FRER. Without
+/// generating sequence numbers, the talker can't replicate the stream, so the listeners would only
+/// receive it on one of the trees.
+fn fail_unreplicated_streams(
+    topology: &Topology,
+    domains: &mut Vec<Domain>,
+    trees: &HashMap<String, Vec<Tree>>,
+    failed_streams: &mut Vec<FailedStream>,
+) {
+    for domain in domains.iter_mut() {
+        for cuc in domain.cuc.iter_mut() {
+            for stream in cuc.stream.iter_mut() {
+                let stream_trees = match trees.get(&stream.stream_id) {
+                    Some(stream_trees) if stream_trees.len() > 1 => stream_trees,
+                    _ => continue,
+                };
+                if failed_streams
+                    .iter()
+                    .any(|f| f.stream_id == stream.stream_id)
+                {
+                    continue;
+                }
+
+                let capabilities = &stream.talker.group_talker.interface_capabilities;
+                let splits_at_talker = matches!(
+                    split_node(stream_trees, &tree_ports(stream_trees)),
+                    Some(node_id) if !is_bridge(topology, node_id)
+                );
+                if !splits_at_talker || !capabilities.cb_sequence_type_list.is_empty() {
+                    continue;
+                }
+
+                println!(
+                    "[Scheduler] the trees of stream {} split at the talker, which doesn't support FRER",
+                    stream.stream_id
+                );
+
+                stream.group_status_stream.status_info.failure_code =
+                    INSUFFICIENT_BRIDGE_RESOURCES as i32;
+                failed_streams.push(FailedStream {
+                    stream_id: stream.stream_id.clone(),
+                    cuc_id: cuc.cuc_id.clone(),
+                    domain_id: domain.domain_id.clone(),
+                    failure_code: INSUFFICIENT_BRIDGE_RESOURCES,
+                });
+            }
+        }
+    }
+}
+
+/// identification of the stream by its data-frame-specification. IP Stream identification is used
+/// if the talker provides an IP tuple, otherwise Null Stream identification. None if the stream
+/// can't be identified.
+fn stream_identification(stream: &Stream) -> Option<StreamIdentificationType> {
+    let mut destination_mac: Option<String> = None;
+    let mut tagged = VlanTagIdentification::All;
+    let mut vlan: u16 = 0;
+    let mut ip_stream: Option<IpStreamIdentification> = None;
+
+    for spec in stream.talker.group_talker.data_frame_specification.iter() {
+        match &spec.field {
+            DataFrameSpecificationElementType::Ieee802MacAddresses(x) => {
+                destination_mac = Some(x.destination_mac_adress.clone());
+            }
+            DataFrameSpecificationElementType::Ieee802VlanTag(x) => {
+                tagged = match x.vlan_id {
+                    0 => VlanTagIdentification::Priority,
+                    _ => VlanTagIdentification::Tagged,
+                };
+                vlan = x.vlan_id;
+            }
+            DataFrameSpecificationElementType::Ipv4Tuple(x) => {
+                ip_stream = Some(IpStreamIdentification {
+                    destination_mac: None,
+                    tagged,
+                    vlan,
+                    ip_source: x.source_ip_adress.into(),
+                    ip_destination: x.destination_ip_adress.into(),
+                    dscp: x.dscp,
+                    next_protocol: x.protocol,
+                    source_port: x.source_port,
+                    destination_port: x.destination_port,
+                });
+            }
+            DataFrameSpecificationElementType::Ipv6Tuple(x) => {
+                ip_stream = Some(IpStreamIdentification {
+                    destination_mac: None,
+                    tagged,
+                    vlan,
+                    ip_source: x.source_ip_adress.into(),
+                    ip_destination: x.destination_ip_adress.into(),
+                    dscp: x.dscp,
+                    next_protocol: x.protocol,
+                    source_port: x.source_port,
+                    destination_port: x.destination_port,
+                });
+            }
+        }
+    }
+
+    // the elements can be in any order
+    match (ip_stream, destination_mac) {
+        (Some(ip_stream), destination_mac) => {
+            Some(StreamIdentificationType::IpStream(IpStreamIdentification {
+                destination_mac,
+                tagged,
+                vlan,
+                ..ip_stream
+            }))
+        }
+        (None, Some(destination_mac)) => Some(StreamIdentificationType::NullStream(
+            NullStreamIdentification {
+                destination_mac,
+                tagged,
+                vlan,
+            },
+        )),
+        (None, None) => None,
+    }
+}
+
+/// derives the static filtering entries of the stream on every bridge egress port along the trees,
+/// so its frames are only forwarded along them. If the stream has a VLAN ID, all bridge ports along
+/// the trees are registered as tagged members of the VLAN.
+fn forward_stream(
+    topology: &Topology,
+    stream: &Stream,
+    trees: &Vec<Tree>,
+    forwarding: &mut HashMap<PortKey, PortForwarding>,
+) {
+    let mut destination_mac: Option<String> = None;
+    let mut vlan_id: u16 = 0;
+    for spec in stream.talker.group_talker.data_frame_specification.iter() {
+        match &spec.field {
+            DataFrameSpecificationElementType::Ieee802MacAddresses(x) => {
+                destination_mac = Some(x.destination_mac_adress.clone());
+            }
+            DataFrameSpecificationElementType::Ieee802VlanTag(x) => vlan_id = x.vlan_id,
+            _ => (),
+        }
+    }
+
+    let vid = match vlan_id {
+        0 => DEFAULT_VID,
+        vlan_id => vlan_id,
+    };
+
+    for hop in trees
+        .iter()
+        .flat_map(|t| t.routes.iter())
+        .flat_map(|r| r.hops.iter())
+    {
+        if !is_bridge(topology, hop.node_id) {
+            continue;
+        }
+
+        if let (Some(egress_port), Some(address)) = (&hop.egress_port, &destination_mac) {
+            let entry = StaticFilteringEntry {
+                address: address.clone(),
+                vid,
+                control_element: StaticFilteringControl::Forward,
+            };
+            let port_forwarding = port_forwarding(forwarding, hop.node_id, egress_port, stream);
+            if !port_forwarding.static_filtering.contains(&entry) {
+                port_forwarding.static_filtering.push(entry);
+            }
+        }
+
+        if vlan_id == 0 {
+            continue; // all ports are members of the default VLAN
+        }
+        for port in hop.ingress_port.iter().chain(hop.egress_port.iter()) {
+            let entry = VlanRegistrationEntry {
+                vid,
+                vlan_transmitted: VlanTransmitted::Tagged,
+            };
+            let port_forwarding = port_forwarding(forwarding, hop.node_id, port, stream);
+            if !port_forwarding.vlan_registration.contains(&entry) {
+                port_forwarding.vlan_registration.push(entry);
+            }
+        }
+    }
+}
+
+/// the static filtering and VLAN registration entries of the port, where the stream is added to
+fn port_forwarding<'f>(
+    forwarding: &'f mut HashMap<PortKey, PortForwarding>,
+    node_id: u32,
+    port: &String,
+    stream: &Stream,
+) -> &'f mut PortForwarding {
+    let port_forwarding = forwarding.entry((node_id, port.clone())).or_default();
+    if !port_forwarding.streams.contains(&stream.stream_id) {
+        port_forwarding.streams.push(stream.stream_id.clone());
+    }
+    port_forwarding
+}
+
+/// returns how far the window has to be moved to end the overlap with a reserved window of
+/// another stream. The reserved windows are also compared with the previous and next cycle.
+fn conflict(
+    reserved: &Vec<Window>,
+    stream_id: &String,
+    start: u64,
+    end: u64,
+    cycle_time: u64,
+) -> Option<u64> {
+    for window in reserved.iter().filter(|w| &w.stream_id != stream_id) {
+        for shift in [0, cycle_time, 2 * cycle_time] {
+            // window of the previous cycle is (start - cycle_time) and so on
+            let reserved_start = window.start + shift;
+            let reserved_end = window.end + shift;
+            let (start, end) = (start + cycle_time, end + cycle_time);
+
+            if reserved_start < end && start < reserved_end {
+                return Some(reserved_end - start);
+            }
+        }
+    }
+    None
+}
+
+/// writes the data-frame-specification of the stream in the network, the time-aware-offset and the
+/// latencies to the talker and listeners
+fn set_stream_configuration(
+    stream: &mut Stream,
+    specification: &Vec<DataFrameSpecificationElement>,
+    offset: Option<u64>,
+    latencies: &Vec<u64>,
+) {
+    let config_list = config_list(specification);
+
+    // the offset is only provided to the talker of scheduled streams
+    let mut talker_config_list = config_list.clone();
+    if let Some(offset) = offset {
+        let offset_index = config_list.iter().map(|c| c.index + 1).max().unwrap_or(0);
+        talker_config_list.push(ConfigListElement {
+            index: offset_index,
+            config_value: ConfigValue::TimeAwareOffset(offset as u32),
+        });
+    }
+
+    let talker = &mut stream.talker;
+    talker.group_status_talker_listener.accumulated_latency =
+        latencies.iter().max().copied().unwrap_or(0) as u32;
+    talker
+        .group_status_talker_listener
+        .interface_configuration
+        .interface_list = talker
+        .group_talker
+        .end_station_interfaces
+        .iter()
+        .map(|interface| InterfaceListElement {
+            group_interface_id: interface.clone(),
+            config_list: talker_config_list.clone(),
+        })
+        .collect();
+
+    for (listener, latency) in stream.listener.iter_mut().zip(latencies.iter()) {
+        listener.group_status_talker_listener.accumulated_latency = *latency as u32;
+        listener
+            .group_status_talker_listener
+            .interface_configuration
+            .interface_list = listener
+            .group_listener
+            .end_station_interfaces
+            .iter()
+            .map(|interface| InterfaceListElement {
+                group_interface_id: interface.clone(),
+                config_list: config_list.clone(),
+            })
+            .collect();
+    }
+
+    let status_info = &mut stream.group_status_stream.status_info;
+    status_info.talker_status = TalkerStatus::Ready;
+    status_info.listener_status = ListenerStatus::Ready;
+    status_info.failure_code = 0;
+}
+
+/// creates the gate control list of every bridge port with reserved windows.
+///
+/// During a window only the gate of its traffic class is open. Otherwise the gates of all
+/// traffic classes without windows are open, except for a guard band before each window.
+/// With frame preemption, the MAC is held from the guard band until the window ends.
+fn gate_control_lists(
+    topology: &Topology,
+    ports: &HashMap<PortKey, PortSchedule>,
+    frame_preemption: bool,
+) -> Vec<Config> {
+    let mut configs: Vec<Config> = Vec::new();
+
+    // sorted, so the configs are in the same order for the same schedule
+    let ports: BTreeMap<&PortKey, &PortSchedule> = ports.iter().collect();
+
+    for ((node_id, port_name), port) in ports {
+        let node = match topology.get_node_from_id(*node_id) {
+            Some(node) if node.endstation == NodeType::Bridge => node,
+            _ => continue, // end stations are not configured
+        };
+        if port.windows.is_empty() {
+            continue;
+        }
+
+        let port_windows = &port.windows;
+        let cycle_time = port.cycle_ns();
+
+        let scheduled_classes: u8 = port_windows
+            .iter()
+            .fold(0, |acc, w| acc | (1 << w.traffic_class));
+        let best_effort: u8 = !scheduled_classes;
+        let guard_band_frame_size = if frame_preemption {
+            PREEMPTION_GUARD_BAND_FRAME_SIZE
+        } else {
+            GUARD_BAND_FRAME_SIZE
+        };
+        let guard_band = transmission_time(
+            topology,
+            (*node_id, port_name.as_str()),
+            guard_band_frame_size,
+        );
+
+        // windows and guard bands within the cycle. Wrapping ones are split at the cycle end.
+        let mut scheduled: Vec<(u64, u64, u8)> = Vec::new();
+        let mut guard_bands: Vec<(u64, u64)> = Vec::new();
+        for window in port_windows.iter() {
+            let gate = 1 << window.traffic_class;
+            split_at_cycle(window.start, window.end, cycle_time)
+                .for_each(|(start, end)| scheduled.push((start, end, gate)));
+
+            let guard_start = window.start + cycle_time - guard_band.min(cycle_time);
+            split_at_cycle(guard_start, window.start + cycle_time, cycle_time)
+                .for_each(|(start, end)| guard_bands.push((start, end)));
+        }
+
+        let mut boundaries: Vec<u64> = vec![0, cycle_time];
+        boundaries.extend(scheduled.iter().flat_map(|(start, end, _)| [*start, *end]));
+        boundaries.extend(guard_bands.iter().flat_map(|(start, end)| [*start, *end]));
+        boundaries.sort();
+        boundaries.dedup();
+
+        let mut admin_control_list: Vec<GateControlEntry> = Vec::new();
+        for segment in boundaries.windows(2) {
+            let (start, end) = (segment[0], segment[1]);
+
+            // scheduled gates override guard bands, guard bands override best effort
+            let scheduled_gates = scheduled
+                .iter()
+                .filter(|(s, e, _)| *s <= start && end <= *e)
+                .fold(0, |acc, (_, _, gate)| acc | gate);
+            let in_guard_band = guard_bands.iter().any(|(s, e)| *s <= start && end <= *e);
+            let state = match (scheduled_gates, in_guard_band) {
+                (0, true) => 0,
+                (0, false) => best_effort,
+                (gates, _) => gates,
+            };
+            let operation_name = match (frame_preemption, state == best_effort) {
+                (false, _) => GateControlOperation::SetGateStates,
+                (true, false) => GateControlOperation::SetAndHoldMAC,
+                (true, true) => GateControlOperation::SetAndReleaseMAC,
+            };
+
+            match admin_control_list.last_mut() {
+                Some(last)
+                    if last.gate_state_value == state && last.operation_name == operation_name =>
+                {
+                    last.time_interval_value += (end - start) as u32;
+                }
+                _ => admin_control_list.push(GateControlEntry {
+                    operation_name,
+                    time_interval_value: (end - start) as u32,
+                    gate_state_value: state,
+                }),
+            }
+        }
+
+        let bridge_port = node.ports.iter().find(|p| &p.name == port_name);
+        let mac_address = bridge_port
+            .map(|p| p.mac_address.clone())
+            .unwrap_or_default();
+        let port_number = bridge_port.map(|p| p.port_number).unwrap_or(0);
+
+        let mut affected_streams: Vec<String> =
+            port_windows.iter().map(|w| w.stream_id.clone()).collect();
+        affected_streams.sort();
+        affected_streams.dedup();
+
+        // the scheduled traffic classes are express, all others can be preempted by them
+        let frame_preemption = match frame_preemption {
+            true => (0..8)
+                .map(|traffic_class| FramePreemptionStatusEntry {
+                    traffic_class,
+                    frame_preemption_status: match scheduled_classes & (1 << traffic_class) {
+                        0 => FramePreemptionStatus::Preemptable,
+                        _ => FramePreemptionStatus::Express,
+                    },
+                })
+                .collect(),
+            false => Vec::new(),
+        };
+
+        configs.push(Config {
+            node_id: *node_id,
+            port: PortConfiguration {
+                name: port_name.clone(),
+                mac_address,
+                port_number,
+                config: ConfigurableGateParameterTableEntry {
+                    gate_enable: true,
+                    admin_gate_states: 255,
+                    admin_control_list,
+                    admin_cycle_time: (port.cycle_time.0 as i32, port.cycle_time.1 as i32),
+                    admin_cycle_time_extension: 0,
+                    // aligned with the PTP epoch. The bridge starts at the next cycle boundary.
+                    admin_base_time: (0, 0),
+                    config_change: true,
+                },
+                frame_preemption,
+                fqtss: Vec::new(),
+                psfp: Vec::new(),
+                stream_identification: Vec::new(),
+                frer: FrerConfiguration::default(),
+                static_filtering: Vec::new(),
+                vlan_registration: Vec::new(),
+                released_static_filtering: Vec::new(),
+                released_vlan_registration: Vec::new(),
+            },
+            affected_streams,
+        });
+    }
+
+    configs
+}
+
+/// adds the credit-based shaper of every bridge port with reserved bandwidth to the configs.
+fn add_fqtss_configs(
+    topology: &Topology,
+    mut configs: Vec<Config>,
+    bandwidth: &HashMap<PortKey, PortBandwidth>,
+) -> Vec<Config> {
+    // sorted, so the configs are in the same order for the same schedule
+    let bandwidth: BTreeMap<&PortKey, &PortBandwidth> = bandwidth.iter().collect();
+
+    for (port, port_bandwidth) in bandwidth {
+        let config = match port_config(topology, &mut configs, port) {
+            Some(config) => config,
+            None => continue, // end stations are not configured
+        };
+
+        config.port.fqtss = port_bandwidth
+            .idle_slopes
+            .iter()
+            .map(|(traffic_class, idle_slope)| FqtssTrafficClassEntry {
+                traffic_class: *traffic_class,
+                transmission_selection_algorithm: TransmissionSelectionAlgorithm::CreditBasedShaper,
+                admin_idle_slope: *idle_slope,
+            })
+            .collect();
+        config
+            .affected_streams
+            .extend(port_bandwidth.streams.iter().cloned());
+        config.affected_streams.sort();
+        config.affected_streams.dedup();
+    }
+
+    configs
+}
+
+/// adds the per-stream filtering and policing of the streams to the configs of their ingress ports.
+fn add_psfp_configs(
+    topology: &Topology,
+    mut configs: Vec<Config>,
+    psfp: &HashMap<PortKey, Vec<(String, PsfpEntry)>>,
+) -> Vec<Config> {
+    // sorted, so the configs are in the same order for the same schedule
+    let psfp: BTreeMap<&PortKey, &Vec<(String, PsfpEntry)>> = psfp.iter().collect();
+
+    for (port, entries) in psfp {
+        let config = match port_config(topology, &mut configs, port) {
+            Some(config) => config,
+            None => continue, // end stations are not configured
+        };
+
+        // in the order of the stream handles
+        config.port.psfp = entries.iter().map(|(_, entry)| entry.clone()).collect();
+        config
+            .port
+            .psfp
+            .sort_by_key(|entry| entry.stream_filter.stream_filter_instance_id);
+        config
+            .affected_streams
+            .extend(entries.iter().map(|(stream_id, _)| stream_id.clone()));
+        config.affected_streams.sort();
+        config.affected_streams.dedup();
+    }
+
+    configs
+}
+
+/// the stream identification and FRER functions of the port, where the stream is added to
+fn port_frer<'f>(
+    frer: &'f mut HashMap<PortKey, PortFrer>,
+    node_id: u32,
+    port: &String,
+    stream_id: &String,
+) -> &'f mut PortFrer {
+    let port_frer = frer.entry((node_id, port.clone())).or_default();
+    if !port_frer.streams.contains(stream_id) {
+        port_frer.streams.push(stream_id.clone());
+    }
+    port_frer
+}
+
+/// adds the stream identification and FRER functions of the streams to the configs of their ports.
+fn add_frer_configs(
+    topology: &Topology,
+    mut configs: Vec<Config>,
+    frer: &HashMap<PortKey, PortFrer>,
+) -> Vec<Config> {
+    // sorted, so the configs are in the same order for the same schedule
+    let frer: BTreeMap<&PortKey, &PortFrer> = frer.iter().collect();
+
+    for (port, port_frer) in frer {
+        let config = match port_config(topology, &mut configs, port) {
+            Some(config) => config,
+            None => continue, // end stations are not configured
+        };
+
+        config.port.stream_identification = port_frer.stream_identification.clone();
+        config.port.frer = port_frer.frer.clone();
+        config
+            .affected_streams
+            .extend(port_frer.streams.iter().cloned());
+        config.affected_streams.sort();
+        config.affected_streams.dedup();
+    }
+
+    configs
+}
+
+/// adds the static filtering and VLAN registration entries of the streams to the configs of their
+/// ports.
+fn add_forwarding_configs(
+    topology: &Topology,
+    mut configs: Vec<Config>,
+    forwarding: &HashMap<PortKey, PortForwarding>,
+) -> Vec<Config> {
+    // sorted, so the configs are in the same order for the same schedule
+    let forwarding: BTreeMap<&PortKey, &PortForwarding> = forwarding.iter().collect();
+
+    for (port, port_forwarding) in forwarding {
+        let config = match port_config(topology, &mut configs, port) {
+            Some(config) => config,
+            None => continue, // end stations are not configured
+        };
+
+        config.port.static_filtering = port_forwarding.static_filtering.clone();
+        config.port.vlan_registration = port_forwarding.vlan_registration.clone();
+        config
+            .affected_streams
+            .extend(port_forwarding.streams.iter().cloned());
+        config.affected_streams.sort();
+        config.affected_streams.dedup();
+    }
+
+    configs
+}
+
+/// the config of a bridge port. Ports without scheduled streams get a config with disabled gates.
+/// None if the node isn't a bridge.
+fn port_config<'c>(
+    topology: &Topology,
+    configs: &'c mut Vec<Config>,
+    port: &PortKey,
+) -> Option<&'c mut Config> {
+    let (node_id, port_name) = port;
+    let node = match topology.get_node_from_id(*node_id) {
+        Some(node) if node.endstation == NodeType::Bridge => node,
+        _ => return None,
+    };
+
+    let index = match configs
+        .iter()
+        .position(|c| c.node_id == *node_id && &c.port.name == port_name)
+    {
+        Some(index) => index,
+        None => {
+            let bridge_port = node.ports.iter().find(|p| &p.name == port_name);
+            let mac_address = bridge_port
+                .map(|p| p.mac_address.clone())
+                .unwrap_or_default();
+            let port_number = bridge_port.map(|p| p.port_number).unwrap_or(0);
+
+            configs.push(Config {
+                node_id: *node_id,
+                port: PortConfiguration {
+                    name: port_name.clone(),
+                    mac_address,
+                    port_number,
+                    config: ConfigurableGateParameterTableEntry {
+                        gate_enable: false,
+                        admin_gate_states: 255,
+                        admin_control_list: Vec::new(),
+                        // not used while the gates are disabled
+                        admin_cycle_time: (1, 1000),
+                        admin_cycle_time_extension: 0,
+                        admin_base_time: (0, 0),
+                        config_change: true,
+                    },
+                    frame_preemption: Vec::new(),
+                    fqtss: Vec::new(),
+                    psfp: Vec::new(),
+                    stream_identification: Vec::new(),
+                    frer: FrerConfiguration::default(),
+                    static_filtering: Vec::new(),
+                    vlan_registration: Vec::new(),
+                    released_static_filtering: Vec::new(),
+                    released_vlan_registration: Vec::new(),
+                },
+                affected_streams: Vec::new(),
+            });
+            configs.len() - 1
+        }
+    };
+
+    Some(&mut configs[index])
+}
+
+/// splits a time span, that can reach into the next cycle, into the parts within the cycle
+fn split_at_cycle(start: u64, end: u64, cycle_time: u64) -> impl Iterator<Item = (u64, u64)> {
+    let (start, end) = (start % cycle_time, start % cycle_time + (end - start));
+
+    let parts = if end > cycle_time {
+        vec![(start, cycle_time), (0, end - cycle_time)]
+    } else {
+        vec![(start, end)]
+    };
+    parts.into_iter().filter(|(start, end)| start < end)
+}
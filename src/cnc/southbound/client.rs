@@ -0,0 +1,505 @@
+use ssh2::{Channel, Session};
+use std::io::{Error, ErrorKind, Read, Write};
+use std::time::Duration;
+
+/// end of a message in the framing of base:1.0 (RFC 6242 4.3)
+const DELIMITER: &str = "]]>]]>";
+
+/// end of a chunked message in the framing of base:1.1 (RFC 6242 4.2)
+const END_OF_CHUNKS: &str = "\n##\n";
+
+/// messages larger than this are rejected instead of being buffered
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+const NETCONF_BASE_NAMESPACE: &str = "urn:ietf:params:xml:ns:netconf:base:1.0";
+const NETCONF_BASE_1_0: &str = "urn:ietf:params:netconf:base:1.0";
+const NETCONF_BASE_1_1: &str = "urn:ietf:params:netconf:base:1.1";
+const NETCONF_MONITORING_NAMESPACE: &str = "urn:ietf:params:xml:ns:yang:ietf-netconf-monitoring";
+const NOTIFICATION_NAMESPACE: &str = "urn:ietf:params:xml:ns:netconf:notification:1.0";
+
+/// errors of the netconf-session
+#[derive(Debug)]
+pub enum NetconfClientError {
+    /// the ssh-channel of the netconf subsystem couldn't be opened
+    Ssh(ssh2::Error),
+
+    /// the transport failed or was closed
+    Io(Error),
+
+    /// the message of the server isn't a valid netconf-message
+    MalformedMessage(String),
+
+    /// the server replied with an <rpc-error>, contains the error-tag and error-message
+    Rpc(String),
+}
+
+impl From<Error> for NetconfClientError {
+    fn from(error: Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ssh2::Error> for NetconfClientError {
+    fn from(error: ssh2::Error) -> Self {
+        Self::Ssh(error)
+    }
+}
+
+/// the <hello>-Message of the netconf-server
+#[derive(Debug, Clone)]
+pub struct HelloServer {
+    pub session_id: Option<u32>,
+    pub capabilities: Vec<String>,
+}
+
+/// the <rpc-reply> of the netconf-server. Contains the content of <data>, if the reply has one.
+#[derive(Debug, Clone)]
+pub struct RpcReply {
+    pub data: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum DatastoreType {
+    Running,
+    Candidate,
+}
+
+impl DatastoreType {
+    fn element(&self) -> &'static str {
+        match self {
+            DatastoreType::Running => "<running/>",
+            DatastoreType::Candidate => "<candidate/>",
+        }
+    }
+}
+
+/// the byte-stream the netconf-messages are exchanged over
+pub trait Transport: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Transport for T {}
+
+/// netconf-client (RFC 6241) with the operations the southbound uses. The messages are exchanged
+/// over the netconf subsystem of an ssh-session, or any other transport.
+pub struct NetconfClient {
+    transport: Box<dyn Transport>,
+
+    /// keeps the ssh-session of the channel open
+    session: Option<Session>,
+
+    /// switches to the chunked framing after the <hello>-Messages, if both sides support base:1.1
+    chunked: bool,
+    message_id: u32,
+    buffer: Vec<u8>,
+}
+
+impl NetconfClient {
+    /// opens the netconf subsystem on the authenticated ssh-session
+    pub fn from_session(session: Session) -> Result<Self, NetconfClientError> {
+        let mut channel: Channel = session.channel_session()?;
+        channel.subsystem("netconf")?;
+
+        let mut client = Self::from_transport(channel);
+        client.session = Some(session);
+        Ok(client)
+    }
+
+    /// uses an already established transport, e.g. a tcp-connection to a local netconf-server
+    pub fn from_transport<T: Transport + 'static>(transport: T) -> Self {
+        Self {
+            transport: Box::new(transport),
+            session: None,
+            chunked: false,
+            message_id: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// receives the <hello>-Message of the server
+    pub fn connect(&mut self) -> Result<HelloServer, NetconfClientError> {
+        let message = self.read_message()?;
+        let hello = element(&message, "hello").ok_or(NetconfClientError::MalformedMessage(
+            String::from("expected <hello>"),
+        ))?;
+
+        let capabilities = elements(hello, "capability")
+            .into_iter()
+            .map(|capability| capability.trim().to_string())
+            .collect();
+        let session_id = element(hello, "session-id").and_then(|id| id.trim().parse().ok());
+
+        let hello_server = HelloServer {
+            session_id,
+            capabilities,
+        };
+        self.chunked = hello_server
+            .capabilities
+            .iter()
+            .any(|c| c == NETCONF_BASE_1_1);
+
+        Ok(hello_server)
+    }
+
+    /// sends the <hello>-Message of the client. Must be sent after connect().
+    pub fn send_hello(&mut self) -> Result<(), NetconfClientError> {
+        let hello = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><hello xmlns=\"{NETCONF_BASE_NAMESPACE}\"><capabilities><capability>{NETCONF_BASE_1_0}</capability><capability>{NETCONF_BASE_1_1}</capability></capabilities></hello>"
+        );
+
+        // the <hello> is always framed with the end-of-message delimiter
+        self.transport.write_all(hello.as_bytes())?;
+        self.transport.write_all(DELIMITER.as_bytes())?;
+        self.transport.flush()?;
+        Ok(())
+    }
+
+    pub fn get(&mut self, filter: Option<&str>) -> Result<RpcReply, NetconfClientError> {
+        self.rpc(&format!("<get>{}</get>", subtree_filter(filter)))
+    }
+
+    pub fn get_config(
+        &mut self,
+        source: DatastoreType,
+        filter: Option<&str>,
+    ) -> Result<RpcReply, NetconfClientError> {
+        self.rpc(&format!(
+            "<get-config><source>{}</source>{}</get-config>",
+            source.element(),
+            subtree_filter(filter)
+        ))
+    }
+
+    /// merges the config into the target. The config is tested before it's set and rolled back
+    /// on errors.
+    pub fn edit_config(
+        &mut self,
+        target: DatastoreType,
+        config: &str,
+    ) -> Result<RpcReply, NetconfClientError> {
+        self.rpc(&format!(
+            "<edit-config><target>{}</target><default-operation>merge</default-operation><test-option>test-then-set</test-option><error-option>rollback-on-error</error-option><config>{config}</config></edit-config>",
+            target.element()
+        ))
+    }
+
+    pub fn lock(&mut self, target: DatastoreType) -> Result<RpcReply, NetconfClientError> {
+        self.rpc(&format!(
+            "<lock><target>{}</target></lock>",
+            target.element()
+        ))
+    }
+
+    pub fn unlock(&mut self, target: DatastoreType) -> Result<RpcReply, NetconfClientError> {
+        self.rpc(&format!(
+            "<unlock><target>{}</target></unlock>",
+            target.element()
+        ))
+    }
+
+    pub fn validate(&mut self, source: DatastoreType) -> Result<RpcReply, NetconfClientError> {
+        self.rpc(&format!(
+            "<validate><source>{}</source></validate>",
+            source.element()
+        ))
+    }
+
+    pub fn commit(&mut self) -> Result<RpcReply, NetconfClientError> {
+        self.rpc("<commit/>")
+    }
+
+    /// <commit> with <confirmed/>, the server rolls back after the timeout in seconds
+    pub fn commit_confirmed(&mut self, timeout: u32) -> Result<RpcReply, NetconfClientError> {
+        self.rpc(&format!(
+            "<commit><confirmed/><confirm-timeout>{timeout}</confirm-timeout></commit>"
+        ))
+    }
+
+    pub fn cancel_commit(&mut self) -> Result<RpcReply, NetconfClientError> {
+        self.rpc("<cancel-commit/>")
+    }
+
+    pub fn discard_changes(&mut self) -> Result<RpcReply, NetconfClientError> {
+        self.rpc("<discard-changes/>")
+    }
+
+    pub fn close_session(&mut self) -> Result<RpcReply, NetconfClientError> {
+        self.rpc("<close-session/>")
+    }
+
+    /// <get-schema> of ietf-netconf-monitoring, the <data> contains the escaped schema
+    pub fn get_schema(
+        &mut self,
+        identifier: &str,
+        version: Option<&str>,
+        format: Option<&str>,
+    ) -> Result<RpcReply, NetconfClientError> {
+        let version = version
+            .map(|version| format!("<version>{}</version>", escape(version)))
+            .unwrap_or_default();
+        let format = format
+            .map(|format| format!("<format>{}</format>", escape(format)))
+            .unwrap_or_default();
+
+        self.rpc(&format!(
+            "<get-schema xmlns=\"{NETCONF_MONITORING_NAMESPACE}\"><identifier>{}</identifier>{version}{format}</get-schema>",
+            escape(identifier)
+        ))
+    }
+
+    /// <create-subscription> of RFC 5277, without a stream the server uses the NETCONF stream
+    pub fn create_subscription(
+        &mut self,
+        stream: Option<&str>,
+    ) -> Result<RpcReply, NetconfClientError> {
+        let stream = stream
+            .map(|stream| format!("<stream>{}</stream>", escape(stream)))
+            .unwrap_or_default();
+
+        self.rpc(&format!(
+            "<create-subscription xmlns=\"{NOTIFICATION_NAMESPACE}\">{stream}</create-subscription>"
+        ))
+    }
+
+    /// sets the timeout of the ssh-session in which the server has to answer. Zero waits without
+    /// timeout, e.g. for notifications. Other transports aren't affected.
+    pub fn set_timeout(&self, timeout: Duration) {
+        if let Some(session) = &self.session {
+            session.set_timeout(timeout.as_millis() as u32);
+        }
+    }
+
+    /// blocks until the next <notification> is received and returns the whole message
+    pub fn receive_notification(&mut self) -> Result<String, NetconfClientError> {
+        loop {
+            let message = self.read_message()?;
+            if element(&message, "notification").is_some() {
+                return Ok(message);
+            }
+        }
+    }
+
+    /// sends the operation in an <rpc> and waits for its <rpc-reply>. Notifications received in
+    /// between are dropped.
+    fn rpc(&mut self, operation: &str) -> Result<RpcReply, NetconfClientError> {
+        self.message_id += 1;
+        let message_id = self.message_id.to_string();
+        self.send_message(&format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><rpc message-id=\"{message_id}\" xmlns=\"{NETCONF_BASE_NAMESPACE}\">{operation}</rpc>"
+        ))?;
+
+        loop {
+            let message = self.read_message()?;
+            let (start_tag, reply) = match element_with_start_tag(&message, "rpc-reply") {
+                Some(reply) => reply,
+                None => continue,
+            };
+
+            if attribute(start_tag, "message-id").is_some_and(|id| id != message_id) {
+                continue;
+            }
+
+            if let Some(error) = element(reply, "rpc-error") {
+                let tag = element(error, "error-tag").unwrap_or_default().trim();
+                let message = element(error, "error-message").unwrap_or_default().trim();
+                return Err(NetconfClientError::Rpc(format!("{tag}: {message}")));
+            }
+
+            return Ok(RpcReply {
+                data: element(reply, "data").map(str::to_string),
+            });
+        }
+    }
+
+    fn send_message(&mut self, message: &str) -> Result<(), NetconfClientError> {
+        if self.chunked {
+            write!(self.transport, "\n#{}\n", message.len())?;
+            self.transport.write_all(message.as_bytes())?;
+            self.transport.write_all(END_OF_CHUNKS.as_bytes())?;
+        } else {
+            self.transport.write_all(message.as_bytes())?;
+            self.transport.write_all(DELIMITER.as_bytes())?;
+        }
+
+        self.transport.flush()?;
+        Ok(())
+    }
+
+    fn read_message(&mut self) -> Result<String, NetconfClientError> {
+        loop {
+            let message = if self.chunked {
+                self.decode_chunks()?
+            } else {
+                self.decode_delimited()
+            };
+            if let Some(message) = message {
+                return Ok(message);
+            }
+
+            if self.buffer.len() > MAX_MESSAGE_SIZE {
+                return Err(NetconfClientError::MalformedMessage(format!(
+                    "message exceeds {MAX_MESSAGE_SIZE} bytes"
+                )));
+            }
+
+            let mut chunk = [0u8; 4096];
+            let read = self.transport.read(&mut chunk)?;
+            if read == 0 {
+                return Err(NetconfClientError::Io(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "netconf-session closed",
+                )));
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+
+    /// takes the next message framed with the end-of-message delimiter from the buffer
+    fn decode_delimited(&mut self) -> Option<String> {
+        let position = self
+            .buffer
+            .windows(DELIMITER.len())
+            .position(|window| window == DELIMITER.as_bytes())?;
+        let message: Vec<u8> = self
+            .buffer
+            .drain(..position + DELIMITER.len())
+            .take(position)
+            .collect();
+
+        Some(String::from_utf8_lossy(&message).to_string())
+    }
+
+    /// takes the next chunked message from the buffer, if it's complete
+    fn decode_chunks(&mut self) -> Result<Option<String>, NetconfClientError> {
+        let mut message = Vec::new();
+        let mut position = 0;
+
+        loop {
+            let header_end = match self.buffer[position..]
+                .iter()
+                .skip(1)
+                .position(|b| *b == b'\n')
+            {
+                Some(end) => position + end + 1,
+                None => return Ok(None),
+            };
+            let header = String::from_utf8_lossy(&self.buffer[position..header_end]).to_string();
+
+            if header == "\n##" {
+                self.buffer.drain(..=header_end);
+                return Ok(Some(String::from_utf8_lossy(&message).to_string()));
+            }
+
+            let size: usize = header
+                .strip_prefix("\n#")
+                .and_then(|size| size.parse().ok())
+                .ok_or(NetconfClientError::MalformedMessage(format!(
+                    "invalid chunk header {header:?}"
+                )))?;
+
+            let chunk_start = header_end + 1;
+            if self.buffer.len() < chunk_start + size {
+                return Ok(None);
+            }
+            message.extend_from_slice(&self.buffer[chunk_start..chunk_start + size]);
+            position = chunk_start + size;
+        }
+    }
+}
+
+fn subtree_filter(filter: Option<&str>) -> String {
+    filter
+        .map(|filter| format!("<filter type=\"subtree\">{filter}</filter>"))
+        .unwrap_or_default()
+}
+
+/// the start-tag and content of the first element with the provided name (without prefix). The
+/// end-tag is searched from the back, so this only works if the element isn't followed by
+/// elements with the same name.
+fn element_with_start_tag<'a>(xml: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+    let mut offset = 0;
+
+    while let Some(found) = xml[offset..].find('<') {
+        let start = offset + found;
+        offset = start + 1;
+
+        let tag_end = start + xml[start..].find('>')?;
+        let start_tag = &xml[start..=tag_end];
+        let qualified_name_end =
+            match start_tag[1..].find(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+                Some(end) => 1 + end,
+                None => continue,
+            };
+        let qualified_name = &start_tag[1..qualified_name_end];
+        if qualified_name.rsplit(':').next() != Some(name) {
+            continue;
+        }
+
+        if start_tag.ends_with("/>") {
+            return Some((start_tag, ""));
+        }
+
+        let end_tag = format!("</{qualified_name}");
+        let end_tag_start = tag_end + 1 + xml[tag_end + 1..].rfind(&end_tag)?;
+        return Some((start_tag, &xml[tag_end + 1..end_tag_start]));
+    }
+
+    None
+}
+
+/// the content of the first element with the provided name
+fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    element_with_start_tag(xml, name).map(|(_, content)| content)
+}
+
+/// the contents of all sibling elements with the provided name, e.g. the <capability>s
+fn elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut contents = Vec::new();
+    let mut rest = xml;
+
+    while let Some((start_tag, _)) = element_with_start_tag(rest, name) {
+        let start = rest.find(start_tag).unwrap_or_default() + start_tag.len();
+        let qualified_name = start_tag[1..]
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        if start_tag.ends_with("/>") {
+            contents.push("");
+            rest = &rest[start..];
+            continue;
+        }
+
+        let end_tag = format!("</{qualified_name}>");
+        match rest[start..].find(&end_tag) {
+            Some(end) => {
+                contents.push(&rest[start..start + end]);
+                rest = &rest[start + end + end_tag.len()..];
+            }
+            None => break,
+        }
+    }
+
+    contents
+}
+
+/// value of an attribute in a start-tag
+fn attribute(start_tag: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let pattern = format!(" {name}={quote}");
+        if let Some(position) = start_tag.find(&pattern) {
+            let value_start = position + pattern.len();
+            let value_end = value_start + start_tag[value_start..].find(quote)?;
+            return Some(start_tag[value_start..value_end].to_string());
+        }
+    }
+
+    None
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
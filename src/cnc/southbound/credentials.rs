@@ -0,0 +1,271 @@
+use super::types::ConnectionError;
+use crate::cnc::types::topology::SSHConfigurationParams;
+use serde::Deserialize;
+use ssh2::{CheckResult, KnownHostFileKind, Session};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// credential file of the bridges. Can be changed with CNC_CREDENTIALS_FILE.
+pub const CREDENTIALS_FILE: &str = "./credentials.json";
+
+/// environment variables for the credentials of bridges that aren't in the credential file
+const ENV_CREDENTIALS_FILE: &str = "CNC_CREDENTIALS_FILE";
+const ENV_USERNAME: &str = "CNC_SSH_USERNAME";
+const ENV_PASSWORD: &str = "CNC_SSH_PASSWORD";
+const ENV_KEY_FILE: &str = "CNC_SSH_KEY_FILE";
+const ENV_PASSPHRASE: &str = "CNC_SSH_PASSPHRASE";
+const ENV_KNOWN_HOSTS: &str = "CNC_KNOWN_HOSTS";
+
+/// time a bridge has to accept the tcp-connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// time a bridge has to answer on the ssh-session, e.g. the handshake or an rpc. Sessions that wait
+/// for notifications disable it.
+const SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// how the CNC authenticates at a bridge
+#[derive(Debug, Clone, PartialEq)]
+pub enum SSHAuthentication {
+    Password(String),
+    PublicKey {
+        key_file: PathBuf,
+        passphrase: Option<String>,
+    },
+}
+
+/// The credentials of a bridge. If a key_file is given, public-key authentication is used,
+/// otherwise the password.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Credentials {
+    pub username: String,
+
+    #[serde(default)]
+    pub password: Option<String>,
+
+    /// private key in OpenSSH format
+    #[serde(default)]
+    pub key_file: Option<PathBuf>,
+
+    /// passphrase of the private key, if it's encrypted
+    #[serde(default)]
+    pub passphrase: Option<String>,
+}
+
+/// The credential file of the bridges. Credentials are kept here instead of the topology, so they
+/// aren't shared with the northbound.
+///
+/// # Example
+///
+/// ```json
+/// {
+///     "known_hosts": "./assets/known_hosts",
+///     "default": { "username": "admin", "password": "admin" },
+///     "nodes": {
+///         "10.2.0.2": { "username": "cnc", "key_file": "./assets/keys/cnc", "passphrase": "secret" }
+///     }
+/// }
+/// ```
+#[derive(Deserialize, Debug, Default)]
+pub struct CredentialStore {
+    /// OpenSSH known-hosts file to verify the host keys of the bridges. Defaults to
+    /// ~/.ssh/known_hosts.
+    #[serde(default)]
+    pub known_hosts: Option<PathBuf>,
+
+    /// credentials of bridges without an entry
+    #[serde(default)]
+    pub default: Option<Credentials>,
+
+    /// credentials by the name in the SSHConfigurationParams or the ip of the bridge
+    #[serde(default)]
+    pub nodes: HashMap<String, Credentials>,
+}
+
+impl Credentials {
+    /// the credentials from the environment, if CNC_SSH_USERNAME is set
+    pub fn from_env() -> Option<Self> {
+        let username = env::var(ENV_USERNAME).ok()?;
+
+        Some(Self {
+            username,
+            password: env::var(ENV_PASSWORD).ok(),
+            key_file: env::var(ENV_KEY_FILE).ok().map(PathBuf::from),
+            passphrase: env::var(ENV_PASSPHRASE).ok(),
+        })
+    }
+
+    pub fn authentication(&self) -> Result<SSHAuthentication, String> {
+        match (&self.key_file, &self.password) {
+            (Some(key_file), _) => Ok(SSHAuthentication::PublicKey {
+                key_file: key_file.clone(),
+                passphrase: self.passphrase.clone(),
+            }),
+            (None, Some(password)) => Ok(SSHAuthentication::Password(password.clone())),
+            (None, None) => Err(format!(
+                "credentials of {} have neither a password nor a key file",
+                self.username
+            )),
+        }
+    }
+}
+
+impl CredentialStore {
+    /// parses the credential file from json
+    pub fn from_json(content: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str::<Self>(content)
+    }
+
+    /// loads the credential file of CNC_CREDENTIALS_FILE or the CREDENTIALS_FILE. The store is
+    /// empty if there is no file.
+    pub fn load() -> Result<Self, String> {
+        let path = env::var(ENV_CREDENTIALS_FILE).unwrap_or(CREDENTIALS_FILE.to_string());
+
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::from_json(&content)
+                .map_err(|e| format!("invalid credential file {path}: {e}")),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// The credentials of the bridge. They are resolved in this order:
+    ///
+    /// 1. the entry with the name of the credentials in the SSHConfigurationParams
+    /// 2. the entry with the ip of the bridge
+    /// 3. the environment (CNC_SSH_USERNAME, CNC_SSH_PASSWORD, CNC_SSH_KEY_FILE, CNC_SSH_PASSPHRASE)
+    /// 4. the default entry
+    pub fn credentials(
+        &self,
+        config_params: &SSHConfigurationParams,
+    ) -> Result<Credentials, String> {
+        if let Some(name) = &config_params.credentials {
+            match self.nodes.get(name) {
+                Some(credentials) => return Ok(credentials.clone()),
+                None => {
+                    eprintln!("[Southbound] unknown credentials {name}, resolving them instead")
+                }
+            }
+        }
+
+        self.nodes
+            .get(&config_params.ip)
+            .cloned()
+            .or_else(Credentials::from_env)
+            .or_else(|| self.default.clone())
+            .ok_or(format!("no credentials for {}", config_params.ip))
+    }
+
+    /// the known-hosts file of CNC_KNOWN_HOSTS, the credential file or ~/.ssh/known_hosts
+    pub fn known_hosts_file(&self) -> Option<PathBuf> {
+        env::var(ENV_KNOWN_HOSTS)
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| self.known_hosts.clone())
+            .or_else(|| {
+                env::var("HOME")
+                    .ok()
+                    .map(|home| Path::new(&home).join(".ssh").join("known_hosts"))
+            })
+    }
+}
+
+/// Opens the ssh-session to the bridge that carries the netconf-session. The host key is checked
+/// against the known-hosts file before the credentials are sent, unknown and changed keys are
+/// rejected. Keys can be added with `ssh-keyscan -p <port> <ip> >> <known-hosts>`.
+///
+/// Bridges that don't accept the connection within CONNECT_TIMEOUT or don't answer within
+/// SESSION_TIMEOUT fail, so an unresponsive bridge doesn't block the CNC.
+///
+/// Returns the session and a clone of its tcp-connection, to shut it down from another thread.
+pub fn open_ssh_session(
+    ip: &str,
+    port: u16,
+    known_hosts_file: &Path,
+    credentials: &Credentials,
+) -> Result<(Session, TcpStream), ConnectionError> {
+    let address = (ip, port)
+        .to_socket_addrs()
+        .map_err(|e| ConnectionError::Transport(format!("couldn't resolve {ip}: {e}")))?
+        .next()
+        .ok_or_else(|| ConnectionError::Transport(format!("couldn't resolve {ip}")))?;
+    let tcp = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+        .map_err(|e| ConnectionError::Transport(format!("couldn't connect to {address}: {e}")))?;
+    let socket = tcp
+        .try_clone()
+        .map_err(|e| ConnectionError::Transport(format!("couldn't connect to {address}: {e}")))?;
+    let mut session = Session::new().map_err(|e| ConnectionError::Transport(format!("{e}")))?;
+    session.set_tcp_stream(tcp);
+    session.set_timeout(SESSION_TIMEOUT.as_millis() as u32);
+    session
+        .handshake()
+        .map_err(|e| ConnectionError::Transport(format!("ssh handshake failed: {e}")))?;
+
+    if let Err(e) = check_host_key(&session, ip, port, known_hosts_file) {
+        disconnect(&session, "unknown host key");
+        return Err(ConnectionError::HostKey(e));
+    }
+
+    let authenticated = match credentials
+        .authentication()
+        .map_err(ConnectionError::Credentials)?
+    {
+        SSHAuthentication::Password(password) => {
+            session.userauth_password(&credentials.username, &password)
+        }
+        SSHAuthentication::PublicKey {
+            key_file,
+            passphrase,
+        } => session.userauth_pubkey_file(
+            &credentials.username,
+            None,
+            &key_file,
+            passphrase.as_deref(),
+        ),
+    };
+
+    if let Err(e) = authenticated {
+        disconnect(&session, "authentication failed");
+        return Err(ConnectionError::Credentials(format!(
+            "authentication of {} at {ip}:{port} failed: {e}",
+            credentials.username
+        )));
+    }
+
+    Ok((session, socket))
+}
+
+/// checks the host key of the session against the known-hosts file
+fn check_host_key(
+    session: &Session,
+    ip: &str,
+    port: u16,
+    known_hosts_file: &Path,
+) -> Result<(), String> {
+    let (key, _) = session.host_key().ok_or("server sent no host key")?;
+    let mut known_hosts = session.known_hosts().map_err(|e| format!("{e}"))?;
+    known_hosts
+        .read_file(known_hosts_file, KnownHostFileKind::OpenSSH)
+        .map_err(|e| format!("couldn't read {:?}: {e}", known_hosts_file))?;
+
+    match known_hosts.check_port(ip, port, key) {
+        CheckResult::Match => Ok(()),
+        CheckResult::NotFound => Err(format!(
+            "host key of {ip}:{port} isn't in {:?}",
+            known_hosts_file
+        )),
+        CheckResult::Mismatch => Err(format!(
+            "host key of {ip}:{port} doesn't match the one in {:?}",
+            known_hosts_file
+        )),
+        CheckResult::Failure => Err(format!("couldn't check the host key of {ip}:{port}")),
+    }
+}
+
+fn disconnect(session: &Session, reason: &str) {
+    if let Err(e) = session.disconnect(None, reason, None) {
+        eprintln!("[Southbound] Error while closing connection... {:?}", e);
+    }
+}
@@ -0,0 +1,597 @@
+use super::client::NetconfClientError;
+use super::netconf::{
+    cancel_commit, commit_confirmed, confirm_commit, discard_changes, edit_config_in_candidate,
+    extract_oper_gate_parameters, get_config_interfaces, get_gate_parameters, lock_candidate,
+    missing_optional_modules, put_configurations_in_dtree, unlock_candidate, validate_candidate,
+};
+use super::sessions::{PooledSession, SessionPool};
+use super::types::NetconfConnection;
+use super::verification::{verify_gate_parameters, GateVerification};
+use crate::cnc::types::failure_codes::{
+    CONFIGURATION_FAILED, CONFIGURATION_NOT_IN_EFFECT, FUNCTION_NOT_SUPPORTED,
+};
+use crate::cnc::types::scheduling::{Config, PortConfiguration, Schedule};
+use crate::cnc::types::topology::{SSHConfigurationParams, Topology};
+use crate::cnc::types::tsn_types::GroupInterfaceId;
+use crate::cnc::types::{FailedInterface, FailedInterfaces};
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// seconds until a bridge rolls back a confirmed commit that isn't confirmed
+const CONFIRM_TIMEOUT: u32 = 120;
+
+/// maximum number of bridges that are configured at the same time
+const MAX_PARALLEL_NODES: usize = 8;
+
+/// time a bridge has for each step of the deployment. Bridges that take longer are handled as
+/// failed.
+const NODE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// the gate-parameters are read back until no configuration is pending anymore, at most
+/// VERIFY_ATTEMPTS times with VERIFY_INTERVAL in between
+const VERIFY_ATTEMPTS: u32 = 5;
+const VERIFY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// failure-code of the streams of interfaces whose configuration couldn't be deployed
+const DEPLOYMENT_FAILED: u32 = CONFIGURATION_FAILED;
+
+/// failure-code of the streams of interfaces whose configuration was deployed, but isn't in effect
+const VERIFICATION_FAILED: u32 = CONFIGURATION_NOT_IN_EFFECT;
+
+/// failure-code of the streams of interfaces whose configuration needs an optional yang-module,
+/// that the bridge doesn't provide
+const MODULE_MISSING: u32 = FUNCTION_NOT_SUPPORTED;
+
+/// the state of a bridge in the deployment of a schedule
+struct NodeTransaction {
+    node_id: u32,
+    connection: PooledSession,
+    configs: Vec<Config>,
+
+    /// ports that can't be configured, because the bridge lacks an optional yang-module they need
+    unsupported_ports: Vec<String>,
+
+    /// config-change-error counters of the ports before the deployment
+    config_change_errors: HashMap<String, u64>,
+
+    /// a step of the deployment failed on this bridge
+    failed: bool,
+
+    /// the candidate is committed
+    committed: bool,
+
+    /// the commit has to be confirmed or can be cancelled
+    confirmable: bool,
+}
+
+/// deploys the schedule as one transaction over all bridges:
+///
+/// 1. the candidate datastores of all bridges are locked, edited and validated
+/// 2. if that succeeded everywhere, the candidates are committed with <confirmed/>
+/// 3. if all commits succeeded, they are confirmed
+/// 4. the operational gate-parameters are read back and compared to the deployed ones
+///
+/// The bridges are configured in parallel, each step waits for all of them. If step 1 or 2 fails
+/// or times out on any bridge, the candidates are discarded and the confirmed commits are
+/// cancelled (or rolled back by the bridges after CONFIRM_TIMEOUT), so no bridge is configured.
+/// This includes bridges that lack an optional yang-module a port needs, these ports are reported
+/// with MODULE_MISSING.
+/// The confirmations in step 3 aren't atomic: a bridge whose confirmation fails rolls back after
+/// CONFIRM_TIMEOUT, while the others keep the new configuration. All interfaces whose
+/// configuration isn't in effect afterwards are returned.
+///
+/// The sessions are taken from the pool and returned to it afterwards.
+pub fn deploy_schedule(
+    sessions: &Arc<SessionPool>,
+    topology: &Topology,
+    schedule: &Schedule,
+) -> FailedInterfaces {
+    // group the configurations by node, keeping their order
+    let mut node_ids: Vec<u32> = Vec::new();
+    let mut node_configurations: HashMap<u32, Vec<Config>> = HashMap::new();
+    for configuration in schedule.configs.iter() {
+        if !node_configurations.contains_key(&configuration.node_id) {
+            node_ids.push(configuration.node_id);
+        }
+        node_configurations
+            .entry(configuration.node_id)
+            .or_insert_with(Vec::new)
+            .push(configuration.clone());
+    }
+
+    let mut aborted = false;
+    let mut nodes: Vec<(u32, NodeDeployment)> = Vec::new();
+    for node_id in node_ids {
+        let config_params = topology
+            .get_node_from_id(node_id)
+            .and_then(|node| node.configuration_params);
+
+        match config_params {
+            Some(config_params) => {
+                let configs = node_configurations.remove(&node_id).unwrap();
+                nodes.push((node_id, (sessions.clone(), config_params, configs)));
+            }
+            None => {
+                eprintln!("[Southbound] node {} has no configuration params", node_id);
+                aborted = true;
+            }
+        }
+    }
+
+    if aborted {
+        eprintln!("[Southbound] not comitting since there where configuration failures...");
+        return failed_interfaces(schedule.configs.iter());
+    }
+
+    // phase 1: lock, edit and validate the candidates
+    let prepared = run_on_nodes(nodes, prepare_node, |_, transaction| {
+        if let Some(transaction) = transaction {
+            roll_back_node(transaction.node_id, transaction);
+        }
+    });
+    let mut transactions: Vec<NodeTransaction> = Vec::new();
+    for (_, transaction) in prepared {
+        match transaction {
+            Some(Some(transaction)) => {
+                aborted |= transaction.failed;
+                transactions.push(transaction);
+            }
+            _ => aborted = true,
+        }
+    }
+
+    if aborted {
+        eprintln!("[Southbound] not comitting since there where configuration failures...");
+        let unsupported_ports: Vec<(u32, String)> = transactions
+            .iter()
+            .flat_map(|t| {
+                t.unsupported_ports
+                    .iter()
+                    .map(move |name| (t.node_id, name.clone()))
+            })
+            .collect();
+        roll_back(transactions);
+
+        let mut failed = failed_interfaces(schedule.configs.iter());
+        for interface in failed.interfaces.iter_mut() {
+            let port = (
+                interface.node_id,
+                interface.interface.interface_name.clone(),
+            );
+            if unsupported_ports.contains(&port) {
+                interface.failure_code = MODULE_MISSING;
+            }
+        }
+        return failed;
+    }
+
+    // phase 2: commit all candidates with <confirmed/>
+    let committed = run_on_nodes(
+        transactions.into_iter().map(|t| (t.node_id, t)).collect(),
+        commit_node,
+        roll_back_node,
+    );
+    let mut transactions: Vec<NodeTransaction> = Vec::new();
+    for (_, transaction) in committed {
+        match transaction {
+            Some(transaction) => {
+                aborted |= transaction.failed;
+                transactions.push(transaction);
+            }
+            None => aborted = true,
+        }
+    }
+
+    if aborted {
+        roll_back(transactions);
+        return failed_interfaces(schedule.configs.iter());
+    }
+
+    // phase 3 and 4: confirm the commits and verify them. A bridge where the confirmation fails
+    // rolls back after the timeout.
+    let confirmed = run_on_nodes(
+        transactions.into_iter().map(|t| (t.node_id, t)).collect(),
+        confirm_node,
+        |_, _| (),
+    );
+
+    let mut failed = FailedInterfaces {
+        interfaces: Vec::new(),
+    };
+    for (node_id, failed_ports) in confirmed {
+        let configs = schedule.configs.iter().filter(|c| c.node_id == node_id);
+        match failed_ports {
+            Some(failed_ports) => failed.interfaces.extend(configs.filter_map(|c| {
+                failed_ports
+                    .get(&c.port.name)
+                    .map(|failure_code| failed_interface(c, *failure_code))
+            })),
+            None => failed
+                .interfaces
+                .extend(configs.map(|c| failed_interface(c, DEPLOYMENT_FAILED))),
+        }
+    }
+
+    failed
+}
+
+/// the session pool, configuration params and configurations of a node
+type NodeDeployment = (Arc<SessionPool>, SSHConfigurationParams, Vec<Config>);
+
+/// runs the task for all nodes in parallel, but at most MAX_PARALLEL_NODES at the same time.
+///
+/// Returns the output of the task for every node, None if the task didn't finish within
+/// NODE_TIMEOUT. The output of these tasks is passed to abandon(...) when they finish, to clean up
+/// the connections.
+pub(super) fn run_on_nodes<I, O>(
+    inputs: Vec<(u32, I)>,
+    task: fn(u32, I) -> O,
+    abandon: fn(u32, O),
+) -> Vec<(u32, Option<O>)>
+where
+    I: Send + 'static,
+    O: Send + 'static,
+{
+    run_on_nodes_with_timeout(inputs, NODE_TIMEOUT, task, abandon)
+}
+
+/// same as run_on_nodes(...), but each task has to finish within the given timeout
+pub(crate) fn run_on_nodes_with_timeout<I, O>(
+    inputs: Vec<(u32, I)>,
+    timeout: Duration,
+    task: fn(u32, I) -> O,
+    abandon: fn(u32, O),
+) -> Vec<(u32, Option<O>)>
+where
+    I: Send + 'static,
+    O: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel::<(u32, O)>();
+    let node_ids: Vec<u32> = inputs.iter().map(|(node_id, _)| *node_id).collect();
+    let mut pending = inputs.into_iter();
+    let mut running: HashMap<u32, Instant> = HashMap::new();
+    let mut outputs: HashMap<u32, O> = HashMap::new();
+
+    loop {
+        while running.len() < MAX_PARALLEL_NODES {
+            let (node_id, input) = match pending.next() {
+                Some(next) => next,
+                None => break,
+            };
+
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let output = task(node_id, input);
+
+                // the receiver is gone if the deployment finished without this node
+                if let Err(mpsc::SendError((node_id, output))) = sender.send((node_id, output)) {
+                    abandon(node_id, output);
+                }
+            });
+            running.insert(node_id, Instant::now());
+        }
+
+        let deadline = match running.values().min() {
+            Some(started) => *started + timeout,
+            None => break,
+        };
+
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok((node_id, output)) => match running.remove(&node_id) {
+                Some(_) => {
+                    outputs.insert(node_id, output);
+                }
+                None => abandon(node_id, output), // already timed out
+            },
+            Err(_) => {
+                let now = Instant::now();
+                running.retain(|node_id, started| {
+                    let timed_out = now.duration_since(*started) >= timeout;
+                    if timed_out {
+                        eprintln!("[Southbound] node {} timed out", node_id);
+                    }
+                    !timed_out
+                });
+            }
+        }
+    }
+
+    node_ids
+        .into_iter()
+        .map(|node_id| {
+            let output = outputs.remove(&node_id);
+            (node_id, output)
+        })
+        .collect()
+}
+
+/// this configures a node-port on the given client
+pub fn configure_port(
+    netconf_connection: &mut NetconfConnection,
+    port_configuration: &PortConfiguration,
+) -> Result<(), NetconfClientError> {
+    match get_config_interfaces(netconf_connection) {
+        Ok(mut netconf_configuration) => {
+            put_configurations_in_dtree(
+                &mut netconf_configuration,
+                &netconf_connection.yang_paths,
+                port_configuration,
+            );
+            edit_config_in_candidate(netconf_connection, &netconf_configuration)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// takes the session of the node from the pool, locks its candidate datastore, edits and validates
+/// it. None if the node couldn't be connected or locked.
+fn prepare_node(
+    node_id: u32,
+    (sessions, config_params, configs): NodeDeployment,
+) -> Option<NodeTransaction> {
+    let mut connection = match sessions.session(&config_params) {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!("[Southbound] error while connecting via netconf {e:?}");
+            return None;
+        }
+    };
+
+    if let Err(e) = lock_candidate(&mut connection) {
+        eprintln!(
+            "[Southbound] couldn't lock the candidate of node {}: {e:?}",
+            node_id
+        );
+        connection.discard();
+        return None;
+    }
+
+    // the streams of ports that need an optional yang-module the bridge doesn't provide can't be
+    // configured completely, so the whole deployment is aborted
+    let mut unsupported_ports: Vec<String> = Vec::new();
+    for config in configs.iter() {
+        let missing = missing_optional_modules(&connection.yang_ctx, &config.port);
+        if !missing.is_empty() {
+            eprintln!(
+                "[Southbound] node {} doesn't provide {}, can't configure {}",
+                node_id,
+                missing.join(", "),
+                config.port.name
+            );
+            unsupported_ports.push(config.port.name.clone());
+        }
+    }
+
+    let mut failed = !unsupported_ports.is_empty();
+    if !failed {
+        for config in configs.iter() {
+            if let Err(e) = configure_port(&mut connection, &config.port) {
+                eprintln!(
+                    "[Southbound] configuration of {} on node {} failed: {e:?}",
+                    config.port.name, node_id
+                );
+                failed = true;
+            }
+        }
+    }
+
+    if !failed {
+        if let Err(e) = validate_candidate(&mut connection) {
+            eprintln!("[Southbound] validation on node {} failed: {e:?}", node_id);
+            failed = true;
+        }
+    }
+
+    let config_change_errors = match get_gate_parameters(&mut connection) {
+        Ok(dtree) => extract_oper_gate_parameters(&dtree, &connection.yang_paths)
+            .into_iter()
+            .map(|(name, gate_parameters)| (name, gate_parameters.config_change_error))
+            .collect(),
+        Err(e) => {
+            eprintln!(
+                "[Southbound] couldn't read the gate-parameters of node {}: {e:?}",
+                node_id
+            );
+            HashMap::new()
+        }
+    };
+
+    Some(NodeTransaction {
+        node_id,
+        connection,
+        configs,
+        unsupported_ports,
+        config_change_errors,
+        failed,
+        committed: false,
+        confirmable: false,
+    })
+}
+
+/// commits the candidate of the node with <confirmed/>
+fn commit_node(node_id: u32, mut transaction: NodeTransaction) -> NodeTransaction {
+    match commit_confirmed(&mut transaction.connection, CONFIRM_TIMEOUT) {
+        Ok(confirmable) => {
+            transaction.committed = true;
+            transaction.confirmable = confirmable;
+        }
+        Err(e) => {
+            eprintln!(
+                "[Southbound] error while committing on node {}: {e:?}",
+                node_id
+            );
+            transaction.failed = true;
+        }
+    }
+
+    transaction
+}
+
+/// confirms the commit of the node, verifies it and returns the session to the pool. Returns the
+/// failure-codes of the ports whose configuration isn't in effect.
+fn confirm_node(node_id: u32, mut transaction: NodeTransaction) -> HashMap<String, u32> {
+    if transaction.confirmable {
+        if let Err(e) = confirm_commit(&mut transaction.connection) {
+            eprintln!(
+                "[Southbound] error while confirming the commit on node {}: {e:?}",
+                node_id
+            );
+            let failed_ports = transaction
+                .configs
+                .iter()
+                .map(|c| (c.port.name.clone(), DEPLOYMENT_FAILED))
+                .collect();
+            close_node(transaction);
+            return failed_ports;
+        }
+    }
+
+    let failed_ports = verify_node(node_id, &mut transaction)
+        .into_keys()
+        .map(|name| (name, VERIFICATION_FAILED))
+        .collect();
+
+    close_node(transaction);
+    failed_ports
+}
+
+/// Reads the operational gate-parameters of the node back and compares them to the deployed ones.
+/// Returns the ports whose configuration isn't in effect, with the reason.
+///
+/// Bridges apply a schedule at its config-change-time, so the values are read again while a
+/// configuration is pending. A base-time in the past increases the config-change-error, but the
+/// bridge still applies the schedule at the start of the next cycle, so this is only reported.
+fn verify_node(node_id: u32, transaction: &mut NodeTransaction) -> HashMap<String, String> {
+    let mut failed_ports: HashMap<String, String> = HashMap::new();
+
+    for attempt in 1..=VERIFY_ATTEMPTS {
+        failed_ports.clear();
+        if attempt > 1 {
+            thread::sleep(VERIFY_INTERVAL);
+        }
+
+        let oper_gate_parameters = match get_gate_parameters(&mut transaction.connection) {
+            Ok(dtree) => extract_oper_gate_parameters(&dtree, &transaction.connection.yang_paths),
+            Err(e) => {
+                for config in transaction.configs.iter() {
+                    failed_ports.insert(
+                        config.port.name.clone(),
+                        format!("couldn't read the gate-parameters: {e:?}"),
+                    );
+                }
+                continue;
+            }
+        };
+
+        let mut pending = false;
+        for config in transaction.configs.iter() {
+            let name = &config.port.name;
+            let oper = match oper_gate_parameters.get(name) {
+                Some(oper) => oper,
+                None => {
+                    failed_ports.insert(name.clone(), String::from("no gate-parameters"));
+                    continue;
+                }
+            };
+
+            match verify_gate_parameters(&config.port.config, oper) {
+                GateVerification::Applied => (),
+                GateVerification::Pending => {
+                    pending = true;
+                    failed_ports.insert(name.clone(), String::from("config-pending"));
+                }
+                GateVerification::Mismatch(reason) => {
+                    failed_ports.insert(name.clone(), reason);
+                }
+            }
+
+            let before = transaction.config_change_errors.get(name);
+            if attempt == 1 && before.map_or(false, |before| oper.config_change_error > *before) {
+                println!(
+                    "[Southbound] base-time of {} on node {} was in the past, the schedule starts with the next cycle",
+                    name, node_id
+                );
+            }
+        }
+
+        if !pending {
+            break;
+        }
+    }
+
+    for (name, reason) in failed_ports.iter() {
+        eprintln!(
+            "[Southbound] configuration of {} on node {} isn't in effect: {}",
+            name, node_id, reason
+        );
+    }
+
+    failed_ports
+}
+
+/// cancels the confirmed commits and discards the candidates of all nodes in parallel
+fn roll_back(transactions: Vec<NodeTransaction>) {
+    run_on_nodes(
+        transactions.into_iter().map(|t| (t.node_id, t)).collect(),
+        roll_back_node,
+        |_, _| (),
+    );
+}
+
+/// cancels the confirmed commit or discards the candidate of the node and returns the session to
+/// the pool.
+/// Bridges that can't cancel their commit roll it back after the timeout.
+fn roll_back_node(node_id: u32, mut transaction: NodeTransaction) {
+    let result = match (transaction.committed, transaction.confirmable) {
+        (true, true) => cancel_commit(&mut transaction.connection),
+        (true, false) => {
+            eprintln!(
+                "[Southbound] node {} was committed without confirmation and can't be rolled back",
+                node_id
+            );
+            Ok(())
+        }
+        (false, _) => discard_changes(&mut transaction.connection),
+    };
+
+    if let Err(e) = result {
+        eprintln!("[Southbound] couldn't roll back node {}: {e:?}", node_id);
+    }
+
+    close_node(transaction);
+}
+
+/// unlocks the candidate and returns the session of the node to the pool. The session is closed
+/// if the candidate stays locked.
+fn close_node(mut transaction: NodeTransaction) {
+    if let Err(e) = unlock_candidate(&mut transaction.connection) {
+        eprintln!(
+            "[Southbound] couldn't unlock the candidate of node {}: {e:?}",
+            transaction.node_id
+        );
+        transaction.connection.discard();
+    }
+}
+
+/// the failed interfaces of the configurations that couldn't be deployed
+fn failed_interfaces<'a>(configs: impl Iterator<Item = &'a Config>) -> FailedInterfaces {
+    FailedInterfaces {
+        interfaces: configs
+            .map(|config| failed_interface(config, DEPLOYMENT_FAILED))
+            .collect(),
+    }
+}
+
+pub(super) fn failed_interface(config: &Config, failure_code: u32) -> FailedInterface {
+    FailedInterface {
+        node_id: config.node_id,
+        interface: GroupInterfaceId {
+            interface_name: config.port.name.clone(),
+            mac_address: config.port.mac_address.clone(),
+        },
+        affected_streams: config.affected_streams.iter().map(|x| x.clone()).collect(),
+        failure_code,
+    }
+}
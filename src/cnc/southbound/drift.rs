@@ -0,0 +1,112 @@
+use super::deployment::{failed_interface, run_on_nodes};
+use super::netconf::{extract_admin_gate_parameters, get_gate_parameters};
+use super::sessions::SessionPool;
+use super::verification::diff_gate_parameters;
+use crate::cnc::types::failure_codes::CONFIGURATION_DRIFTED;
+use crate::cnc::types::scheduling::Config;
+use crate::cnc::types::topology::{SSHConfigurationParams, Topology};
+use crate::cnc::types::FailedInterfaces;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// failure-code of the streams of interfaces whose configuration was changed on the bridge
+const DRIFT_DETECTED: u32 = CONFIGURATION_DRIFTED;
+
+/// the session pool, configuration params and stored configurations of a node
+type NodeCheck = (Arc<SessionPool>, SSHConfigurationParams, Vec<Config>);
+
+/// Reads the gate-parameters of all configured ports and compares them with the stored
+/// configurations. The bridges are checked in parallel. All interfaces whose gate-parameters
+/// differ are returned, the differences are logged.
+///
+/// Bridges that can't be reached are skipped, their configuration is unknown.
+pub fn detect_drift(
+    sessions: &Arc<SessionPool>,
+    topology: &Topology,
+    configs: &Vec<Config>,
+) -> FailedInterfaces {
+    let mut node_configurations: HashMap<u32, Vec<Config>> = HashMap::new();
+    for config in configs.iter() {
+        node_configurations
+            .entry(config.node_id)
+            .or_insert_with(Vec::new)
+            .push(config.clone());
+    }
+
+    let mut nodes: Vec<(u32, NodeCheck)> = Vec::new();
+    for (node_id, configs) in node_configurations {
+        match topology
+            .get_node_from_id(node_id)
+            .and_then(|node| node.configuration_params)
+        {
+            Some(config_params) => {
+                nodes.push((node_id, (sessions.clone(), config_params, configs)))
+            }
+            None => eprintln!("[Southbound] node {} has no configuration params", node_id),
+        }
+    }
+
+    let mut drifted = FailedInterfaces {
+        interfaces: Vec::new(),
+    };
+    for (node_id, drifted_configs) in run_on_nodes(nodes, check_node, |_, _| ()) {
+        match drifted_configs {
+            Some(Some(drifted_configs)) => drifted.interfaces.extend(
+                drifted_configs
+                    .iter()
+                    .map(|config| failed_interface(config, DRIFT_DETECTED)),
+            ),
+            Some(None) => (),
+            None => eprintln!(
+                "[Southbound] couldn't check the configuration of node {} in time",
+                node_id
+            ),
+        }
+    }
+
+    drifted
+}
+
+/// returns the configurations of the node that differ from the gate-parameters on the bridge, None
+/// if they couldn't be read
+fn check_node(node_id: u32, (sessions, config_params, configs): NodeCheck) -> Option<Vec<Config>> {
+    let mut connection = match sessions.session(&config_params) {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!("[Southbound] couldn't connect to node {}: {e:?}", node_id);
+            return None;
+        }
+    };
+
+    let admin_gate_parameters = match get_gate_parameters(&mut connection) {
+        Ok(dtree) => extract_admin_gate_parameters(&dtree, &connection.yang_paths),
+        Err(e) => {
+            eprintln!(
+                "[Southbound] couldn't read the gate-parameters of node {}: {e:?}",
+                node_id
+            );
+            connection.discard();
+            return None;
+        }
+    };
+
+    let mut drifted: Vec<Config> = Vec::new();
+    for config in configs {
+        let differences = match admin_gate_parameters.get(&config.port.name) {
+            Some(actual) => diff_gate_parameters(&config.port.config, actual),
+            None => vec![String::from("no gate-parameters")],
+        };
+
+        if !differences.is_empty() {
+            println!(
+                "[Southbound] configuration of {} on node {} drifted: {}",
+                config.port.name,
+                node_id,
+                differences.join(", ")
+            );
+            drifted.push(config);
+        }
+    }
+
+    Some(drifted)
+}
@@ -1,29 +1,76 @@
-use super::types::{NetconfConnection, YangModule, YangPaths, YANG_MODULES};
+use super::client::{DatastoreType, HelloServer, NetconfClient, NetconfClientError};
+use super::credentials::{open_ssh_session, CredentialStore};
+use super::profiles::DeviceProfile;
+use super::types::{
+    ConnectionError, ModuleCapability, NetconfConnection, SwitchParameters, YangContexts,
+    YangModule, YangPaths, OPTIONAL_YANG_MODULES,
+};
+use crate::cnc::types::bridge_types::{StaticFilteringControl, VlanTransmitted};
+use crate::cnc::types::frer_types::{
+    SequenceRecoveryAlgorithm, StreamIdentificationType, VlanTagIdentification,
+};
 use crate::cnc::types::lldp_types::{ManagementAddress, RemoteSystemsData};
+use crate::cnc::types::sched_types::{
+    ConfigurableGateParameterTableEntry, FramePreemptionStatus, GateControlEntry,
+    GateControlOperation, GateParameterTableEntry, StreamGateState, TransmissionSelectionAlgorithm,
+};
 use crate::cnc::types::scheduling::PortConfiguration;
 use crate::cnc::types::topology::{Port, SSHConfigurationParams};
 use crate::cnc::types::tsn_types::BridgePortDelays;
-use netconf_client::errors::NetconfClientError;
-use netconf_client::models::replies::HelloServer;
-use netconf_client::models::requests::{Filter, FilterType};
-use netconf_client::netconf_client::NetconfClient;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 use yang2::context::{Context, ContextFlags};
 use yang2::data::{
-    Data, DataFormat, DataParserFlags, DataPrinterFlags, DataTree, DataValidationFlags,
+    Data, DataFormat, DataNodeRef, DataOperation, DataParserFlags, DataPrinterFlags, DataTree,
+    DataValidationFlags,
 };
 use yang2::schema::DataValue;
 
 /// folder for all needed yang-models
 const SEARCH_DIR: &str = "./assets/yang/";
 
-/// Initialize context for working with the correct yang models. This is unique for each Switch
-/// since the Modules  might differ.
-pub fn init_yang_ctx(yang_modules: &Vec<YangModule>) -> Arc<Context> {
+/// folder for the yang-models that were downloaded from the netconf-servers with <get-schema>
+const CACHE_DIR: &str = "./assets/yang/cache/";
+
+/// capability of netconf-servers that support <get-schema>
+const NETCONF_MONITORING: &str = "urn:ietf:params:xml:ns:yang:ietf-netconf-monitoring";
+
+/// capability of netconf-servers that support <commit> with <confirmed/> and <cancel-commit>
+const CONFIRMED_COMMIT: &str = "urn:ietf:params:netconf:capability:confirmed-commit:1.1";
+
+/// capability of netconf-servers that support <validate>
+const VALIDATE: &str = "urn:ietf:params:netconf:capability:validate:";
+
+/// creates a context that finds the yang-models in the search and cache directory
+fn new_yang_ctx() -> Context {
     let mut ctx =
         Context::new(ContextFlags::NO_YANGLIBRARY).expect("Failed to create yang-context");
     ctx.set_searchdir(SEARCH_DIR)
         .expect("failed to set search directory to find yang-models");
+    if Path::new(CACHE_DIR).is_dir() {
+        ctx.set_searchdir(CACHE_DIR)
+            .expect("failed to set cache directory to find yang-models");
+    }
+
+    ctx
+}
+
+fn load_optional_yang_modules(ctx: &mut Context) {
+    for module in OPTIONAL_YANG_MODULES {
+        if let Err(e) = ctx.load_module(module.name, module.revision, module.features) {
+            eprintln!(
+                "[Southbound] optional yang-module {} couldn't be loaded: {:?}",
+                module.name, e
+            );
+        }
+    }
+}
+
+/// Initialize context for working with the correct yang models.
+pub fn init_yang_ctx(yang_modules: &Vec<YangModule>) -> Arc<Context> {
+    let mut ctx = new_yang_ctx();
 
     // Load YANG modules.
     for module in yang_modules {
@@ -31,31 +78,211 @@ pub fn init_yang_ctx(yang_modules: &Vec<YangModule>) -> Arc<Context> {
             .expect("failed to load yang-module");
     }
 
+    load_optional_yang_modules(&mut ctx);
     Arc::new(ctx)
 }
 
-/// this extracts the yang_modules form the <hello>-Message
-/// TODO: this is not implemented yet. The used B&R switch doesn't provide all needed models.
-pub fn extract_used_yang_modules(hello_server: &HelloServer) -> Vec<YangModule> {
-    // TODO: load yangmodules based on the provided capabilities.
-    // Since the used B&R switch doesnt provide all needed Models, these are hardcoded here.
-    let _capabilities = &hello_server.capabilities;
-    let yang_modules: Vec<YangModule> = YANG_MODULES.to_vec();
-    // ----------
+/// Initialize context for the yang models of a netconf-server. This is unique for each Switch
+/// since the Modules might differ.
+///
+/// The models of the device profile are needed for its paths and have to be loaded, otherwise the
+/// server can't be used. Other advertised models are skipped if they can't be loaded.
+fn init_server_yang_ctx(
+    yang_modules: &Vec<ModuleCapability>,
+    profile: &DeviceProfile,
+) -> Result<Arc<Context>, ConnectionError> {
+    let mut ctx = new_yang_ctx();
+
+    for module in yang_modules {
+        let features: Vec<&str> = module.features.iter().map(|f| f.as_str()).collect();
+        let result = ctx.load_module(&module.name, module.revision.as_deref(), &features);
+
+        if let Err(e) = result {
+            if profile.needs_module(&module.name) {
+                return Err(ConnectionError::YangModule(format!(
+                    "failed to load yang-module {} of profile {}: {:?}",
+                    module.name, profile.name, e
+                )));
+            }
+            eprintln!(
+                "[Southbound] advertised yang-module {} couldn't be loaded: {:?}",
+                module.name, e
+            );
+        }
+    }
+
+    load_optional_yang_modules(&mut ctx);
+    Ok(Arc::new(ctx))
+}
+
+/// the context for the yang-models of a netconf-server. It's shared with all servers that use the
+/// same models and device profile, and only built for the first of them.
+///
+/// The context is built without holding the lock, so connections to other servers aren't blocked
+/// meanwhile. If two connections build the same context at once, the first one is kept. Contexts
+/// that can't be built aren't kept, so they are tried again with the next connection.
+pub fn shared_server_yang_ctx(
+    yang_contexts: &YangContexts,
+    yang_modules: &Vec<ModuleCapability>,
+    profile: &DeviceProfile,
+) -> Result<Arc<Context>, ConnectionError> {
+    let mut modules: Vec<String> = yang_modules
+        .iter()
+        .map(|m| {
+            format!(
+                "{}@{}+{}",
+                m.name,
+                m.revision.as_deref().unwrap_or_default(),
+                m.features.join(",")
+            )
+        })
+        .collect();
+    modules.sort();
+    let key = format!("{}:{}", profile.name, modules.join(";"));
+
+    if let Some(ctx) = yang_contexts.lock().unwrap().get(&key) {
+        return Ok(ctx.clone());
+    }
+
+    let ctx = init_server_yang_ctx(yang_modules, profile)?;
+    Ok(yang_contexts
+        .lock()
+        .unwrap()
+        .entry(key)
+        .or_insert(ctx)
+        .clone())
+}
+
+/// this extracts the yang_modules form the capabilities of the <hello>-Message, merged with the
+/// models of the device profile.
+pub fn extract_used_yang_modules(
+    hello_server: &HelloServer,
+    profile: &DeviceProfile,
+) -> Vec<ModuleCapability> {
+    profile.yang_modules(&hello_server.capabilities)
+}
+
+/// the file name of the yang-model, e.g. ietf-interfaces@2018-02-20.yang
+fn yang_module_file(module: &ModuleCapability) -> String {
+    match &module.revision {
+        Some(revision) => format!("{}@{}.yang", module.name, revision),
+        None => format!("{}.yang", module.name),
+    }
+}
+
+/// checks if the yang-model is in the search or cache directory. Without a revision, any revision
+/// of the model is sufficient.
+fn is_yang_module_available(module: &ModuleCapability) -> bool {
+    [SEARCH_DIR, CACHE_DIR]
+        .iter()
+        .any(|dir| match &module.revision {
+            Some(_) => Path::new(dir).join(yang_module_file(module)).is_file(),
+            None => match fs::read_dir(dir) {
+                Ok(entries) => entries.flatten().any(|entry| {
+                    let file_name = entry.file_name().to_string_lossy().to_string();
+                    file_name == yang_module_file(module)
+                        || file_name.starts_with(&format!("{}@", module.name))
+                }),
+                Err(_) => false,
+            },
+        })
+}
+
+/// downloads the advertised yang-models, that aren't in the search or cache directory, with
+/// <get-schema> of ietf-netconf-monitoring into the cache directory.
+fn download_missing_yang_modules(
+    netconf_client: &mut NetconfClient,
+    capabilities: &Vec<String>,
+    yang_modules: &Vec<ModuleCapability>,
+) {
+    let missing: Vec<&ModuleCapability> = yang_modules
+        .iter()
+        .filter(|m| !is_yang_module_available(m))
+        .collect();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    if !capabilities
+        .iter()
+        .any(|c| c.starts_with(NETCONF_MONITORING))
+    {
+        eprintln!("[Southbound] netconf-server doesn't support <get-schema>, can't download missing yang-modules");
+        return;
+    }
+
+    if let Err(e) = fs::create_dir_all(CACHE_DIR) {
+        eprintln!("[Southbound] couldn't create the yang-module cache: {e:?}");
+        return;
+    }
+
+    for module in missing {
+        let response =
+            netconf_client.get_schema(&module.name, module.revision.as_deref(), Some("yang"));
+
+        let schema = match response {
+            Ok(response) => match response.data {
+                Some(schema) => unescape_xml(&schema),
+                None => {
+                    eprintln!(
+                        "[Southbound] <get-schema> of {} returned no data",
+                        module.name
+                    );
+                    continue;
+                }
+            },
+            Err(e) => {
+                eprintln!(
+                    "[Southbound] couldn't download yang-module {}: {e:?}",
+                    module.name
+                );
+                continue;
+            }
+        };
+
+        let file_path = Path::new(CACHE_DIR).join(yang_module_file(module));
+        match fs::write(&file_path, schema) {
+            Ok(_) => println!("[Southbound] downloaded yang-module {}", module.name),
+            Err(e) => eprintln!("[Southbound] couldn't write {:?}: {e:?}", file_path),
+        }
+    }
+}
 
-    yang_modules
+/// the text of the schema is escaped in the <data> of the <get-schema> reply
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
 }
 
 /// this function establishes a connection to the netconf-server. It will load all needed yang-models
+/// and the paths of the device profile
 pub fn establish_netconf_connection(
     config_params: &SSHConfigurationParams,
-) -> Result<NetconfConnection, NetconfClientError> {
-    let mut netconf_client = NetconfClient::new(
-        config_params.ip.as_str(),
+    yang_contexts: &YangContexts,
+) -> Result<NetconfConnection, ConnectionError> {
+    let credential_store = CredentialStore::load().map_err(ConnectionError::Credentials)?;
+    let credentials = credential_store
+        .credentials(config_params)
+        .map_err(ConnectionError::Credentials)?;
+
+    let known_hosts_file = credential_store
+        .known_hosts_file()
+        .ok_or(ConnectionError::HostKey(String::from(
+            "no known-hosts file",
+        )))?;
+
+    // the netconf-session runs over the ssh-session whose host key was verified
+    let (session, socket) = open_ssh_session(
+        &config_params.ip,
         config_params.port,
-        config_params.username.as_str(),
-        config_params.password.as_str(),
-    );
+        &known_hosts_file,
+        &credentials,
+    )?;
+    let mut netconf_client = NetconfClient::from_session(session)?;
 
     println!(
         "[Southbound] trying to establish netconf-connection to {}",
@@ -63,36 +290,59 @@ pub fn establish_netconf_connection(
     );
 
     let hello_server = netconf_client.connect()?;
-    let yang_modules: Vec<YangModule> = extract_used_yang_modules(&hello_server);
+    let profile =
+        DeviceProfile::for_device(config_params.profile.as_ref(), &hello_server.capabilities);
+    let yang_modules: Vec<ModuleCapability> = extract_used_yang_modules(&hello_server, &profile);
 
     netconf_client.send_hello()?;
 
+    download_missing_yang_modules(
+        &mut netconf_client,
+        &hello_server.capabilities,
+        &yang_modules,
+    );
+
     let netconf_connection = NetconfConnection {
         netconf_client,
-        yang_ctx: init_yang_ctx(&yang_modules),
-        yang_paths: YangPaths::load_paths(&yang_modules),
+        yang_ctx: shared_server_yang_ctx(yang_contexts, &yang_modules, &profile)?,
+        yang_paths: YangPaths::load_paths(&profile),
+        capabilities: hello_server.capabilities.clone(),
+        socket,
     };
 
     Ok(netconf_connection)
 }
 
+/// this runs a <get> rpc that selects no data, to check if the session is still alive and keep it
+/// open. The ietf-netconf-monitoring filter matches no session, servers without the model reply
+/// with empty data as well.
+pub fn keep_alive(netconf_connection: &mut NetconfConnection) -> Result<(), NetconfClientError> {
+    let keep_alive_filter = format!(
+        "<netconf-state xmlns=\"{}\"><sessions><session><session-id>0</session-id></session></sessions></netconf-state>",
+        NETCONF_MONITORING
+    );
+
+    netconf_connection
+        .netconf_client
+        .get(Some(&keep_alive_filter))?;
+    Ok(())
+}
+
 /// this runs a <get-config> rpc on the netconf-client. This will provied all configurable
-/// fields to edit and commit in the end.
+/// fields to edit and commit in the end. Besides the gate-parameters of the interfaces, this
+/// includes the filtering database to update the static entries of the streams.
 pub fn get_config_interfaces(
     netconf_connection: &mut NetconfConnection,
 ) -> Result<DataTree, NetconfClientError> {
-    let get_config_interfaces_filter = Filter {
-        filter_type: FilterType::Subtree,
-        data: netconf_connection
-            .yang_paths
-            .filters
-            .gate_parameters
-            .clone(),
-    };
+    let get_config_interfaces_filter = netconf_connection
+        .yang_paths
+        .filters
+        .gate_parameters_and_filtering_database
+        .clone();
 
     let get_config_response = netconf_connection.netconf_client.get_config(
-        netconf_client::models::requests::DatastoreType::Candidate,
-        Some(get_config_interfaces_filter),
+        DatastoreType::Candidate,
+        Some(&get_config_interfaces_filter),
     )?;
 
     let response_data = get_config_response.data.expect(
@@ -118,12 +368,13 @@ pub fn put_configurations_in_dtree(
     yang_paths: &YangPaths,
     port_configuration: &PortConfiguration,
 ) {
+    // path-example: /ietf-interfaces:interfaces/interface[name='eth0']
+    let mut interface_xpath: String = String::from("/");
+    interface_xpath.push_str(&yang_paths.params.interfaces_by_name);
+    interface_xpath = interface_xpath.replace("{}", &port_configuration.name);
+
     // path-example: /ietf-interfaces:interfaces/interface[name='eth0']/ieee802-dot1q-sched:gate-parameters
-    let mut port_xpath: String = String::from("/");
-    port_xpath.push_str(&yang_paths.params.interfaces_by_name);
-    port_xpath = port_xpath.replace("{}", &port_configuration.name);
-    port_xpath.push_str("/");
-    port_xpath.push_str(&yang_paths.params.gate_parameters);
+    let port_xpath = interface_xpath.clone() + "/" + &yang_paths.params.gate_parameters;
 
     let config = &port_configuration.config;
 
@@ -143,11 +394,23 @@ pub fn put_configurations_in_dtree(
 
     // admin-control-list
     for (i, gce) in config.admin_control_list.iter().enumerate() {
-        let operation_name = match gce.operation_name {
-            crate::cnc::types::sched_types::GateControlOperation::SetGateStates => {
-                "set-gate-states"
-            }
-            _ => panic!("not supported"),
+        // the parameters are in a separate container for each operation
+        let (operation_name, gate_states_value, time_interval_value) = match gce.operation_name {
+            GateControlOperation::SetGateStates => (
+                "set-gate-states",
+                &yang_paths.params.sgs_params_gate_states_value,
+                &yang_paths.params.sgs_params_time_interval_value,
+            ),
+            GateControlOperation::SetAndHoldMAC => (
+                "set-and-hold-mac",
+                &yang_paths.params.shm_params_gate_states_value,
+                &yang_paths.params.shm_params_time_interval_value,
+            ),
+            GateControlOperation::SetAndReleaseMAC => (
+                "set-and-release-mac",
+                &yang_paths.params.srm_params_gate_states_value,
+                &yang_paths.params.srm_params_time_interval_value,
+            ),
         };
 
         let path_prefix = yang_paths
@@ -164,13 +427,13 @@ pub fn put_configurations_in_dtree(
         put_gate_parameters_in_dtree(
             dtree,
             port_xpath.clone(),
-            &(path_prefix.clone() + "/" + &yang_paths.params.sgs_params_gate_states_value),
+            &(path_prefix.clone() + "/" + gate_states_value),
             &gce.gate_state_value.to_string(),
         );
         put_gate_parameters_in_dtree(
             dtree,
             port_xpath.clone(),
-            &(path_prefix.clone() + "/" + &yang_paths.params.sgs_params_time_interval_value),
+            &(path_prefix.clone() + "/" + time_interval_value),
             &gce.time_interval_value.to_string(),
         );
     }
@@ -236,6 +499,711 @@ pub fn put_configurations_in_dtree(
         &yang_paths.params.config_change,
         &config.config_change.to_string(),
     );
+
+    // frame-preemption-status-table
+    let preemption_xpath = interface_xpath + "/" + &yang_paths.params.frame_preemption_parameters;
+    for entry in port_configuration.frame_preemption.iter() {
+        let frame_preemption_status = match entry.frame_preemption_status {
+            FramePreemptionStatus::Express => "express",
+            FramePreemptionStatus::Preemptable => "preemptable",
+        };
+
+        put_gate_parameters_in_dtree(
+            dtree,
+            preemption_xpath.clone(),
+            &yang_paths
+                .params
+                .frame_preemption_status_by_traffic_class
+                .replace("{}", &entry.traffic_class.to_string()),
+            frame_preemption_status,
+        );
+    }
+
+    put_fqtss_in_dtree(dtree, yang_paths, port_configuration);
+    put_psfp_in_dtree(dtree, yang_paths, port_configuration);
+    put_stream_identification_in_dtree(dtree, yang_paths, port_configuration);
+    put_frer_in_dtree(dtree, yang_paths, port_configuration);
+    put_forwarding_in_dtree(dtree, yang_paths, port_configuration);
+}
+
+/// the optional yang-modules, that the configuration of the port needs but that aren't loaded for
+/// the bridge. The port can't be configured without them.
+pub fn missing_optional_modules(
+    yang_ctx: &Context,
+    port_configuration: &PortConfiguration,
+) -> Vec<&'static str> {
+    let frer = &port_configuration.frer;
+    let needed = [
+        !port_configuration.fqtss.is_empty(),
+        !port_configuration.psfp.is_empty(),
+        !port_configuration.stream_identification.is_empty(),
+        !frer.sequence_generation.is_empty()
+            || !frer.sequence_recovery.is_empty()
+            || !frer.sequence_identification.is_empty(),
+    ];
+
+    OPTIONAL_YANG_MODULES
+        .iter()
+        .zip(needed)
+        .filter(|(module, needed)| *needed && !is_module_loaded(yang_ctx, module))
+        .map(|(module, _)| module.name)
+        .collect()
+}
+
+/// checks if the yang-module is loaded in the context
+fn is_module_loaded(yang_ctx: &Context, module: &YangModule) -> bool {
+    yang_ctx.get_module_latest(module.name).is_some()
+}
+
+/// the credit-based shaper of the port will be loaded into the given dtree. Ports that need it are
+/// only deployed if ieee802-dot1q-fqtss is loaded, see missing_optional_modules(...).
+fn put_fqtss_in_dtree(
+    dtree: &mut DataTree,
+    yang_paths: &YangPaths,
+    port_configuration: &PortConfiguration,
+) {
+    if port_configuration.fqtss.is_empty() {
+        return;
+    }
+    if !is_module_loaded(dtree.context(), &OPTIONAL_YANG_MODULES[0]) {
+        return;
+    }
+
+    for entry in port_configuration.fqtss.iter() {
+        let fqtss_xpath = |path: &String| {
+            String::from("/")
+                + &path
+                    .replace("{port}", &port_configuration.name)
+                    .replace("{traffic_class}", &entry.traffic_class.to_string())
+        };
+
+        // the idle slope is split into the most and least significant 32 bits
+        let bap_xpath = fqtss_xpath(&yang_paths.params.fqtss_bap_entry);
+        put_gate_parameters_in_dtree(
+            dtree,
+            bap_xpath.clone(),
+            &yang_paths.params.fqtss_admin_idle_slope_ms,
+            &(entry.admin_idle_slope >> 32).to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            bap_xpath,
+            &yang_paths.params.fqtss_admin_idle_slope_ls,
+            &(entry.admin_idle_slope & 0xffff_ffff).to_string(),
+        );
+
+        let algorithm_id = match entry.transmission_selection_algorithm {
+            TransmissionSelectionAlgorithm::StrictPriority => 0,
+            TransmissionSelectionAlgorithm::CreditBasedShaper => 1,
+        };
+        put_gate_parameters_in_dtree(
+            dtree,
+            fqtss_xpath(&yang_paths.params.fqtss_tx_selection_algorithm_entry),
+            &yang_paths.params.fqtss_tx_selection_algorithm_id,
+            &algorithm_id.to_string(),
+        );
+    }
+}
+
+/// the stream filters, stream gates and flow meters of the streams received on the port will be
+/// loaded into the given dtree. They belong to the bridge component. Ports that need them are only
+/// deployed if ieee802-dot1q-psfp is loaded, see missing_optional_modules(...).
+fn put_psfp_in_dtree(
+    dtree: &mut DataTree,
+    yang_paths: &YangPaths,
+    port_configuration: &PortConfiguration,
+) {
+    if port_configuration.psfp.is_empty() {
+        return;
+    }
+    if !is_module_loaded(dtree.context(), &OPTIONAL_YANG_MODULES[1]) {
+        return;
+    }
+
+    let params = &yang_paths.params;
+    let component_xpath = String::from("/") + &params.bridge_component;
+
+    for entry in port_configuration.psfp.iter() {
+        // stream-filter-instance-table
+        let filter = &entry.stream_filter;
+        let filter_xpath = component_xpath.clone()
+            + "/"
+            + &params
+                .stream_filter_by_id
+                .replace("{}", &filter.stream_filter_instance_id.to_string());
+
+        match filter.stream_handle {
+            Some(stream_handle) => put_gate_parameters_in_dtree(
+                dtree,
+                filter_xpath.clone(),
+                &params.stream_handle,
+                &stream_handle.to_string(),
+            ),
+            None => put_gate_parameters_in_dtree(
+                dtree,
+                filter_xpath.clone(),
+                &params.stream_handle_wildcard,
+                "",
+            ),
+        }
+        // no priority-spec matches all priorities
+        if let Some(priority_spec) = filter.priority_spec {
+            put_gate_parameters_in_dtree(
+                dtree,
+                filter_xpath.clone(),
+                &params.priority_spec,
+                &priority_spec.to_string(),
+            );
+        }
+        put_gate_parameters_in_dtree(
+            dtree,
+            filter_xpath.clone(),
+            &params.max_sdu_size,
+            &filter.max_sdu_size.to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            filter_xpath.clone(),
+            &params.stream_blocked_due_to_oversize_frame_enabled,
+            &filter
+                .stream_blocked_due_to_oversize_frame_enabled
+                .to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            filter_xpath.clone(),
+            &params.stream_gate_ref,
+            &filter.stream_gate_ref.to_string(),
+        );
+        if let Some(flow_meter_instance_id) = filter.flow_meter_instance_id {
+            put_gate_parameters_in_dtree(
+                dtree,
+                filter_xpath.clone(),
+                &params.flow_meter_ref,
+                &flow_meter_instance_id.to_string(),
+            );
+        }
+        put_gate_parameters_in_dtree(
+            dtree,
+            filter_xpath,
+            &params.flow_meter_enable,
+            &filter.flow_meter_instance_id.is_some().to_string(),
+        );
+
+        // stream-gate-instance-table
+        let gate = &entry.stream_gate;
+        let gate_xpath = component_xpath.clone()
+            + "/"
+            + &params
+                .stream_gate_by_id
+                .replace("{}", &gate.stream_gate_instance_id.to_string());
+
+        put_gate_parameters_in_dtree(
+            dtree,
+            gate_xpath.clone(),
+            &params.stream_gate_enabled,
+            &gate.gate_enable.to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            gate_xpath.clone(),
+            &params.stream_gate_admin_gate_states,
+            stream_gate_state(gate.admin_gate_states),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            gate_xpath.clone(),
+            &params.stream_gate_admin_ipv,
+            &ipv_spec(gate.admin_ipv),
+        );
+
+        for (i, gce) in gate.admin_control_list.iter().enumerate() {
+            let entry_xpath = gate_xpath.clone()
+                + "/"
+                + &params
+                    .stream_gate_control_entry_by_index
+                    .replace("{}", &i.to_string());
+
+            put_gate_parameters_in_dtree(
+                dtree,
+                entry_xpath.clone(),
+                &params.stream_gate_operation_name,
+                "ieee802-dot1q-psfp:set-gate-and-ipv",
+            );
+            put_gate_parameters_in_dtree(
+                dtree,
+                entry_xpath.clone(),
+                &params.stream_gate_state_value,
+                stream_gate_state(gce.gate_state_value),
+            );
+            put_gate_parameters_in_dtree(
+                dtree,
+                entry_xpath.clone(),
+                &params.stream_gate_ipv_spec,
+                &ipv_spec(gce.ipv_spec),
+            );
+            put_gate_parameters_in_dtree(
+                dtree,
+                entry_xpath.clone(),
+                &params.stream_gate_time_interval_value,
+                &gce.time_interval_value.to_string(),
+            );
+            if let Some(interval_octet_max) = gce.interval_octet_max {
+                put_gate_parameters_in_dtree(
+                    dtree,
+                    entry_xpath,
+                    &params.stream_gate_interval_octet_max,
+                    &interval_octet_max.to_string(),
+                );
+            }
+        }
+
+        put_gate_parameters_in_dtree(
+            dtree,
+            gate_xpath.clone(),
+            &params.stream_gate_admin_cycle_time_numerator,
+            &gate.admin_cycle_time.0.to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            gate_xpath.clone(),
+            &params.stream_gate_admin_cycle_time_denominator,
+            &gate.admin_cycle_time.1.to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            gate_xpath.clone(),
+            &params.stream_gate_admin_base_time_seconds,
+            &gate.admin_base_time.0.to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            gate_xpath.clone(),
+            &params.stream_gate_admin_base_time_nanoseconds,
+            &gate.admin_base_time.1.to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            gate_xpath,
+            &params.stream_gate_config_change,
+            &gate.config_change.to_string(),
+        );
+
+        // flow-meter-instance-table
+        let meter = &entry.flow_meter;
+        let meter_xpath = component_xpath.clone()
+            + "/"
+            + &params
+                .flow_meter_by_id
+                .replace("{}", &meter.flow_meter_instance_id.to_string());
+
+        let meter_params = [
+            (
+                &params.committed_information_rate,
+                meter.committed_information_rate.to_string(),
+            ),
+            (
+                &params.committed_burst_size,
+                meter.committed_burst_size.to_string(),
+            ),
+            (
+                &params.excess_information_rate,
+                meter.excess_information_rate.to_string(),
+            ),
+            (
+                &params.excess_burst_size,
+                meter.excess_burst_size.to_string(),
+            ),
+            (&params.drop_on_yellow, meter.drop_on_yellow.to_string()),
+            (
+                &params.mark_all_frames_red_enable,
+                meter.mark_all_frames_red_enable.to_string(),
+            ),
+        ];
+        for (path, value) in meter_params {
+            put_gate_parameters_in_dtree(dtree, meter_xpath.clone(), path, &value);
+        }
+    }
+}
+
+/// the stream identities of the streams received on the port will be loaded into the given dtree.
+/// Ports that need them are only deployed if ieee802-dot1cb-stream-identification is loaded, see
+/// missing_optional_modules(...).
+fn put_stream_identification_in_dtree(
+    dtree: &mut DataTree,
+    yang_paths: &YangPaths,
+    port_configuration: &PortConfiguration,
+) {
+    if port_configuration.stream_identification.is_empty() {
+        return;
+    }
+    if !is_module_loaded(dtree.context(), &OPTIONAL_YANG_MODULES[2]) {
+        return;
+    }
+
+    let params = &yang_paths.params;
+    for entry in port_configuration.stream_identification.iter() {
+        let identity_xpath = String::from("/")
+            + &params
+                .stream_identity_by_index
+                .replace("{}", &entry.index.to_string());
+
+        put_gate_parameters_in_dtree(
+            dtree,
+            identity_xpath.clone(),
+            &params.stream_identity_handle,
+            &entry.handle.to_string(),
+        );
+        for port in entry.in_facing_input_port_list.iter() {
+            put_gate_parameters_in_dtree(
+                dtree,
+                identity_xpath.clone(),
+                &params.stream_identity_input_port,
+                port,
+            );
+        }
+
+        // the parameters are in a separate container for each identification type
+        let (identification_xpath, destination_mac, tagged, vlan) = match &entry.identification {
+            StreamIdentificationType::NullStream(x) => (
+                identity_xpath + "/" + &params.null_stream_identification,
+                Some(&x.destination_mac),
+                x.tagged,
+                x.vlan,
+            ),
+            StreamIdentificationType::IpStream(x) => (
+                identity_xpath + "/" + &params.ip_stream_identification,
+                x.destination_mac.as_ref(),
+                x.tagged,
+                x.vlan,
+            ),
+        };
+
+        if let Some(destination_mac) = destination_mac {
+            put_gate_parameters_in_dtree(
+                dtree,
+                identification_xpath.clone(),
+                &params.destination_mac,
+                destination_mac,
+            );
+        }
+        let tagged = match tagged {
+            VlanTagIdentification::Tagged => "tagged",
+            VlanTagIdentification::Priority => "priority",
+            VlanTagIdentification::All => "all",
+        };
+        put_gate_parameters_in_dtree(dtree, identification_xpath.clone(), &params.tagged, tagged);
+        put_gate_parameters_in_dtree(
+            dtree,
+            identification_xpath.clone(),
+            &params.vlan,
+            &vlan.to_string(),
+        );
+
+        if let StreamIdentificationType::IpStream(x) = &entry.identification {
+            let next_protocol = match x.next_protocol {
+                6 => "tcp",
+                17 => "udp",
+                132 => "sctp",
+                _ => "none",
+            };
+            let ip_params = [
+                (&params.ip_source, x.ip_source.to_string()),
+                (&params.ip_destination, x.ip_destination.to_string()),
+                (&params.dscp, x.dscp.to_string()),
+                (&params.next_protocol, next_protocol.to_string()),
+                (&params.source_port, x.source_port.to_string()),
+                (&params.destination_port, x.destination_port.to_string()),
+            ];
+            for (path, value) in ip_params {
+                put_gate_parameters_in_dtree(dtree, identification_xpath.clone(), path, &value);
+            }
+        }
+    }
+}
+
+/// the FRER functions of the port will be loaded into the given dtree. The sequence generation and
+/// recovery functions belong to the bridge. Ports that need them are only deployed if
+/// ieee802-dot1cb-frer is loaded, see missing_optional_modules(...).
+fn put_frer_in_dtree(
+    dtree: &mut DataTree,
+    yang_paths: &YangPaths,
+    port_configuration: &PortConfiguration,
+) {
+    let frer = &port_configuration.frer;
+    if frer.sequence_generation.is_empty()
+        && frer.sequence_recovery.is_empty()
+        && frer.sequence_identification.is_empty()
+    {
+        return;
+    }
+    if !is_module_loaded(dtree.context(), &OPTIONAL_YANG_MODULES[3]) {
+        return;
+    }
+
+    let params = &yang_paths.params;
+    for entry in frer.sequence_generation.iter() {
+        let generation_xpath = String::from("/")
+            + &params
+                .sequence_generation_by_index
+                .replace("{}", &entry.index.to_string());
+
+        for stream_handle in entry.stream_list.iter() {
+            put_gate_parameters_in_dtree(
+                dtree,
+                generation_xpath.clone(),
+                &params.frer_stream,
+                &stream_handle.to_string(),
+            );
+        }
+        put_gate_parameters_in_dtree(
+            dtree,
+            generation_xpath,
+            &params.frer_direction_out_facing,
+            &entry.direction_out_facing.to_string(),
+        );
+    }
+
+    for entry in frer.sequence_recovery.iter() {
+        let recovery_xpath = String::from("/")
+            + &params
+                .sequence_recovery_by_index
+                .replace("{}", &entry.index.to_string());
+
+        for stream_handle in entry.stream_list.iter() {
+            put_gate_parameters_in_dtree(
+                dtree,
+                recovery_xpath.clone(),
+                &params.frer_stream,
+                &stream_handle.to_string(),
+            );
+        }
+        for port in entry.port_list.iter() {
+            put_gate_parameters_in_dtree(dtree, recovery_xpath.clone(), &params.frer_port, port);
+        }
+
+        let algorithm = match entry.algorithm {
+            SequenceRecoveryAlgorithm::Vector => "ieee802-dot1cb-frer-types:vector",
+            SequenceRecoveryAlgorithm::Match => "ieee802-dot1cb-frer-types:match",
+        };
+        let recovery_params = [
+            (
+                &params.frer_direction_out_facing,
+                entry.direction_out_facing.to_string(),
+            ),
+            (&params.algorithm, algorithm.to_string()),
+            (&params.history_length, entry.history_length.to_string()),
+            (&params.reset_timeout, entry.reset_timeout.to_string()),
+            (&params.take_no_sequence, entry.take_no_sequence.to_string()),
+            (
+                &params.individual_recovery,
+                entry.individual_recovery.to_string(),
+            ),
+        ];
+        for (path, value) in recovery_params {
+            put_gate_parameters_in_dtree(dtree, recovery_xpath.clone(), path, &value);
+        }
+    }
+
+    // the sequence identification table is keyed by the port and direction
+    for entry in frer.sequence_identification.iter() {
+        let identification_xpath = String::from("/")
+            + &params
+                .sequence_identification_by_port
+                .replace("{port}", &port_configuration.name)
+                .replace("{direction}", &entry.direction_out_facing.to_string());
+
+        for stream_handle in entry.stream_list.iter() {
+            put_gate_parameters_in_dtree(
+                dtree,
+                identification_xpath.clone(),
+                &params.frer_stream,
+                &stream_handle.to_string(),
+            );
+        }
+        put_gate_parameters_in_dtree(
+            dtree,
+            identification_xpath.clone(),
+            &params.active,
+            &entry.active.to_string(),
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            identification_xpath,
+            &params.encapsulation,
+            "ieee802-dot1cb-frer-types:r-tag",
+        );
+    }
+}
+
+/// the static filtering and VLAN registration entries of the port will be loaded into the given
+/// dtree. They belong to the filtering database of the bridge component and refer to the port by
+/// its port-number.
+///
+/// The edit-config only merges, so entries of the port that aren't part of the configuration
+/// anymore are reset instead of removed: static filtering entries that forward to the port are
+/// changed to forward-filter and tagged VLAN registrations to normal registration. Untagged
+/// registrations like the default VLAN are kept.
+fn put_forwarding_in_dtree(
+    dtree: &mut DataTree,
+    yang_paths: &YangPaths,
+    port_configuration: &PortConfiguration,
+) {
+    if port_configuration.port_number == 0 {
+        if !port_configuration.static_filtering.is_empty()
+            || !port_configuration.vlan_registration.is_empty()
+            || !port_configuration.released_static_filtering.is_empty()
+            || !port_configuration.released_vlan_registration.is_empty()
+        {
+            eprintln!(
+                "[Southbound] port {} has no port-number, skipping its static filtering entries",
+                port_configuration.name
+            );
+        }
+        return;
+    }
+
+    let params = &yang_paths.params;
+    let component_xpath = String::from("/") + &params.bridge_component;
+    let port_map = params
+        .port_map_by_ref
+        .replace("{}", &port_configuration.port_number.to_string());
+
+    // entries that the cnc configured before and no stream needs anymore. Entries of streams
+    // that aren't part of this configuration and entries of others aren't touched.
+    for entry in port_configuration.released_static_filtering.iter() {
+        let port_map_xpath = component_xpath.clone()
+            + "/"
+            + &params
+                .filtering_entry
+                .replace("{vid}", &entry.vid.to_string())
+                .replace("{address}", &entry.address)
+            + "/"
+            + &port_map;
+
+        if dtree.find_path(&port_map_xpath).is_ok() {
+            put_gate_parameters_in_dtree(
+                dtree,
+                port_map_xpath,
+                &params.control_element,
+                "forward-filter",
+            );
+        }
+    }
+    for entry in port_configuration.released_vlan_registration.iter() {
+        let port_map_xpath = component_xpath.clone()
+            + "/"
+            + &params
+                .vlan_registration_entry
+                .replace("{vid}", &entry.vid.to_string())
+            + "/"
+            + &port_map;
+
+        if dtree.find_path(&port_map_xpath).is_ok() {
+            put_gate_parameters_in_dtree(
+                dtree,
+                port_map_xpath,
+                &params.registrar_admin_control,
+                "normal",
+            );
+        }
+    }
+
+    for entry in port_configuration.static_filtering.iter() {
+        let entry_xpath = component_xpath.clone()
+            + "/"
+            + &params
+                .filtering_entry
+                .replace("{vid}", &entry.vid.to_string())
+                .replace("{address}", &entry.address);
+
+        let control_element = match entry.control_element {
+            StaticFilteringControl::Forward => "forward",
+            StaticFilteringControl::Filter => "filter",
+            StaticFilteringControl::ForwardFilter => "forward-filter",
+        };
+        put_gate_parameters_in_dtree(dtree, entry_xpath.clone(), &params.entry_type, "static");
+        put_gate_parameters_in_dtree(
+            dtree,
+            entry_xpath + "/" + &port_map,
+            &params.control_element,
+            control_element,
+        );
+    }
+
+    for entry in port_configuration.vlan_registration.iter() {
+        let entry_xpath = component_xpath.clone()
+            + "/"
+            + &params
+                .vlan_registration_entry
+                .replace("{vid}", &entry.vid.to_string());
+        let port_map_xpath = entry_xpath.clone() + "/" + &port_map;
+
+        let vlan_transmitted = match entry.vlan_transmitted {
+            VlanTransmitted::Tagged => "tagged",
+            VlanTransmitted::Untagged => "untagged",
+        };
+        put_gate_parameters_in_dtree(dtree, entry_xpath, &params.entry_type, "static");
+        put_gate_parameters_in_dtree(
+            dtree,
+            port_map_xpath.clone(),
+            &params.registrar_admin_control,
+            "fixed-new-ignored",
+        );
+        put_gate_parameters_in_dtree(
+            dtree,
+            port_map_xpath,
+            &params.vlan_transmitted,
+            vlan_transmitted,
+        );
+    }
+}
+
+/// the value of a leaf below the node. None if the leaf doesn't exist or isn't a string.
+fn leaf_value(dnode: &DataNodeRef, path: &str) -> Option<String> {
+    match dnode.find_path(path).ok()?.value()? {
+        DataValue::Other(v) => Some(v),
+        _ => None,
+    }
+}
+
+/// the value of a numeric leaf below the node. None if the leaf doesn't exist or isn't a number.
+fn leaf_number(dnode: &DataNodeRef, path: &str) -> Option<u64> {
+    match dnode.find_path(path).ok()?.value()? {
+        DataValue::Uint8(v) => Some(v as u64),
+        DataValue::Uint16(v) => Some(v as u64),
+        DataValue::Uint32(v) => Some(v as u64),
+        DataValue::Uint64(v) => Some(v),
+        DataValue::Int8(v) => u64::try_from(v).ok(),
+        DataValue::Int16(v) => u64::try_from(v).ok(),
+        DataValue::Int32(v) => u64::try_from(v).ok(),
+        DataValue::Int64(v) => u64::try_from(v).ok(),
+        DataValue::Other(v) => v.parse().ok(),
+        _ => None,
+    }
+}
+
+/// the value of a boolean leaf below the node. None if the leaf doesn't exist or isn't a boolean.
+fn leaf_bool(dnode: &DataNodeRef, path: &str) -> Option<bool> {
+    match dnode.find_path(path).ok()?.value()? {
+        DataValue::Bool(v) => Some(v),
+        _ => None,
+    }
+}
+
+fn stream_gate_state(state: StreamGateState) -> &'static str {
+    match state {
+        StreamGateState::Open => "open",
+        StreamGateState::Closed => "closed",
+    }
+}
+
+/// the internal priority value or wildcard, if the priority of the frame is kept
+fn ipv_spec(ipv: Option<u8>) -> String {
+    match ipv {
+        Some(ipv) => ipv.to_string(),
+        None => "wildcard".to_string(),
+    }
 }
 
 /// puts the in path specified node at xpath into the dtree. The value to insert can be provided as well.
@@ -276,13 +1244,99 @@ pub fn edit_config_in_candidate(
         .expect("couldnt parse datatree")
         .expect("no data");
 
-    netconf_connection.netconf_client.edit_config(
-        netconf_client::models::requests::DatastoreType::Candidate,
-        data,
-        Some(netconf_client::models::requests::DefaultOperationType::Merge),
-        Some(netconf_client::models::requests::TestOptionType::TestThenSet),
-        Some(netconf_client::models::requests::ErrorOptionType::RollbackOnError),
-    )?;
+    netconf_connection
+        .netconf_client
+        .edit_config(DatastoreType::Candidate, &data)?;
+
+    Ok(())
+}
+
+/// This will <lock> the candidate datastore, so no other client can change it until the
+/// deployment is finished. Changes that are still in the candidate are discarded, so only the
+/// configurations of the CNC are committed.
+pub fn lock_candidate(
+    netconf_connection: &mut NetconfConnection,
+) -> Result<(), NetconfClientError> {
+    netconf_connection
+        .netconf_client
+        .lock(DatastoreType::Candidate)?;
+    netconf_connection.netconf_client.discard_changes()?;
+
+    Ok(())
+}
+
+/// This will <unlock> the candidate datastore again.
+pub fn unlock_candidate(
+    netconf_connection: &mut NetconfConnection,
+) -> Result<(), NetconfClientError> {
+    netconf_connection
+        .netconf_client
+        .unlock(DatastoreType::Candidate)?;
+
+    Ok(())
+}
+
+/// This will <validate> the candidate datastore. Netconf-servers without the validate capability
+/// only validate on commit.
+pub fn validate_candidate(
+    netconf_connection: &mut NetconfConnection,
+) -> Result<(), NetconfClientError> {
+    if !netconf_connection.supports(VALIDATE) {
+        return Ok(());
+    }
+
+    netconf_connection
+        .netconf_client
+        .validate(DatastoreType::Candidate)?;
+
+    Ok(())
+}
+
+/// This will <commit> the candidate datastore with <confirmed/>. The netconf-server rolls back the
+/// commit after the timeout in seconds, if it isn't confirmed with confirm_commit(...) before.
+///
+/// Netconf-servers without the confirmed-commit capability are committed directly. Returns if the
+/// commit has to be confirmed.
+pub fn commit_confirmed(
+    netconf_connection: &mut NetconfConnection,
+    timeout: u32,
+) -> Result<bool, NetconfClientError> {
+    if !netconf_connection.supports(CONFIRMED_COMMIT) {
+        eprintln!(
+            "[Southbound] netconf-server doesn't support confirmed commits, committing directly"
+        );
+        netconf_connection.netconf_client.commit()?;
+        return Ok(false);
+    }
+
+    netconf_connection
+        .netconf_client
+        .commit_confirmed(timeout)?;
+
+    Ok(true)
+}
+
+/// This will confirm a confirmed commit with a <commit>.
+pub fn confirm_commit(
+    netconf_connection: &mut NetconfConnection,
+) -> Result<(), NetconfClientError> {
+    netconf_connection.netconf_client.commit()?;
+
+    Ok(())
+}
+
+/// This will roll back a confirmed commit with <cancel-commit>.
+pub fn cancel_commit(netconf_connection: &mut NetconfConnection) -> Result<(), NetconfClientError> {
+    netconf_connection.netconf_client.cancel_commit()?;
+
+    Ok(())
+}
+
+/// This will <discard-changes> of the candidate datastore.
+pub fn discard_changes(
+    netconf_connection: &mut NetconfConnection,
+) -> Result<(), NetconfClientError> {
+    netconf_connection.netconf_client.discard_changes()?;
 
     Ok(())
 }
@@ -291,18 +1345,15 @@ pub fn edit_config_in_candidate(
 pub fn get_lldp_remote_systems_data(
     netconf_connection: &mut NetconfConnection,
 ) -> Result<DataTree, NetconfClientError> {
-    let get_lldp_filter = Filter {
-        filter_type: FilterType::Subtree,
-        data: netconf_connection
-            .yang_paths
-            .filters
-            .remote_systems_data
-            .clone(),
-    };
+    let get_lldp_filter = netconf_connection
+        .yang_paths
+        .filters
+        .remote_systems_data
+        .clone();
 
     let response = netconf_connection
         .netconf_client
-        .get(Some(get_lldp_filter))?;
+        .get(Some(&get_lldp_filter))?;
     let data = response.data.expect("no data in dtree");
     let dtree = DataTree::parse_string(
         &netconf_connection.yang_ctx,
@@ -320,18 +1371,44 @@ pub fn get_lldp_remote_systems_data(
 pub fn get_interface_data(
     netconf_connection: &mut NetconfConnection,
 ) -> Result<DataTree, NetconfClientError> {
-    let get_interfaces_filter = Filter {
-        filter_type: FilterType::Subtree,
-        data: netconf_connection
-            .yang_paths
-            .filters
-            .gate_parameters_and_bridge_ports
-            .clone(),
-    };
+    let get_interfaces_filter = netconf_connection
+        .yang_paths
+        .filters
+        .gate_parameters_and_bridge_ports
+        .clone();
+
+    let response = netconf_connection
+        .netconf_client
+        .get(Some(&get_interfaces_filter))?;
+
+    let data = response.data.expect("no data in dtree");
+
+    let dtree = DataTree::parse_string(
+        &netconf_connection.yang_ctx,
+        data.as_str(),
+        DataFormat::XML,
+        DataParserFlags::NO_VALIDATION,
+        DataValidationFlags::empty(),
+    )
+    .expect("couldnt parse data");
+
+    Ok(dtree)
+}
+
+/// this runs a <get> rpc for the gate-parameters of all interfaces. Other than <get-config>, this
+/// includes the operational values.
+pub fn get_gate_parameters(
+    netconf_connection: &mut NetconfConnection,
+) -> Result<DataTree, NetconfClientError> {
+    let get_gate_parameters_filter = netconf_connection
+        .yang_paths
+        .filters
+        .gate_parameters
+        .clone();
 
     let response = netconf_connection
         .netconf_client
-        .get(Some(get_interfaces_filter))?;
+        .get(Some(&get_gate_parameters_filter))?;
 
     let data = response.data.expect("no data in dtree");
 
@@ -347,6 +1424,230 @@ pub fn get_interface_data(
     Ok(dtree)
 }
 
+/// extracts the operational gate-parameters of the interfaces by their name. Only gate-enabled and
+/// the operational values are filled, interfaces without gate-parameters are missing.
+pub fn extract_oper_gate_parameters(
+    dtree: &DataTree,
+    yang_paths: &YangPaths,
+) -> HashMap<String, GateParameterTableEntry> {
+    let params = &yang_paths.params;
+    let mut gate_parameters: HashMap<String, GateParameterTableEntry> = HashMap::new();
+
+    for (name, gate_dnode) in gate_parameter_nodes(dtree, params) {
+        // the other fields are private, they aren't read back
+        let mut entry = GateParameterTableEntry::default();
+        entry.gate_enable = leaf_bool(&gate_dnode, &params.gate_enabled).unwrap_or_default();
+        entry.oper_control_list =
+            extract_control_list(&gate_dnode, &params.oper_control_list, params);
+        entry.oper_cycle_time = (
+            leaf_number(&gate_dnode, &params.oper_cycle_time_numerator).unwrap_or_default() as i32,
+            leaf_number(&gate_dnode, &params.oper_cycle_time_denominator).unwrap_or_default()
+                as i32,
+        );
+        entry.oper_base_time = (
+            leaf_number(&gate_dnode, &params.oper_base_time_seconds).unwrap_or_default(),
+            leaf_number(&gate_dnode, &params.oper_base_time_fractional_seconds).unwrap_or_default()
+                as u32,
+        );
+        entry.config_pending = leaf_bool(&gate_dnode, &params.config_pending).unwrap_or_default();
+        entry.config_change_error =
+            leaf_number(&gate_dnode, &params.config_change_error).unwrap_or_default();
+
+        gate_parameters.insert(name, entry);
+    }
+
+    gate_parameters
+}
+
+/// extracts the administrative gate-parameters of the interfaces by their name, as they are
+/// configured on the bridge. Interfaces without gate-parameters are missing.
+pub fn extract_admin_gate_parameters(
+    dtree: &DataTree,
+    yang_paths: &YangPaths,
+) -> HashMap<String, ConfigurableGateParameterTableEntry> {
+    let params = &yang_paths.params;
+    let mut gate_parameters: HashMap<String, ConfigurableGateParameterTableEntry> = HashMap::new();
+
+    for (name, gate_dnode) in gate_parameter_nodes(dtree, params) {
+        let entry = ConfigurableGateParameterTableEntry {
+            gate_enable: leaf_bool(&gate_dnode, &params.gate_enabled).unwrap_or_default(),
+            admin_gate_states: leaf_number(&gate_dnode, &params.admin_gate_states)
+                .unwrap_or_default() as u8,
+            admin_control_list: extract_control_list(
+                &gate_dnode,
+                &params.admin_control_list,
+                params,
+            ),
+            admin_cycle_time: (
+                leaf_number(&gate_dnode, &params.admin_cycle_time_numerator).unwrap_or_default()
+                    as i32,
+                leaf_number(&gate_dnode, &params.admin_cycle_time_denominator).unwrap_or_default()
+                    as i32,
+            ),
+            admin_cycle_time_extension: leaf_number(&gate_dnode, &params.admin_cycle_time_extension)
+                .unwrap_or_default() as u32,
+            admin_base_time: (
+                leaf_number(&gate_dnode, &params.admin_base_time_seconds).unwrap_or_default(),
+                leaf_number(&gate_dnode, &params.admin_base_time_fractional_seconds)
+                    .unwrap_or_default() as u32,
+            ),
+            config_change: leaf_bool(&gate_dnode, &params.config_change).unwrap_or_default(),
+        };
+
+        gate_parameters.insert(name, entry);
+    }
+
+    gate_parameters
+}
+
+/// the gate-parameters nodes of the interfaces in the dtree with the name of their interface
+fn gate_parameter_nodes<'a>(
+    dtree: &'a DataTree,
+    params: &SwitchParameters,
+) -> Vec<(String, DataNodeRef<'a>)> {
+    let interfaces_path: String = String::from("/") + &params.interfaces;
+
+    let interfaces = match dtree.find_xpath(&interfaces_path) {
+        Ok(interfaces) => interfaces,
+        Err(_) => return Vec::new(),
+    };
+
+    interfaces
+        .filter_map(|interface_dnode| {
+            let gate_dnode = interface_dnode.find_path(&params.gate_parameters).ok()?;
+            let name = extract_interface_name_from_xpath(interface_dnode.path().as_str());
+            Some((name, gate_dnode))
+        })
+        .collect()
+}
+
+/// extracts the gate control list at the path below the gate-parameters node, ordered by the
+/// index of the entries
+fn extract_control_list(
+    gate_dnode: &DataNodeRef,
+    list_path: &str,
+    params: &SwitchParameters,
+) -> Vec<GateControlEntry> {
+    let mut control_list: Vec<(u64, GateControlEntry)> = Vec::new();
+
+    if let Ok(entries) = gate_dnode.find_xpath(list_path) {
+        for entry in entries {
+            let operation = leaf_value(&entry, &params.operation_name).unwrap_or_default();
+            // identities might be prefixed with their module
+            let (operation_name, gate_states_value, time_interval_value) =
+                match operation.rsplit(':').next().unwrap_or_default() {
+                    "set-and-hold-mac" => (
+                        GateControlOperation::SetAndHoldMAC,
+                        &params.shm_params_gate_states_value,
+                        &params.shm_params_time_interval_value,
+                    ),
+                    "set-and-release-mac" => (
+                        GateControlOperation::SetAndReleaseMAC,
+                        &params.srm_params_gate_states_value,
+                        &params.srm_params_time_interval_value,
+                    ),
+                    _ => (
+                        GateControlOperation::SetGateStates,
+                        &params.sgs_params_gate_states_value,
+                        &params.sgs_params_time_interval_value,
+                    ),
+                };
+
+            control_list.push((
+                leaf_number(&entry, "index").unwrap_or_default(),
+                GateControlEntry {
+                    operation_name,
+                    time_interval_value: leaf_number(&entry, time_interval_value)
+                        .unwrap_or_default() as u32,
+                    gate_state_value: leaf_number(&entry, gate_states_value).unwrap_or_default()
+                        as u8,
+                },
+            ));
+        }
+    }
+
+    control_list.sort_by_key(|(index, _)| *index);
+    control_list.into_iter().map(|(_, e)| e).collect()
+}
+
+/// this runs a <create-subscription> rpc (RFC 5277) for the default NETCONF event stream. The
+/// session receives the notifications of the bridge afterwards and can't be used for other rpcs.
+pub fn create_subscription(
+    netconf_connection: &mut NetconfConnection,
+) -> Result<(), NetconfClientError> {
+    netconf_connection
+        .netconf_client
+        .create_subscription(None)?;
+    Ok(())
+}
+
+/// blocks until the next <notification> of the subscription is received and returns it
+pub fn receive_notification(
+    netconf_connection: &mut NetconfConnection,
+) -> Result<String, NetconfClientError> {
+    netconf_connection.netconf_client.receive_notification()
+}
+
+/// parses the content of a <notification> with the yang-models of the bridge
+pub fn parse_notification(
+    netconf_connection: &NetconfConnection,
+    content: &str,
+) -> Result<DataTree, yang2::Error> {
+    DataTree::parse_op_string(
+        &netconf_connection.yang_ctx,
+        content,
+        DataFormat::XML,
+        DataOperation::NotificationYang,
+    )
+}
+
+/// describes a netconf-config-change notification (ietf-netconf-notifications) by the user and the
+/// changed paths. None if the notification is another one.
+pub fn extract_config_change(dtree: &DataTree) -> Option<String> {
+    let change = dtree
+        .find_path("/ietf-netconf-notifications:netconf-config-change")
+        .ok()?;
+
+    let username = leaf_value(&change, "changed-by/username").unwrap_or(String::from("server"));
+    let targets: Vec<String> = match change.find_xpath("edit/target") {
+        Ok(targets) => targets.filter_map(|t| t.value_canonical()).collect(),
+        Err(_) => Vec::new(),
+    };
+
+    Some(format!("by {}: {}", username, targets.join(", ")))
+}
+
+/// extracts the changed operational states of the interfaces from a notification by the name of
+/// the interface, true if it is up. Every oper-status leaf in the notification is a change, the
+/// interface is named by a sibling leaf or the key of the interface in its path.
+pub fn extract_link_changes(dtree: &DataTree, yang_paths: &YangPaths) -> Vec<(String, bool)> {
+    let params = &yang_paths.params;
+    let mut link_changes: Vec<(String, bool)> = Vec::new();
+
+    for dnode in dtree.traverse() {
+        if dnode.schema().name() != params.oper_status {
+            continue;
+        }
+
+        let status = dnode.value_canonical().unwrap_or_default();
+        let path = dnode.path();
+        let name = dnode
+            .parent()
+            .and_then(|parent| leaf_value(&parent, &params.oper_status_interface_name))
+            .or_else(|| {
+                path.contains("interface[name='")
+                    .then(|| extract_interface_name_from_xpath(&path))
+            });
+
+        match name {
+            Some(name) => link_changes.push((name, status == "up")),
+            None => eprintln!("[Southbound] no interface for the oper-status at {}", path),
+        }
+    }
+
+    link_changes
+}
+
 /// helper function to extract the interface name from an xpath
 ///
 /// this only works, if a single attribute is provided in the xpath and this one is named 'name'
@@ -368,6 +1669,24 @@ fn extract_interface_name_from_xpath(xpath: &str) -> String {
     String::from(only_name)
 }
 
+/// helper function to extract the local port name from an xpath of the lldp-data
+///
+/// # Example
+///
+/// "/ieee802-dot1ab-lldp:lldp/port[name='sw0p2'][dest-mac-address='01-80-C2-00-00-0E']/remote-systems-data[time-mark='0'][remote-index='1']" -> "sw0p2"
+///
+/// Names that contain a ' are quoted with " instead. None if the xpath has no port name.
+fn extract_port_name_from_lldp_xpath(xpath: &str) -> Option<String> {
+    let (_, quoted_name) = xpath.split_once("port[name=")?;
+    let quote = quoted_name
+        .chars()
+        .next()
+        .filter(|c| *c == '\'' || *c == '"')?;
+    let (name, _) = quoted_name[1..].split_once(quote)?;
+
+    Some(String::from(name))
+}
+
 /// helper function to extract the last node name from an xpath
 ///
 /// # Example
@@ -392,6 +1711,16 @@ pub fn extract_remote_systems_data(
         .expect("no remote-systems-data found")
     {
         let mut system = RemoteSystemsData::new();
+        system.local_port_name = match extract_port_name_from_lldp_xpath(dnode.path().as_str()) {
+            Some(port_name) => port_name,
+            None => {
+                eprintln!(
+                    "[Southbound] no local port in the remote-systems-data {}, skipping it",
+                    dnode.path()
+                );
+                continue;
+            }
+        };
 
         if let Ok(child_node) = dnode.find_path(&yang_paths.params.chassis_id_subtype) {
             if let Some(value) = child_node.value() {
@@ -574,6 +1903,9 @@ pub fn extract_port_delays(dtree: &DataTree, yang_paths: &YangPaths) -> Vec<Port
             mac_address: String::new(),
             delays: Vec::new(),
             tick_granularity: 0,
+            port_number: 0,
+            supported_list_max: 0,
+            supported_cycle_max: (0, 0),
         };
 
         if let Ok(child_node) = interface_dnode.find_path(&yang_paths.params.bridge_port_address) {
@@ -587,6 +1919,17 @@ pub fn extract_port_delays(dtree: &DataTree, yang_paths: &YangPaths) -> Vec<Port
             eprintln!("no bridge-port-address found in dtree");
         };
 
+        if let Ok(child_node) = interface_dnode.find_path(&yang_paths.params.bridge_port_number) {
+            if let Some(value) = child_node.value() {
+                match value {
+                    DataValue::Uint32(v) => port.port_number = v,
+                    _ => eprintln!("found an unexpected node in dtree"),
+                }
+            }
+        } else {
+            eprintln!("no bridge-port-number found in dtree");
+        };
+
         if let Ok(child_node) = interface_dnode.find_path(&yang_paths.params.tick_granularity) {
             if let Some(value) = child_node.value() {
                 match value {
@@ -598,6 +1941,46 @@ pub fn extract_port_delays(dtree: &DataTree, yang_paths: &YangPaths) -> Vec<Port
             eprintln!("no tick-granularity found in dtree");
         };
 
+        let gate_parameters = yang_paths.params.gate_parameters.clone() + "/";
+        if let Ok(child_node) = interface_dnode
+            .find_path(&(gate_parameters.clone() + &yang_paths.params.supported_list_max))
+        {
+            if let Some(value) = child_node.value() {
+                match value {
+                    DataValue::Uint32(v) => port.supported_list_max = v,
+                    _ => eprintln!("found an unexpected node in dtree"),
+                }
+            }
+        } else {
+            eprintln!("no supported-list-max found in dtree");
+        };
+
+        if let Ok(child_node) = interface_dnode.find_path(
+            &(gate_parameters.clone() + &yang_paths.params.supported_cycle_max_numerator),
+        ) {
+            if let Some(value) = child_node.value() {
+                match value {
+                    DataValue::Uint32(v) => port.supported_cycle_max.0 = v as i32,
+                    _ => eprintln!("found an unexpected node in dtree"),
+                }
+            }
+        } else {
+            eprintln!("no supported-cycle-max found in dtree");
+        };
+
+        if let Ok(child_node) = interface_dnode
+            .find_path(&(gate_parameters + &yang_paths.params.supported_cycle_max_denominator))
+        {
+            if let Some(value) = child_node.value() {
+                match value {
+                    DataValue::Uint32(v) => port.supported_cycle_max.1 = v as i32,
+                    _ => eprintln!("found an unexpected node in dtree"),
+                }
+            }
+        } else {
+            eprintln!("no supported-cycle-max found in dtree");
+        };
+
         for bridge_port_delays_dnode in interface_dnode
             .find_xpath((path + "/bridge-port/bridge-port-delays").as_str())
             .expect("no bpd nodes found")
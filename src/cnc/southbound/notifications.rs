@@ -0,0 +1,248 @@
+use super::netconf::{
+    create_subscription, extract_config_change, extract_link_changes, parse_notification,
+    receive_notification,
+};
+use super::sessions::{close, SessionPool};
+use super::types::NetconfConnection;
+use super::SouthboundControllerInterface;
+use crate::cnc::types::topology::{LinkEvent, SSHConfigurationParams};
+use crate::cnc::Cnc;
+use std::collections::{HashMap, HashSet};
+use std::net::{Shutdown, TcpStream};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// bridges of the topology without a subscription are subscribed in this interval, e.g. after they
+/// were discovered or their subscription was lost
+const SUBSCRIPTION_INTERVAL: Duration = Duration::from_secs(30);
+
+/// the subscriptions are stopped at most this long after the CNC stopped
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// capability of netconf-servers that support <create-subscription> (RFC 5277)
+const NOTIFICATION: &str = "urn:ietf:params:netconf:capability:notification:1.0";
+
+/// nodes with a running subscription or without support for notifications. While the
+/// notifications are received, the node has the shutdown handle of its connection.
+type Subscriptions = Arc<Mutex<HashMap<u32, Option<TcpStream>>>>;
+
+/// Subscribes to the notifications of all bridges in the topology until the CNC stops. Every
+/// bridge gets its own session and thread, which receives the notifications and forwards link
+/// changes to the CNC.
+///
+/// The subscriptions of bridges that left the topology and all subscriptions after the CNC stopped
+/// are ended by shutting their connections down, which interrupts the waiting threads.
+pub fn subscribe_bridges(cnc: Weak<Cnc>, sessions: Arc<SessionPool>) {
+    let subscriptions: Subscriptions = Arc::new(Mutex::new(HashMap::new()));
+
+    'subscribe: loop {
+        let topology = match cnc.upgrade() {
+            Some(cnc) => cnc.topology.get_topology(),
+            None => break,
+        };
+
+        let node_ids: HashSet<u32> = topology.nodes.iter().map(|n| n.id).collect();
+        subscriptions
+            .lock()
+            .unwrap()
+            .retain(|node_id, shutdown_handle| {
+                if node_ids.contains(node_id) {
+                    return true;
+                }
+
+                println!(
+                    "[Southbound] node {} left the topology, ending its subscription",
+                    node_id
+                );
+                end_subscription(shutdown_handle);
+                false
+            });
+
+        for node in topology.nodes {
+            let config_params = match node.configuration_params {
+                Some(config_params) => config_params,
+                None => continue,
+            };
+
+            let mut running = subscriptions.lock().unwrap();
+            if running.contains_key(&node.id) {
+                continue;
+            }
+            running.insert(node.id, None);
+            drop(running);
+
+            let cnc = cnc.clone();
+            let sessions = sessions.clone();
+            let subscriptions = subscriptions.clone();
+            thread::spawn(move || {
+                if receive_notifications(node.id, config_params, &sessions, &cnc, &subscriptions) {
+                    subscriptions.lock().unwrap().remove(&node.id);
+                }
+            });
+        }
+
+        let started = Instant::now();
+        while started.elapsed() < SUBSCRIPTION_INTERVAL {
+            thread::sleep(SHUTDOWN_CHECK_INTERVAL);
+            if cnc.strong_count() == 0 {
+                break 'subscribe;
+            }
+        }
+    }
+
+    for (_, shutdown_handle) in subscriptions.lock().unwrap().drain() {
+        end_subscription(&shutdown_handle);
+    }
+}
+
+/// shuts the connection of the subscription down. Its thread stops receiving notifications.
+fn end_subscription(shutdown_handle: &Option<TcpStream>) {
+    if let Some(socket) = shutdown_handle {
+        // fails if the connection is already closed
+        let _ = socket.shutdown(Shutdown::Both);
+    }
+}
+
+/// the content of a <notification> (RFC 5277) without its envelope and eventTime
+///
+/// # Example
+///
+/// "<notification xmlns=\"urn:ietf:params:xml:ns:netconf:notification:1.0\"><eventTime>2024-01-01T00:00:00Z</eventTime><link-down/></notification>" -> "<link-down/>"
+pub fn notification_content(notification: &str) -> Option<&str> {
+    let start = notification.find("</eventTime>")? + "</eventTime>".len();
+    let end = notification.rfind("</")?;
+
+    (start <= end).then(|| notification[start..end].trim())
+}
+
+/// Receives the notifications of the bridge until the subscription is lost or ended. Returns true
+/// if the bridge should be subscribed again.
+fn receive_notifications(
+    node_id: u32,
+    config_params: SSHConfigurationParams,
+    sessions: &Arc<SessionPool>,
+    cnc: &Weak<Cnc>,
+    subscriptions: &Subscriptions,
+) -> bool {
+    let mut connection = match sessions.dedicated_session(&config_params) {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!(
+                "[Southbound] couldn't connect to node {} for notifications: {e:?}",
+                node_id
+            );
+            return true;
+        }
+    };
+
+    if !connection.supports(NOTIFICATION) {
+        println!(
+            "[Southbound] node {} doesn't support notifications, it is only polled",
+            node_id
+        );
+        close(connection);
+        return false;
+    }
+
+    if let Err(e) = create_subscription(&mut connection) {
+        eprintln!("[Southbound] couldn't subscribe to node {}: {e:?}", node_id);
+        close(connection);
+        return true;
+    }
+    println!(
+        "[Southbound] subscribed to the notifications of node {}",
+        node_id
+    );
+
+    // notifications can be minutes apart, the session is ended with the shutdown handle instead
+    connection.netconf_client.set_timeout(Duration::ZERO);
+
+    let registered = match subscriptions.lock().unwrap().get_mut(&node_id) {
+        Some(shutdown_handle) => {
+            *shutdown_handle = connection.shutdown_handle();
+            true
+        }
+        None => false, // the subscription was ended while it was created
+    };
+    if !registered {
+        close(connection);
+        return false;
+    }
+
+    match cnc.upgrade() {
+        Some(cnc) => cnc.notify_subscribed(node_id),
+        None => {
+            close(connection);
+            return false;
+        }
+    }
+
+    loop {
+        let notification = match receive_notification(&mut connection) {
+            Ok(notification) => notification,
+            Err(_) if !subscriptions.lock().unwrap().contains_key(&node_id) => {
+                println!("[Southbound] ended the subscription of node {}", node_id);
+                close(connection);
+                return false;
+            }
+            Err(e) => {
+                eprintln!(
+                    "[Southbound] lost the subscription of node {}: {e:?}",
+                    node_id
+                );
+                close(connection);
+                return true;
+            }
+        };
+
+        let cnc = match cnc.upgrade() {
+            Some(cnc) => cnc,
+            None => {
+                close(connection);
+                return false;
+            }
+        };
+
+        handle_notification(node_id, &connection, &notification, &cnc);
+    }
+}
+
+/// decodes the notification, logs configuration changes and forwards link changes to the CNC
+fn handle_notification(
+    node_id: u32,
+    connection: &NetconfConnection,
+    notification: &str,
+    cnc: &Arc<Cnc>,
+) {
+    let dtree = match notification_content(notification).map(|c| parse_notification(connection, c))
+    {
+        Some(Ok(dtree)) => dtree,
+        Some(Err(e)) => {
+            eprintln!(
+                "[Southbound] couldn't decode a notification of node {}: {e}",
+                node_id
+            );
+            return;
+        }
+        None => {
+            eprintln!("[Southbound] invalid notification of node {}", node_id);
+            return;
+        }
+    };
+
+    if let Some(change) = extract_config_change(&dtree) {
+        println!(
+            "[Southbound] configuration of node {} changed {}",
+            node_id, change
+        );
+    }
+
+    for (port_name, up) in extract_link_changes(&dtree, &connection.yang_paths) {
+        cnc.notify_link_changed(LinkEvent {
+            node_id,
+            port_name,
+            up,
+        });
+    }
+}
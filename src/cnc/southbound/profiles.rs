@@ -0,0 +1,194 @@
+use super::types::{ModuleCapability, SearchFilters, SwitchParameters, YANG_MODULES};
+use serde::Deserialize;
+use std::fs;
+
+/// folder for the device profiles. Every *.json file in there is loaded as a profile.
+pub const PROFILE_DIR: &str = "./assets/profiles/";
+
+/// name of the built-in profile for devices that implement the standard yang-models
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A device profile provides the yang-models, paths and filters to manage the devices of a
+/// vendor or model.
+///
+/// A profile is selected by its name in the SSHConfigurationParams of a node. Otherwise, the first
+/// profile with a capability in its namespace in the <hello>-Message of the device is used, and the
+/// default profile if there is none.
+///
+/// # Example
+///
+/// ```json
+/// {
+///     "name": "example",
+///     "namespace": "urn:example:vendor",
+///     "pin_revisions": true,
+///     "params": {
+///         "gate_enabled": "gate-enabled"
+///     }
+/// }
+/// ```
+#[derive(Deserialize, Clone)]
+pub struct DeviceProfile {
+    pub name: String,
+
+    /// devices with a capability in this namespace use this profile
+    #[serde(default)]
+    pub namespace: Option<String>,
+
+    /// yang-models that are needed for the paths and filters of the profile. They are always
+    /// loaded with their features, even if the device doesn't advertise them.
+    #[serde(default = "default_modules")]
+    pub modules: Vec<ModuleCapability>,
+
+    /// the models are loaded in the revision of the profile instead of the advertised one. This is
+    /// needed for devices that don't advertise the models they implement correctly.
+    #[serde(default)]
+    pub pin_revisions: bool,
+
+    /// paths that aren't specified are the default ones
+    #[serde(default)]
+    pub params: SwitchParameters,
+
+    /// filters that aren't specified are the default ones
+    #[serde(default)]
+    pub filters: SearchFilters,
+}
+
+fn default_modules() -> Vec<ModuleCapability> {
+    YANG_MODULES.iter().map(ModuleCapability::from).collect()
+}
+
+impl Default for DeviceProfile {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_PROFILE.to_string(),
+            namespace: None,
+            modules: default_modules(),
+            pin_revisions: false,
+            params: SwitchParameters::default(),
+            filters: SearchFilters::default(),
+        }
+    }
+}
+
+impl DeviceProfile {
+    /// parses a profile from json
+    pub fn from_json(content: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str::<Self>(content)
+    }
+
+    /// loads the default profile and all profiles in the directory. A profile with the name of the
+    /// default profile replaces it.
+    pub fn load_profiles(dir: &str) -> Vec<Self> {
+        let mut profiles: Vec<Self> = vec![Self::default()];
+
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return profiles,
+        };
+
+        let mut paths: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().map_or(false, |e| e == "json"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let profile = fs::read_to_string(&path)
+                .map_err(|e| format!("{e:?}"))
+                .and_then(|content| Self::from_json(&content).map_err(|e| format!("{e:?}")));
+
+            match profile {
+                Ok(profile) => {
+                    profiles.retain(|p| p.name != profile.name);
+                    profiles.push(profile);
+                }
+                Err(e) => eprintln!(
+                    "[Southbound] couldn't load device profile {:?}: {}",
+                    path, e
+                ),
+            }
+        }
+
+        profiles
+    }
+
+    /// selects the profile of a device by its configured name or by the capabilities of its
+    /// <hello>-Message.
+    pub fn select(profiles: &Vec<Self>, name: Option<&String>, capabilities: &Vec<String>) -> Self {
+        if let Some(name) = name {
+            match profiles.iter().find(|p| &p.name == name) {
+                Some(profile) => return profile.clone(),
+                None => {
+                    eprintln!("[Southbound] unknown device profile {name}, detecting it instead")
+                }
+            }
+        }
+
+        let detected = profiles.iter().find(|p| match &p.namespace {
+            Some(namespace) => capabilities
+                .iter()
+                .any(|c| c.starts_with(namespace.as_str())),
+            None => false,
+        });
+
+        match detected {
+            Some(profile) => profile.clone(),
+            None => profiles
+                .iter()
+                .find(|p| p.name == DEFAULT_PROFILE)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// loads the profiles from the PROFILE_DIR and selects the one of the device
+    pub fn for_device(name: Option<&String>, capabilities: &Vec<String>) -> Self {
+        let profile = Self::select(&Self::load_profiles(PROFILE_DIR), name, capabilities);
+        println!("[Southbound] using device profile {}", profile.name);
+        profile
+    }
+
+    /// this merges the yang-models advertised in the capabilities of the <hello>-Message with the
+    /// models of the profile.
+    ///
+    /// The models of the profile are always needed for its paths. Advertised models keep their
+    /// revision unless the profile pins it, other models are added in the revision of the profile.
+    /// Their features are always enabled, even if the server doesn't advertise them.
+    pub fn yang_modules(&self, capabilities: &Vec<String>) -> Vec<ModuleCapability> {
+        let mut yang_modules: Vec<ModuleCapability> = capabilities
+            .iter()
+            .filter_map(|c| ModuleCapability::parse(c))
+            .collect();
+
+        for module in self.modules.iter() {
+            match yang_modules.iter_mut().find(|m| m.name == module.name) {
+                Some(advertised) => {
+                    if self.pin_revisions {
+                        advertised.revision = module.revision.clone();
+                    }
+                    for feature in module.features.iter() {
+                        if !advertised.features.contains(feature) {
+                            advertised.features.push(feature.clone());
+                        }
+                    }
+                }
+                None => {
+                    eprintln!(
+                        "[Southbound] yang-module {} isn't advertised, loading it from the search directory",
+                        module.name
+                    );
+                    yang_modules.push(module.clone());
+                }
+            }
+        }
+
+        yang_modules
+    }
+
+    /// checks if the yang-model is needed for the paths of the profile
+    pub fn needs_module(&self, name: &str) -> bool {
+        self.modules.iter().any(|m| m.name == name)
+    }
+}
@@ -0,0 +1,225 @@
+use super::netconf::{establish_netconf_connection, keep_alive};
+use super::types::{ConnectionError, NetconfConnection, YangContexts};
+use crate::cnc::types::topology::SSHConfigurationParams;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// idle sessions are checked with a keepalive after this interval and reconnected if they are dead
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// a session that isn't used at the moment
+struct IdleSession {
+    config_params: SSHConfigurationParams,
+    connection: NetconfConnection,
+    last_used: Instant,
+}
+
+/// Pool of the netconf-sessions to the bridges.
+///
+/// Sessions are kept open after they are used, so discovery and configuration don't need a new
+/// SSH handshake and yang-context every time. A background thread sends keepalives over idle
+/// sessions and reconnects them if they are dead. The yang-contexts are shared between sessions
+/// with the same yang-models.
+pub struct SessionPool {
+    /// idle sessions by the address of the bridge
+    sessions: Mutex<HashMap<String, IdleSession>>,
+    yang_contexts: YangContexts,
+}
+
+impl SessionPool {
+    /// creates the pool and starts the keepalive thread. The thread stops with the pool.
+    pub fn new() -> Arc<Self> {
+        let pool = Arc::new(Self {
+            sessions: Mutex::new(HashMap::new()),
+            yang_contexts: Mutex::new(HashMap::new()),
+        });
+
+        let weak_pool = Arc::downgrade(&pool);
+        thread::spawn(move || Self::keep_alive_idle_sessions(weak_pool));
+
+        pool
+    }
+
+    /// the session of the bridge. An idle session is reused if it's still alive, otherwise a new
+    /// one is established. The session returns to the pool when it's dropped.
+    pub fn session(
+        self: &Arc<Self>,
+        config_params: &SSHConfigurationParams,
+    ) -> Result<PooledSession, ConnectionError> {
+        let key = session_key(config_params);
+        let idle = self.sessions.lock().unwrap().remove(&key);
+
+        let connection = match idle {
+            Some(mut idle) if &idle.config_params == config_params => {
+                if idle.last_used.elapsed() < KEEPALIVE_INTERVAL {
+                    Some(idle.connection)
+                } else {
+                    match keep_alive(&mut idle.connection) {
+                        Ok(_) => Some(idle.connection),
+                        Err(e) => {
+                            eprintln!("[Southbound] session to {key} is dead, reconnecting: {e:?}");
+                            None
+                        }
+                    }
+                }
+            }
+            Some(idle) => {
+                // the configuration params changed, e.g. the credentials or the profile
+                close(idle.connection);
+                None
+            }
+            None => None,
+        };
+
+        let connection = match connection {
+            Some(connection) => connection,
+            None => establish_netconf_connection(config_params, &self.yang_contexts)?,
+        };
+
+        Ok(PooledSession {
+            pool: Arc::downgrade(self),
+            config_params: config_params.clone(),
+            connection: Some(connection),
+            reusable: true,
+        })
+    }
+
+    /// a session that doesn't belong to the pool, e.g. for a subscription that occupies it. It
+    /// shares the yang-contexts of the pool and has to be closed by the caller.
+    pub fn dedicated_session(
+        &self,
+        config_params: &SSHConfigurationParams,
+    ) -> Result<NetconfConnection, ConnectionError> {
+        establish_netconf_connection(config_params, &self.yang_contexts)
+    }
+
+    /// puts an established session into the pool, e.g. one to a scripted netconf-server
+    #[cfg(test)]
+    pub(crate) fn insert_session(
+        &self,
+        config_params: SSHConfigurationParams,
+        connection: NetconfConnection,
+    ) {
+        self.release(config_params, connection);
+    }
+
+    /// puts the session back into the pool. If there is already an idle session to the bridge,
+    /// the session is closed.
+    fn release(&self, config_params: SSHConfigurationParams, connection: NetconfConnection) {
+        let key = session_key(&config_params);
+        let mut sessions = self.sessions.lock().unwrap();
+
+        if sessions.contains_key(&key) {
+            drop(sessions);
+            close(connection);
+            return;
+        }
+
+        sessions.insert(
+            key,
+            IdleSession {
+                config_params,
+                connection,
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    /// sends a keepalive over every idle session in the KEEPALIVE_INTERVAL. Dead sessions are
+    /// reconnected, sessions that can't be reconnected are removed.
+    ///
+    /// The sessions are taken out of the pool one at a time, so the others stay available while a
+    /// bridge is slow to answer or reconnected.
+    fn keep_alive_idle_sessions(pool: Weak<Self>) {
+        loop {
+            thread::sleep(KEEPALIVE_INTERVAL);
+
+            let pool = match pool.upgrade() {
+                Some(pool) => pool,
+                None => return,
+            };
+
+            let keys: Vec<String> = pool.sessions.lock().unwrap().keys().cloned().collect();
+
+            for key in keys {
+                // the session was taken in the meantime
+                let mut idle = match pool.sessions.lock().unwrap().remove(&key) {
+                    Some(idle) => idle,
+                    None => continue,
+                };
+
+                if let Err(e) = keep_alive(&mut idle.connection) {
+                    eprintln!("[Southbound] session to {key} is dead, reconnecting: {e:?}");
+
+                    match establish_netconf_connection(&idle.config_params, &pool.yang_contexts) {
+                        Ok(connection) => idle.connection = connection,
+                        Err(e) => {
+                            eprintln!("[Southbound] couldn't reconnect to {key}: {e:?}");
+                            continue;
+                        }
+                    }
+                }
+
+                pool.release(idle.config_params, idle.connection);
+            }
+        }
+    }
+}
+
+/// A netconf-session of the pool. It returns to the pool when it's dropped, unless it was
+/// discarded.
+pub struct PooledSession {
+    pool: Weak<SessionPool>,
+    config_params: SSHConfigurationParams,
+    connection: Option<NetconfConnection>,
+    reusable: bool,
+}
+
+impl PooledSession {
+    /// closes the session instead of returning it to the pool. This is needed if the state of the
+    /// session is unknown, e.g. if the candidate couldn't be unlocked.
+    pub fn discard(mut self) {
+        self.reusable = false;
+    }
+}
+
+impl Deref for PooledSession {
+    type Target = NetconfConnection;
+
+    fn deref(&self) -> &Self::Target {
+        self.connection.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledSession {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.connection.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledSession {
+    fn drop(&mut self) {
+        let connection = match self.connection.take() {
+            Some(connection) => connection,
+            None => return,
+        };
+
+        match (self.reusable, self.pool.upgrade()) {
+            (true, Some(pool)) => pool.release(self.config_params.clone(), connection),
+            _ => close(connection),
+        }
+    }
+}
+
+fn session_key(config_params: &SSHConfigurationParams) -> String {
+    format!("{}:{}", config_params.ip, config_params.port)
+}
+
+pub(super) fn close(mut connection: NetconfConnection) {
+    if let Err(e) = connection.netconf_client.close_session() {
+        eprintln!("[Southbound] Error while closing connection... {:?}", e);
+    }
+}
@@ -1,5 +1,9 @@
-use netconf_client::netconf_client::NetconfClient;
-use std::sync::Arc;
+use super::client::{NetconfClient, NetconfClientError};
+use super::profiles::DeviceProfile;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
 
 /// this is used to specify the yang-models that have to be loaded later.
 #[derive(Debug, Clone, Copy)]
@@ -33,14 +37,153 @@ impl YangModule {
     }
 }
 
+/// a yang-model as advertised by a netconf-server in the capabilities of its <hello>-Message
+///
+/// # Example
+///
+/// "urn:ietf:params:xml:ns:yang:ietf-interfaces?module=ietf-interfaces&revision=2018-02-20&features=if-mib"
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ModuleCapability {
+    pub name: String,
+
+    #[serde(default)]
+    pub revision: Option<String>,
+
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl ModuleCapability {
+    /// parses a capability of the <hello>-Message. Returns None if the capability doesn't advertise
+    /// a yang-model, e.g. the base capabilities of netconf.
+    ///
+    /// The name and revision are used as file name of the downloaded yang-model, so capabilities
+    /// whose name isn't a yang-identifier or whose revision isn't a date are skipped as well.
+    pub fn parse(capability: &str) -> Option<Self> {
+        let capability = capability.trim().replace("&amp;", "&");
+        let (_namespace, query) = capability.split_once('?')?;
+
+        let mut name: Option<String> = None;
+        let mut revision: Option<String> = None;
+        let mut features: Vec<String> = Vec::new();
+
+        for parameter in query.split('&') {
+            match parameter.split_once('=') {
+                Some(("module", value)) => name = Some(value.to_string()),
+                Some(("revision", value)) => revision = Some(value.to_string()),
+                Some(("features", value)) => {
+                    features = value
+                        .split(',')
+                        .filter(|f| !f.is_empty())
+                        .map(|f| f.to_string())
+                        .collect()
+                }
+                _ => (),
+            }
+        }
+
+        let name = name.filter(|name| is_yang_identifier(name))?;
+        if revision
+            .as_ref()
+            .is_some_and(|revision| !is_revision_date(revision))
+        {
+            return None;
+        }
+
+        Some(Self {
+            name,
+            revision,
+            features,
+        })
+    }
+}
+
+/// checks for the grammar of a yang-identifier, [A-Za-z_][A-Za-z0-9_.-]*
+fn is_yang_identifier(name: &str) -> bool {
+    let mut characters = name.chars();
+
+    characters
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && characters.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// checks for the grammar of a revision-date, YYYY-MM-DD
+fn is_revision_date(revision: &str) -> bool {
+    let parts: Vec<&str> = revision.split('-').collect();
+
+    parts.len() == 3
+        && [4, 2, 2]
+            .iter()
+            .zip(&parts)
+            .all(|(length, part)| part.len() == *length && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl From<&YangModule> for ModuleCapability {
+    fn from(module: &YangModule) -> Self {
+        Self {
+            name: module.name.to_string(),
+            revision: module.revision.map(|r| r.to_string()),
+            features: module.features.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+}
+
 // is used to hold a established netconf_connection as well as the yang_context for parsing the
 // exchanged data.
 //
 // the xpath_dict is used as a lookuptable to find the specified field.
+/// errors while establishing a netconf-session to a bridge
+#[derive(Debug)]
+pub enum ConnectionError {
+    /// the credentials of the bridge couldn't be resolved
+    Credentials(String),
+
+    /// the bridge couldn't be connected or didn't answer in time
+    Transport(String),
+
+    /// the host key of the bridge is unknown or doesn't match the known-hosts file
+    HostKey(String),
+
+    /// a yang-model of the device profile couldn't be loaded
+    YangModule(String),
+
+    Netconf(NetconfClientError),
+}
+
+impl From<NetconfClientError> for ConnectionError {
+    fn from(error: NetconfClientError) -> Self {
+        Self::Netconf(error)
+    }
+}
+
+/// yang-contexts of the netconf-servers by their set of yang-models. Servers with the same models
+/// share one context, so it's only built once.
+pub type YangContexts = Mutex<HashMap<String, Arc<yang2::context::Context>>>;
+
 pub struct NetconfConnection {
     pub netconf_client: NetconfClient,
     pub yang_ctx: Arc<yang2::context::Context>,
     pub yang_paths: YangPaths,
+
+    /// capabilities of the <hello>-Message of the netconf-server
+    pub capabilities: Vec<String>,
+
+    /// the tcp-connection of the ssh-session
+    pub socket: TcpStream,
+}
+
+impl NetconfConnection {
+    /// checks if the netconf-server advertised the capability, independent of its version
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c.starts_with(capability))
+    }
+
+    /// a handle to shut the connection down from another thread, e.g. while it waits for a
+    /// notification. Blocking calls on the connection fail afterwards.
+    pub fn shutdown_handle(&self) -> Option<TcpStream> {
+        self.socket.try_clone().ok()
+    }
 }
 
 
@@ -55,12 +198,11 @@ pub struct YangPaths {
 
 
 impl YangPaths {
-    /// this function is used to load the paths and filters for the specified yang_modules.
-    pub fn load_paths(_yang_modules: &Vec<YangModule>) -> Self {
-        // TODO: make this function dynamically load the paths and filters based on the yang_modules
+    /// this function is used to load the paths and filters of the device profile.
+    pub fn load_paths(profile: &DeviceProfile) -> Self {
         Self {
-            filters: SearchFilters::load_br_filters(),
-            params: SwitchParameters::load_br_paths(),
+            filters: profile.filters.clone(),
+            params: profile.params.clone(),
         }
     }
 }
@@ -71,6 +213,10 @@ impl YangPaths {
 /// For different Yang-models, there might be different paths and attribute names that are used for
 /// the specific information. This struct is used as a lookup table to find the correct path and
 /// attribute name.
+///
+/// Device profiles only have to provide the paths that differ from the default ones.
+#[derive(Deserialize, Clone)]
+#[serde(default)]
 pub struct SwitchParameters {
     // ietf-interfaces
     pub interfaces: String,
@@ -85,6 +231,10 @@ pub struct SwitchParameters {
     pub operation_name: String,
     pub sgs_params_gate_states_value: String,
     pub sgs_params_time_interval_value: String,
+    pub shm_params_gate_states_value: String,
+    pub shm_params_time_interval_value: String,
+    pub srm_params_gate_states_value: String,
+    pub srm_params_time_interval_value: String,
     pub admin_control_list_length: String,
     pub admin_cycle_time_numerator: String,
     pub admin_cycle_time_denominator: String,
@@ -93,6 +243,91 @@ pub struct SwitchParameters {
     pub admin_cycle_time_extension: String,
     pub config_change: String,
     pub tick_granularity: String,
+    pub supported_list_max: String,
+    pub supported_cycle_max_numerator: String,
+    pub supported_cycle_max_denominator: String,
+    pub oper_control_list: String,
+    pub oper_cycle_time_numerator: String,
+    pub oper_cycle_time_denominator: String,
+    pub oper_base_time_seconds: String,
+    pub oper_base_time_fractional_seconds: String,
+    pub config_pending: String,
+    pub config_change_error: String,
+
+    // ieee802-dot1q-preemption
+    pub frame_preemption_parameters: String,
+    pub frame_preemption_status_by_traffic_class: String,
+
+    // ieee802-dot1q-fqtss
+    pub fqtss_bap_entry: String,
+    pub fqtss_admin_idle_slope_ms: String,
+    pub fqtss_admin_idle_slope_ls: String,
+    pub fqtss_tx_selection_algorithm_entry: String,
+    pub fqtss_tx_selection_algorithm_id: String,
+
+    // ieee802-dot1q-stream-filters-gates and ieee802-dot1q-psfp
+    pub stream_filter_by_id: String,
+    pub stream_handle: String,
+    pub stream_handle_wildcard: String,
+    pub priority_spec: String,
+    pub max_sdu_size: String,
+    pub stream_blocked_due_to_oversize_frame_enabled: String,
+    pub stream_gate_ref: String,
+    pub flow_meter_ref: String,
+    pub flow_meter_enable: String,
+    pub stream_gate_by_id: String,
+    pub stream_gate_enabled: String,
+    pub stream_gate_admin_gate_states: String,
+    pub stream_gate_admin_ipv: String,
+    pub stream_gate_control_entry_by_index: String,
+    pub stream_gate_operation_name: String,
+    pub stream_gate_state_value: String,
+    pub stream_gate_ipv_spec: String,
+    pub stream_gate_time_interval_value: String,
+    pub stream_gate_interval_octet_max: String,
+    pub stream_gate_admin_cycle_time_numerator: String,
+    pub stream_gate_admin_cycle_time_denominator: String,
+    pub stream_gate_admin_base_time_seconds: String,
+    pub stream_gate_admin_base_time_nanoseconds: String,
+    pub stream_gate_config_change: String,
+    pub flow_meter_by_id: String,
+    pub committed_information_rate: String,
+    pub committed_burst_size: String,
+    pub excess_information_rate: String,
+    pub excess_burst_size: String,
+    pub drop_on_yellow: String,
+    pub mark_all_frames_red_enable: String,
+
+    // ieee802-dot1cb-stream-identification
+    pub stream_identity_by_index: String,
+    pub stream_identity_handle: String,
+    pub stream_identity_input_port: String,
+    pub null_stream_identification: String,
+    pub ip_stream_identification: String,
+    pub destination_mac: String,
+    pub tagged: String,
+    pub vlan: String,
+    pub ip_source: String,
+    pub ip_destination: String,
+    pub dscp: String,
+    pub next_protocol: String,
+    pub source_port: String,
+    pub destination_port: String,
+
+    // ieee802-dot1cb-frer
+    pub sequence_generation_by_index: String,
+    pub sequence_recovery_by_index: String,
+    pub sequence_identification_by_port: String,
+    pub frer_stream: String,
+    pub frer_port: String,
+    pub frer_direction_out_facing: String,
+    pub algorithm: String,
+    pub history_length: String,
+    pub reset_timeout: String,
+    pub take_no_sequence: String,
+    pub individual_recovery: String,
+    pub active: String,
+    pub encapsulation: String,
 
     // ieee802-dot1ab-lldp
     pub remote_systems_data: String,
@@ -114,6 +349,19 @@ pub struct SwitchParameters {
     // ieee802-dot1q-bridge
     pub bridge_port: String,
     pub bridge_port_address: String,
+    pub bridge_port_number: String,
+    pub bridge_component: String,
+    pub filtering_entries: String,
+    pub filtering_entry: String,
+    pub vlan_registration_entries: String,
+    pub vlan_registration_entry: String,
+    pub entry_type: String,
+    pub port_map_by_ref: String,
+    pub address: String,
+    pub vids: String,
+    pub control_element: String,
+    pub registrar_admin_control: String,
+    pub vlan_transmitted: String,
 
     // ieee802-dot1q-bridge-port-delays
     pub bridge_port_delays: String,
@@ -126,9 +374,19 @@ pub struct SwitchParameters {
     pub independent_rly_delay_max: String,
     pub independent_tx_delay_min: String,
     pub independent_tx_delay_max: String,
+
+    // notifications of link changes
+    pub oper_status: String,
+    pub oper_status_interface_name: String,
 }
 
 // TODO: if other configurations are needed, you can add them here.
+impl Default for SwitchParameters {
+    fn default() -> Self {
+        Self::load_br_paths()
+    }
+}
+
 impl SwitchParameters {
     fn load_br_paths() -> Self {
         Self {
@@ -145,6 +403,10 @@ impl SwitchParameters {
             operation_name: "operation-name".to_string(),
             sgs_params_gate_states_value: "sgs-params/gate-states-value".to_string(),
             sgs_params_time_interval_value: "sgs-params/time-interval-value".to_string(),
+            shm_params_gate_states_value: "shm-params/gate-states-value".to_string(),
+            shm_params_time_interval_value: "shm-params/time-interval-value".to_string(),
+            srm_params_gate_states_value: "srm-params/gate-states-value".to_string(),
+            srm_params_time_interval_value: "srm-params/time-interval-value".to_string(),
             admin_control_list_length: "admin-control-list-length".to_string(),
             admin_cycle_time_numerator: "admin-cycle-time/numerator".to_string(),
             admin_cycle_time_denominator: "admin-cycle-time/denominator".to_string(),
@@ -153,6 +415,96 @@ impl SwitchParameters {
             admin_cycle_time_extension: "admin-cycle-time-extension".to_string(),
             config_change: "config-change".to_string(),
             tick_granularity: "tick-granularity".to_string(),
+            supported_list_max: "supported-list-max".to_string(),
+            supported_cycle_max_numerator: "supported-cycle-max/numerator".to_string(),
+            supported_cycle_max_denominator: "supported-cycle-max/denominator".to_string(),
+            oper_control_list: "oper-control-list".to_string(),
+            oper_cycle_time_numerator: "oper-cycle-time/numerator".to_string(),
+            oper_cycle_time_denominator: "oper-cycle-time/denominator".to_string(),
+            oper_base_time_seconds: "oper-base-time/seconds".to_string(),
+            oper_base_time_fractional_seconds: "oper-base-time/fractional-seconds".to_string(),
+            config_pending: "config-pending".to_string(),
+            config_change_error: "config-change-error".to_string(),
+
+            // ieee802-dot1q-preemption
+            frame_preemption_parameters: "ieee802-dot1q-preemption:frame-preemption-parameters"
+                .to_string(),
+            frame_preemption_status_by_traffic_class:
+                "frame-preemption-status-table[traffic-class='{}']/frame-preemption-status"
+                    .to_string(),
+
+            // ieee802-dot1q-fqtss
+            // the tables are keyed by the bridge component (always 1), port and traffic class
+            fqtss_bap_entry: "ieee802-dot1q-fqtss:ieee802-dot1q-fqtss/ieee8021FqtssBapTable/ieee8021FqtssBapEntry[ieee8021BridgeBaseComponentId='1'][ieee8021BridgeBasePort='{port}'][ieee8021FqtssBAPTrafficClass='{traffic_class}']".to_string(),
+            fqtss_admin_idle_slope_ms: "ieee8021FqtssAdminIdleSlopeMs".to_string(),
+            fqtss_admin_idle_slope_ls: "ieee8021FqtssAdminIdleSlopeLs".to_string(),
+            fqtss_tx_selection_algorithm_entry: "ieee802-dot1q-fqtss:ieee802-dot1q-fqtss/ieee8021FqtssTxSelectionAlgorithmTable/ieee8021FqtssTxSelectionAlgorithmEntry[ieee8021BridgeBaseComponentId='1'][ieee8021BridgeBasePort='{port}'][ieee8021FqtssTrafficClass='{traffic_class}']".to_string(),
+            fqtss_tx_selection_algorithm_id: "ieee8021FqtssTxSelectionAlgorithmID".to_string(),
+
+            // ieee802-dot1q-stream-filters-gates and ieee802-dot1q-psfp
+            // PSFP is configured per bridge component (bridge_component)
+            stream_filter_by_id: "ieee802-dot1q-stream-filters-gates:stream-filters/stream-filter-instance-table[stream-filter-instance-id='{}']".to_string(),
+            stream_handle: "stream-handle".to_string(),
+            stream_handle_wildcard: "wildcard".to_string(),
+            priority_spec: "priority-spec".to_string(),
+            max_sdu_size: "max-sdu-size".to_string(),
+            stream_blocked_due_to_oversize_frame_enabled: "stream-blocked-due-to-oversize-frame-enabled".to_string(),
+            stream_gate_ref: "stream-gate-ref".to_string(),
+            flow_meter_ref: "ieee802-dot1q-psfp:flow-meter-instance-id".to_string(),
+            flow_meter_enable: "ieee802-dot1q-psfp:flow-meter-enable".to_string(),
+            stream_gate_by_id: "ieee802-dot1q-stream-filters-gates:stream-gates/stream-gate-instance-table[stream-gate-instance-id='{}']".to_string(),
+            stream_gate_enabled: "gate-enable".to_string(),
+            stream_gate_admin_gate_states: "admin-gate-states".to_string(),
+            stream_gate_admin_ipv: "admin-ipv".to_string(),
+            stream_gate_control_entry_by_index: "ieee802-dot1q-psfp:admin-control-list/gate-control-entry[index='{}']".to_string(),
+            stream_gate_operation_name: "operation-name".to_string(),
+            stream_gate_state_value: "gate-state-value".to_string(),
+            stream_gate_ipv_spec: "ipv-spec".to_string(),
+            stream_gate_time_interval_value: "time-interval-value".to_string(),
+            stream_gate_interval_octet_max: "interval-octet-max".to_string(),
+            stream_gate_admin_cycle_time_numerator: "ieee802-dot1q-psfp:admin-cycle-time/numerator".to_string(),
+            stream_gate_admin_cycle_time_denominator: "ieee802-dot1q-psfp:admin-cycle-time/denominator".to_string(),
+            stream_gate_admin_base_time_seconds: "ieee802-dot1q-psfp:admin-base-time/seconds".to_string(),
+            stream_gate_admin_base_time_nanoseconds: "ieee802-dot1q-psfp:admin-base-time/nanoseconds".to_string(),
+            stream_gate_config_change: "ieee802-dot1q-psfp:config-change".to_string(),
+            flow_meter_by_id: "ieee802-dot1q-psfp:flow-meters/flow-meter-instance-table[flow-meter-instance-id='{}']".to_string(),
+            committed_information_rate: "committed-information-rate".to_string(),
+            committed_burst_size: "committed-burst-size".to_string(),
+            excess_information_rate: "excess-information-rate".to_string(),
+            excess_burst_size: "excess-burst-size".to_string(),
+            drop_on_yellow: "drop-on-yellow".to_string(),
+            mark_all_frames_red_enable: "mark-all-frames-red-enable".to_string(),
+
+            // ieee802-dot1cb-stream-identification
+            stream_identity_by_index: "ieee802-dot1cb-stream-identification:stream-identity[index='{}']".to_string(),
+            stream_identity_handle: "handle".to_string(),
+            stream_identity_input_port: "in-facing/input-port".to_string(),
+            null_stream_identification: "null-stream-identification".to_string(),
+            ip_stream_identification: "ip-stream-identification".to_string(),
+            destination_mac: "destination-mac".to_string(),
+            tagged: "tagged".to_string(),
+            vlan: "vlan".to_string(),
+            ip_source: "ip-source".to_string(),
+            ip_destination: "ip-destination".to_string(),
+            dscp: "dscp".to_string(),
+            next_protocol: "next-protocol".to_string(),
+            source_port: "source-port".to_string(),
+            destination_port: "destination-port".to_string(),
+
+            // ieee802-dot1cb-frer
+            sequence_generation_by_index: "ieee802-dot1cb-frer:frer/sequence-generation[index='{}']".to_string(),
+            sequence_recovery_by_index: "ieee802-dot1cb-frer:frer/sequence-recovery[index='{}']".to_string(),
+            sequence_identification_by_port: "ieee802-dot1cb-frer:frer/sequence-identification[port='{port}'][direction-out-facing='{direction}']".to_string(),
+            frer_stream: "stream".to_string(),
+            frer_port: "port".to_string(),
+            frer_direction_out_facing: "direction-out-facing".to_string(),
+            algorithm: "algorithm".to_string(),
+            history_length: "history-length".to_string(),
+            reset_timeout: "reset-timeout".to_string(),
+            take_no_sequence: "take-no-sequence".to_string(),
+            individual_recovery: "individual-recovery".to_string(),
+            active: "active".to_string(),
+            encapsulation: "encapsulation".to_string(),
 
             // ieee802-dot1ab-lldp
             remote_systems_data: "ieee802-dot1ab-lldp:lldp/port/remote-systems-data".to_string(),
@@ -174,6 +526,21 @@ impl SwitchParameters {
             // ieee802-dot1q-bridge
             bridge_port: "ieee802-dot1q-bridge:bridge/bridge-ports".to_string(),
             bridge_port_address: "bridge-port/address".to_string(),
+            bridge_port_number: "bridge-port/port-number".to_string(),
+            // the filtering database belongs to the bridge component. Its names aren't part of the
+            // interfaces, so the default names of the bridge are used.
+            bridge_component: "ieee802-dot1q-bridge:bridges/bridge[name='br0']/component[name='br0']".to_string(),
+            filtering_entries: "filtering-database/filtering-entry".to_string(),
+            filtering_entry: "filtering-database/filtering-entry[database-id='1'][vids='{vid}'][address='{address}']".to_string(),
+            vlan_registration_entries: "filtering-database/vlan-registration-entry".to_string(),
+            vlan_registration_entry: "filtering-database/vlan-registration-entry[database-id='1'][vids='{vid}']".to_string(),
+            entry_type: "entry-type".to_string(),
+            port_map_by_ref: "port-map[port-ref='{}']".to_string(),
+            address: "address".to_string(),
+            vids: "vids".to_string(),
+            control_element: "static-filtering-entries/control-element".to_string(),
+            registrar_admin_control: "static-vlan-registration-entries/registrar-admin-control".to_string(),
+            vlan_transmitted: "static-vlan-registration-entries/vlan-transmitted".to_string(),
 
             // ieee802-dot1q-bridge-port-delays
             bridge_port_delays: "bridge-port/bridge-port-delays".to_string(),
@@ -186,18 +553,31 @@ impl SwitchParameters {
             independent_rly_delay_max: "independentRlyDelayMax".to_string(),
             independent_tx_delay_min: "independentTxDelayMin".to_string(),
             independent_tx_delay_max: "independentTxDelayMax".to_string(),
+
+            // notifications of link changes
+            oper_status: "oper-status".to_string(),
+            oper_status_interface_name: "name".to_string(),
         }
     }
 }
 
 /// This struct is used to hold all filters that are used to search for specific information in the
 /// yang-models.
+#[derive(Deserialize, Clone)]
+#[serde(default)]
 pub struct SearchFilters {
     pub gate_parameters: String,
     pub gate_parameters_and_bridge_ports: String,
+    pub gate_parameters_and_filtering_database: String,
     pub remote_systems_data: String,
 }
 
+impl Default for SearchFilters {
+    fn default() -> Self {
+        Self::load_br_filters()
+    }
+}
+
 // TODO: if other filters are needed, you can add them here.
 impl SearchFilters {
     fn load_br_filters() -> Self {
@@ -220,6 +600,22 @@ impl SearchFilters {
                 </interfaces>"
             .to_string(),
 
+            gate_parameters_and_filtering_database:
+                "<interfaces xmlns=\"urn:ietf:params:xml:ns:yang:ietf-interfaces\">
+                    <interface>
+                        <gate-parameters xmlns=\"urn:ieee:std:802.1Q:yang:ieee802-dot1q-sched\">
+                        </gate-parameters>
+                    </interface>
+                </interfaces>
+                <bridges xmlns=\"urn:ieee:std:802.1Q:yang:ieee802-dot1q-bridge\">
+                    <bridge>
+                        <component>
+                            <filtering-database></filtering-database>
+                        </component>
+                    </bridge>
+                </bridges>"
+            .to_string(),
+
             remote_systems_data:
                 "<lldp xmlns=\"urn:ieee:std:802.1AB:yang:ieee802-dot1ab-lldp\">
                     <port>
@@ -233,9 +629,8 @@ impl SearchFilters {
 }
 
 
-/// all yang-models to load for the B&R Switch have to be included here.
-/// TODO: if this should be dynamic, you can add a function to load the modules from a file or
-/// something else
+/// all yang-models that are needed for the default paths and filters of the CNC have to be included
+/// here. They are loaded in addition to the models advertised by the netconf-server.
 pub const YANG_MODULES: &'static [YangModule] = &[
     YangModule::new_with_features("ietf-interfaces", "2018-02-20", &["if-mib"]),
     YangModule::new("ietf-yang-types", "2013-07-15"),
@@ -244,9 +639,36 @@ pub const YANG_MODULES: &'static [YangModule] = &[
     YangModule::new("ieee802-dot1q-bridge", "2020-11-07"),
     YangModule::new("ieee802-dot1q-types", "2020-10-24"),
     YangModule::new("ieee802-dot1q-bridge-delays", "2021-11-23"),
-    YangModule::new("ieee802-dot1q-preemption", "2018-09-10"),
+    YangModule::new_with_features("ieee802-dot1q-preemption", "2018-09-10", &["frame-preemption"]),
     YangModule::new_with_features("ieee802-dot1q-sched", "2018-09-11", &["scheduled-traffic"]),
     YangModule::new("ietf-routing", "2018-03-13"),
     YangModule::new("ieee802-dot1ab-types", "2018-10-03"),
     YangModule::new("ieee802-dot1ab-lldp", "2018-11-13"),
 ];
+
+/// yang-models that are only needed for optional features of the bridges. If one of them can't be
+/// loaded, the feature isn't configured but the connection still works.
+///
+/// ieee802-dot1q-fqtss is a translated SMIv2-MIB and additionally needs SNMPv2-TC in the search
+/// directory. It can be downloaded with <get-schema> from bridges that support FQTSS.
+///
+/// ieee802-dot1q-psfp additionally needs ieee802-dot1q-stream-filters-gates in the search
+/// directory, which can be downloaded the same way from bridges that support PSFP.
+///
+/// The IEEE 802.1CB models aren't provided by the used B&R switch, only its
+/// ieee802-dot1cb-stream-identification-params. They are loaded in the latest revision that is
+/// found in the search directory.
+pub const OPTIONAL_YANG_MODULES: &'static [YangModule] = &[
+    YangModule::new("ieee802-dot1q-fqtss", "2014-12-15"),
+    YangModule::new_with_features("ieee802-dot1q-psfp", "2020-07-08", &["psfp"]),
+    YangModule {
+        name: "ieee802-dot1cb-stream-identification",
+        revision: None,
+        features: &[],
+    },
+    YangModule {
+        name: "ieee802-dot1cb-frer",
+        revision: None,
+        features: &[],
+    },
+];
@@ -0,0 +1,147 @@
+use crate::cnc::types::sched_types::{
+    ConfigurableGateParameterTableEntry, GateParameterTableEntry, PtpTimeGrouping, RationalGrouping,
+};
+
+/// the result of comparing the deployed gate-parameters of a port with the operational ones
+#[derive(Debug, Clone, PartialEq)]
+pub enum GateVerification {
+    /// the operational values are the deployed ones
+    Applied,
+
+    /// the bridge hasn't reached the config-change-time yet
+    Pending,
+
+    /// the configuration isn't in effect
+    Mismatch(String),
+}
+
+/// Compares the deployed gate-parameters with the operational ones read back from the bridge. The
+/// operational values are only compared if the gates are enabled.
+pub fn verify_gate_parameters(
+    config: &ConfigurableGateParameterTableEntry,
+    oper: &GateParameterTableEntry,
+) -> GateVerification {
+    if oper.gate_enable != config.gate_enable {
+        return GateVerification::Mismatch(format!(
+            "gate-enabled is {}, expected {}",
+            oper.gate_enable, config.gate_enable
+        ));
+    }
+
+    if !config.gate_enable {
+        return GateVerification::Applied;
+    }
+
+    if oper.config_pending {
+        return GateVerification::Pending;
+    }
+
+    if oper.oper_control_list != config.admin_control_list {
+        return GateVerification::Mismatch(format!(
+            "oper-control-list has {} entries that differ from the {} deployed ones",
+            oper.oper_control_list.len(),
+            config.admin_control_list.len()
+        ));
+    }
+
+    if !is_same_rational(oper.oper_cycle_time, config.admin_cycle_time) {
+        return GateVerification::Mismatch(format!(
+            "oper-cycle-time is {:?}, expected {:?}",
+            oper.oper_cycle_time, config.admin_cycle_time
+        ));
+    }
+
+    if !is_cycle_start(oper.oper_base_time, config) {
+        return GateVerification::Mismatch(format!(
+            "oper-base-time is {:?}, expected {:?}",
+            oper.oper_base_time, config.admin_base_time
+        ));
+    }
+
+    GateVerification::Applied
+}
+
+/// Compares the stored gate-parameters of a port with the ones configured on the bridge and
+/// describes every difference. Empty if there is no drift. If the gates are disabled in both, the
+/// other parameters aren't in effect and aren't compared.
+pub fn diff_gate_parameters(
+    expected: &ConfigurableGateParameterTableEntry,
+    actual: &ConfigurableGateParameterTableEntry,
+) -> Vec<String> {
+    let mut differences: Vec<String> = Vec::new();
+
+    if actual.gate_enable != expected.gate_enable {
+        differences.push(format!(
+            "gate-enabled is {}, expected {}",
+            actual.gate_enable, expected.gate_enable
+        ));
+    }
+
+    if !actual.gate_enable && !expected.gate_enable {
+        return differences;
+    }
+
+    if actual.admin_gate_states != expected.admin_gate_states {
+        differences.push(format!(
+            "admin-gate-states is {}, expected {}",
+            actual.admin_gate_states, expected.admin_gate_states
+        ));
+    }
+
+    if actual.admin_control_list != expected.admin_control_list {
+        differences.push(format!(
+            "admin-control-list has {} entries that differ from the {} stored ones",
+            actual.admin_control_list.len(),
+            expected.admin_control_list.len()
+        ));
+    }
+
+    if !is_same_rational(actual.admin_cycle_time, expected.admin_cycle_time) {
+        differences.push(format!(
+            "admin-cycle-time is {:?}, expected {:?}",
+            actual.admin_cycle_time, expected.admin_cycle_time
+        ));
+    }
+
+    if actual.admin_cycle_time_extension != expected.admin_cycle_time_extension {
+        differences.push(format!(
+            "admin-cycle-time-extension is {}, expected {}",
+            actual.admin_cycle_time_extension, expected.admin_cycle_time_extension
+        ));
+    }
+
+    if actual.admin_base_time != expected.admin_base_time {
+        differences.push(format!(
+            "admin-base-time is {:?}, expected {:?}",
+            actual.admin_base_time, expected.admin_base_time
+        ));
+    }
+
+    differences
+}
+
+/// Checks if the base-time is the admin-base-time or a later start of the cycle. If the
+/// admin-base-time is in the past, e.g. (0, 0) as set by the scheduler, the bridge moves the
+/// oper-base-time forward to the next start of the cycle (802.1Q 8.6.9.1.1).
+fn is_cycle_start(
+    base_time: PtpTimeGrouping,
+    config: &ConfigurableGateParameterTableEntry,
+) -> bool {
+    let (numerator, denominator) = config.admin_cycle_time;
+    if numerator <= 0 || denominator <= 0 {
+        return base_time == config.admin_base_time;
+    }
+
+    let base_time = base_time.0 as i128 * 1_000_000_000 + base_time.1 as i128;
+    let admin_base_time =
+        config.admin_base_time.0 as i128 * 1_000_000_000 + config.admin_base_time.1 as i128;
+
+    // (base_time - admin_base_time) / (numerator / denominator * 10^9) has to be a whole number
+    let elapsed = base_time - admin_base_time;
+    elapsed >= 0 && (elapsed * denominator as i128) % (numerator as i128 * 1_000_000_000) == 0
+}
+
+/// compares rationals independent of their representation, e.g. 1/1000 and 1000/1000000
+fn is_same_rational(a: RationalGrouping, b: RationalGrouping) -> bool {
+    a.0 as i64 * b.1 as i64 == b.0 as i64 * a.1 as i64 && (a.1 == 0) == (b.1 == 0)
+}
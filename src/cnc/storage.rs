@@ -1,9 +1,10 @@
+use super::types::allocation::DomainResources;
 use super::types::scheduling::Config;
 use super::types::uni_types::{self, compute_streams, Cuc, Stream, StreamStatus};
 use super::types::FailedInterfaces;
 use super::{Cnc, CNC_NOT_PRESENT};
 use rand::Rng;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Error, Read, Write};
 use std::sync::{RwLock, Weak};
@@ -20,6 +21,9 @@ pub trait StorageAdapterInterface {
         domains: Vec<compute_streams::Domain>,
     ) -> Vec<uni_types::Domain>;
 
+    /// This will return all domains with all of their cucs and streams.
+    fn get_all_domains(&self) -> Vec<uni_types::Domain>;
+
     /// This will return all streams of the provided domain.
     fn get_streams_in_domain(&self, domain: compute_streams::Domain) -> Vec<uni_types::Domain>;
 
@@ -59,8 +63,8 @@ pub trait StorageAdapterInterface {
     /// This will return all configs in the storage.
     fn get_all_configs(&self) -> Vec<Config>;
 
-    /// This will return the requested config. If it is not present, this will return None
-    fn get_config(&self, node_id: u32) -> Option<Config>;
+    /// This will return the config of the port of the node. If it is not present, this will return None
+    fn get_config(&self, node_id: u32, port_name: &str) -> Option<Config>;
 
     /// This will insert or replace the provided config in the storage.
     fn set_config(&self, config: Config);
@@ -68,6 +72,26 @@ pub trait StorageAdapterInterface {
     /// This will insert or replace the provided configs in the storage.
     fn set_configs(&self, configs: &Vec<Config>);
 
+    /// This will remove the configs of the ports of the provided configs from the storage.
+    fn remove_configs(&self, configs: &Vec<Config>);
+
+    /// This will return the resources that the CNC assigned to the streams of the provided domain.
+    /// If the domain has none, this will return an empty map.
+    fn get_stream_resources(&self, domain_id: &String) -> DomainResources;
+
+    /// This will replace the resources of the streams of the provided domain.
+    fn set_stream_resources(&self, domain_id: &String, resources: &DomainResources);
+
+    /// This will return the stream handles that the CNC assigned to the streams by stream_id.
+    fn get_stream_handles(&self) -> HashMap<String, u32>;
+
+    /// This will replace the stream handles of all streams.
+    fn set_stream_handles(&self, handles: &HashMap<String, u32>);
+
+    /// This will release the resources and stream handles of the provided streams of the domain,
+    /// so they can be assigned to other streams.
+    fn release_stream_resources(&self, domain_id: &String, stream_ids: &Vec<String>);
+
     /// In the fully centralized model, this should not be used.
     /// The CUC should take care of that because it nows the MAC-Addresses of its listeners.
     /// This implementation returns a free id but with MAC-Address 0
@@ -87,11 +111,24 @@ pub trait StorageAdapterInterface {
     fn set_cnc_ref(&mut self, cnc: Weak<Cnc>);
 }
 
+/// the key of the config of a port in the config store, e.g. "1:sw0p2"
+fn config_key(node_id: u32, port_name: &str) -> String {
+    format!("{node_id}:{port_name}")
+}
+
 pub struct FileStorage {
     domains_path: &'static str,
     configs_path: &'static str,
+    resources_path: &'static str,
+    handles_path: &'static str,
     domains: RwLock<Vec<uni_types::Domain>>,
-    configs: RwLock<HashMap<u32, Config>>,
+
+    /// configs by the node_id and name of their port, see config_key(...)
+    configs: RwLock<HashMap<String, Config>>,
+    resources: RwLock<HashMap<String, DomainResources>>,
+
+    /// stream handles of the PSFP and FRER functions by stream_id
+    handles: RwLock<HashMap<String, u32>>,
     cnc: Weak<Cnc>,
 }
 
@@ -100,8 +137,12 @@ impl FileStorage {
         Self {
             domains_path: "domain_storage.json",
             configs_path: "config_storage.json",
+            resources_path: "resource_storage.json",
+            handles_path: "handle_storage.json",
             domains: RwLock::new(Vec::new()),
             configs: RwLock::new(HashMap::new()),
+            resources: RwLock::new(HashMap::new()),
+            handles: RwLock::new(HashMap::new()),
             cnc: Weak::default(),
         }
     }
@@ -132,6 +173,32 @@ impl FileStorage {
         }
     }
 
+    fn save_resources(&self) {
+        match serde_json::to_string(&self.resources) {
+            Err(_) => panic!("[Storage] couldn't parse store to json..."),
+            Ok(s) => {
+                let result: Result<(), Error> = Self::write_to_file(self.resources_path, s.clone());
+                if let Err(e) = result {
+                    println!("[Storage] error while creating file, {e:?}");
+                    panic!("[Storage] not able to function without a file");
+                }
+            }
+        }
+    }
+
+    fn save_handles(&self) {
+        match serde_json::to_string(&self.handles) {
+            Err(_) => panic!("[Storage] couldn't parse store to json..."),
+            Ok(s) => {
+                let result: Result<(), Error> = Self::write_to_file(self.handles_path, s.clone());
+                if let Err(e) = result {
+                    println!("[Storage] error while creating file, {e:?}");
+                    panic!("[Storage] not able to function without a file");
+                }
+            }
+        }
+    }
+
     fn write_to_file(file_path: &str, content: String) -> Result<(), Error> {
         let mut file: File = File::create(file_path)?;
         file.write_all(content.as_bytes())?;
@@ -158,14 +225,41 @@ impl FileStorage {
 
     fn try_load_configs(&self) -> Result<(), Error> {
         let content: String = Self::read_from_file(self.configs_path)?;
-        let configs: HashMap<u32, Config> = serde_json::from_str::<HashMap<u32, Config>>(&content)?;
+        let configs: HashMap<String, Config> =
+            serde_json::from_str::<HashMap<String, Config>>(&content)?;
         let mut config_lock = self.configs.write().unwrap();
-        *config_lock = configs;
+
+        // older files are keyed by the node_id only
+        *config_lock = configs
+            .into_values()
+            .map(|config| (config_key(config.node_id, &config.port.name), config))
+            .collect();
         drop(config_lock);
         println!("[Storage] Successfully loaded configurations");
         return Result::Ok(());
     }
 
+    fn try_load_resources(&self) -> Result<(), Error> {
+        let content: String = Self::read_from_file(self.resources_path)?;
+        let resources: HashMap<String, DomainResources> =
+            serde_json::from_str::<HashMap<String, DomainResources>>(&content)?;
+        let mut resources_lock = self.resources.write().unwrap();
+        *resources_lock = resources;
+        drop(resources_lock);
+        println!("[Storage] Successfully loaded stream resources");
+        return Result::Ok(());
+    }
+
+    fn try_load_handles(&self) -> Result<(), Error> {
+        let content: String = Self::read_from_file(self.handles_path)?;
+        let handles: HashMap<String, u32> = serde_json::from_str::<HashMap<String, u32>>(&content)?;
+        let mut handles_lock = self.handles.write().unwrap();
+        *handles_lock = handles;
+        drop(handles_lock);
+        println!("[Storage] Successfully loaded stream handles");
+        return Result::Ok(());
+    }
+
     fn random_stream_id() -> String {
         let mut rng: rand::prelude::ThreadRng = rand::thread_rng();
         let a: u8 = rng.gen_range(0..255);
@@ -206,6 +300,24 @@ impl StorageAdapterInterface for FileStorage {
 
             self.save_configs();
         }
+
+        if self.try_load_resources().is_err() {
+            // no stream resources could be loaded
+            let mut resources_lock = self.resources.write().unwrap();
+            *resources_lock = HashMap::new();
+            drop(resources_lock);
+
+            self.save_resources();
+        }
+
+        if self.try_load_handles().is_err() {
+            // no stream handles could be loaded
+            let mut handles_lock = self.handles.write().unwrap();
+            *handles_lock = HashMap::new();
+            drop(handles_lock);
+
+            self.save_handles();
+        }
     }
 
     /// remove all streams from a given cuc in the local cnc.domain
@@ -221,10 +333,13 @@ impl StorageAdapterInterface for FileStorage {
             let cuc = domain.cuc.iter_mut().find(|c| &c.cuc_id == cuc_id);
 
             if let Some(cuc) = cuc {
+                let stream_ids: Vec<String> =
+                    cuc.stream.iter().map(|s| s.stream_id.clone()).collect();
                 cuc.stream.clear();
                 drop(domain_lock);
 
                 self.save_domains();
+                self.release_stream_resources(cnc_domain_name, &stream_ids);
             }
         }
     }
@@ -250,12 +365,18 @@ impl StorageAdapterInterface for FileStorage {
 
         if let Some(index) = cuc.stream.iter().position(|s| s.stream_id == stream_id) {
             domain_lock[0].cuc[0].stream.remove(index);
+            let domain_id = domain_lock[0].domain_id.clone();
             drop(domain_lock);
 
             self.save_domains();
+            self.release_stream_resources(&domain_id, &vec![stream_id]);
         }
     }
 
+    fn get_all_domains(&self) -> Vec<uni_types::Domain> {
+        self.domains.read().unwrap().clone()
+    }
+
     fn get_streams_in_domain(&self, get_domain: compute_streams::Domain) -> Vec<uni_types::Domain> {
         let domain_lock = self.domains.write().unwrap();
         let mut result: Vec<uni_types::Domain> = Vec::new();
@@ -367,11 +488,11 @@ impl StorageAdapterInterface for FileStorage {
         result
     }
 
-    /// returnes the requested config. If it is not present, this will return None
-    fn get_config(&self, node_id: u32) -> Option<Config> {
+    /// returnes the config of the port. If it is not present, this will return None
+    fn get_config(&self, node_id: u32, port_name: &str) -> Option<Config> {
         let config_lock = self.configs.write().unwrap();
 
-        return match config_lock.get(&node_id) {
+        return match config_lock.get(&config_key(node_id, port_name)) {
             None => None,
             Some(config) => Some(config.clone()),
         };
@@ -381,7 +502,7 @@ impl StorageAdapterInterface for FileStorage {
     fn set_config(&self, config: Config) {
         let mut config_lock = self.configs.write().unwrap();
 
-        config_lock.insert(config.node_id, config);
+        config_lock.insert(config_key(config.node_id, &config.port.name), config);
 
         drop(config_lock);
         self.save_configs();
@@ -393,13 +514,81 @@ impl StorageAdapterInterface for FileStorage {
 
         for config in configs.iter() {
             let config = config.clone();
-            config_lock.insert(config.node_id, config);
+            config_lock.insert(config_key(config.node_id, &config.port.name), config);
+        }
+
+        drop(config_lock);
+        self.save_configs();
+    }
+
+    /// removes the stored configs of the ports
+    fn remove_configs(&self, configs: &Vec<Config>) {
+        let mut config_lock = self.configs.write().unwrap();
+
+        for config in configs.iter() {
+            config_lock.remove(&config_key(config.node_id, &config.port.name));
         }
 
         drop(config_lock);
         self.save_configs();
     }
 
+    /// returnes the resources of the streams of the domain
+    fn get_stream_resources(&self, domain_id: &String) -> DomainResources {
+        let resources_lock = self.resources.read().unwrap();
+
+        return match resources_lock.get(domain_id) {
+            None => DomainResources::new(),
+            Some(resources) => resources.clone(),
+        };
+    }
+
+    /// stores the provided resources of the domain
+    fn set_stream_resources(&self, domain_id: &String, resources: &DomainResources) {
+        let mut resources_lock = self.resources.write().unwrap();
+
+        resources_lock.insert(domain_id.clone(), resources.clone());
+
+        drop(resources_lock);
+        self.save_resources();
+    }
+
+    fn get_stream_handles(&self) -> HashMap<String, u32> {
+        self.handles.read().unwrap().clone()
+    }
+
+    /// stores the provided stream handles
+    fn set_stream_handles(&self, handles: &HashMap<String, u32>) {
+        let mut handles_lock = self.handles.write().unwrap();
+
+        *handles_lock = handles.clone();
+
+        drop(handles_lock);
+        self.save_handles();
+    }
+
+    /// releases the resources and stream handles of the streams
+    fn release_stream_resources(&self, domain_id: &String, stream_ids: &Vec<String>) {
+        let mut resources_lock = self.resources.write().unwrap();
+
+        if let Some(domain_resources) = resources_lock.get_mut(domain_id) {
+            for stream_id in stream_ids.iter() {
+                domain_resources.remove(stream_id);
+            }
+        }
+
+        drop(resources_lock);
+        self.save_resources();
+
+        let mut handles_lock = self.handles.write().unwrap();
+        for stream_id in stream_ids.iter() {
+            handles_lock.remove(stream_id);
+        }
+
+        drop(handles_lock);
+        self.save_handles();
+    }
+
     fn get_streams_in_domains(
         &self,
         domains: Vec<compute_streams::Domain>,
@@ -484,14 +673,14 @@ impl StorageAdapterInterface for FileStorage {
         domains: &Vec<uni_types::Domain>,
         failed_interfaces: &FailedInterfaces,
     ) {
-        // gets all streams that failed
-        let failed_stream_ids: HashSet<String> =
+        // gets all streams that failed with their failure-code
+        let failed_stream_ids: HashMap<String, u32> =
             failed_interfaces
                 .interfaces
                 .iter()
-                .fold(HashSet::new(), |mut acc, fi| {
+                .fold(HashMap::new(), |mut acc, fi| {
                     fi.affected_streams.iter().for_each(|id| {
-                        acc.insert(id.clone());
+                        acc.insert(id.clone(), fi.failure_code);
                     });
                     acc
                 });
@@ -507,10 +696,17 @@ impl StorageAdapterInterface for FileStorage {
                                 for change_stream in change_cuc.stream.iter() {
                                     for stream in cuc.stream.iter_mut() {
                                         if stream.stream_id == change_stream.stream_id {
-                                            if failed_stream_ids.get(&stream.stream_id).is_none() {
-                                                stream.stream_status = StreamStatus::Configured;
-                                            } else {
-                                                stream.stream_status = StreamStatus::Modified;
+                                            match failed_stream_ids.get(&stream.stream_id) {
+                                                None => {
+                                                    stream.stream_status = StreamStatus::Configured
+                                                }
+                                                Some(failure_code) => {
+                                                    stream.stream_status = StreamStatus::Modified;
+                                                    stream
+                                                        .group_status_stream
+                                                        .status_info
+                                                        .failure_code = *failure_code as i32;
+                                                }
                                             }
                                         }
                                     }
@@ -0,0 +1,358 @@
+use super::path_computation::{PathComputation, PathMetric};
+use super::{canonical_mac, TopologyAdapterInterface, TopologyControllerInterface};
+use crate::cnc::types::lldp_types::RemoteSystemsData;
+use crate::cnc::types::topology::{
+    Connection, ConnectionInterface, NodeInformation, NodeType, Port, SSHConfigurationParams,
+    Topology,
+};
+use crate::cnc::{Cnc, SouthboundRef};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::thread;
+use std::time::Duration;
+
+/// system capabilities (ieee802-dot1ab-lldp system-capabilities-map) of a station that forwards frames
+const BRIDGE_CAPABILITIES: [&str; 3] = ["mac-bridge", "c-vlan-component", "s-vlan-component"];
+
+/// # LLDP Topology
+///
+/// Discovers the topology by walking the LLDP neighbours of all bridges. The discovery starts at
+/// the provided seed bridges. Further bridges are reached over the management address they
+/// advertise and the credentials of the bridge they were found on.
+///
+/// Neighbours without bridge capabilities are added as end stations.
+///
+/// The discovery is repeated periodically. If the topology changed, the CNC gets notified.
+pub struct LldpTopology {
+    discovery: Arc<LldpDiscovery>,
+    cnc: Weak<Cnc>,
+}
+
+struct LldpDiscovery {
+    seeds: Vec<SSHConfigurationParams>,
+
+    /// this has to be a separate instance. The southbound of the CNC can't be shared.
+    southbound: SouthboundRef,
+    refresh_interval: Duration,
+
+    /// metric of the paths in the discovered topology
+    metric: PathMetric,
+    topology: RwLock<Topology>,
+
+    /// assigned node_ids. These stay the same over all refreshes so configs in the storage stay valid.
+    node_ids: Mutex<HashMap<String, u32>>,
+}
+
+/// a LLDP neighbour of a bridge port
+struct Link {
+    node_id: u32,
+    port_name: String,
+    neighbour_key: String,
+    neighbour: RemoteSystemsData,
+}
+
+impl LldpTopology {
+    pub fn new(
+        seeds: Vec<SSHConfigurationParams>,
+        southbound: SouthboundRef,
+        refresh_interval: Duration,
+    ) -> Self {
+        Self::new_with_metric(seeds, southbound, refresh_interval, PathMetric::HopCount)
+    }
+
+    /// computes the paths of the discovered topology with the provided metric
+    pub fn new_with_metric(
+        seeds: Vec<SSHConfigurationParams>,
+        southbound: SouthboundRef,
+        refresh_interval: Duration,
+        metric: PathMetric,
+    ) -> Self {
+        Self {
+            discovery: Arc::new(LldpDiscovery {
+                seeds,
+                southbound,
+                refresh_interval,
+                metric,
+                topology: RwLock::new(Topology {
+                    nodes: Vec::new(),
+                    connections: Vec::new(),
+                    paths: None,
+                }),
+                node_ids: Mutex::new(HashMap::new()),
+            }),
+            cnc: Weak::default(),
+        }
+    }
+
+    /// discovers the topology once. Returns true if the topology changed.
+    pub fn refresh(&self) -> bool {
+        self.discovery.refresh()
+    }
+}
+
+impl LldpDiscovery {
+    fn refresh(&self) -> bool {
+        let topology = self.discover();
+        let mut current = self.topology.write().unwrap();
+
+        if current.diff(&topology).is_empty() {
+            return false;
+        }
+
+        println!(
+            "[Topology] discovered {} nodes and {} connections",
+            topology.nodes.len(),
+            topology.connections.len()
+        );
+        *current = topology;
+        true
+    }
+
+    /// walks all reachable bridges starting at the seeds
+    fn discover(&self) -> Topology {
+        let mut nodes: Vec<NodeInformation> = Vec::new();
+        let mut links: Vec<Link> = Vec::new();
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<SSHConfigurationParams> = self.seeds.iter().cloned().collect();
+
+        while let Some(config_params) = queue.pop_front() {
+            if !visited.insert(config_params.ip.clone()) {
+                continue;
+            }
+
+            let ports = self
+                .southbound
+                .retrieve_station_capibilities(config_params.clone());
+            if ports.is_empty() {
+                eprintln!(
+                    "[Topology] bridge {} is not reachable, skipping it...",
+                    config_params.ip
+                );
+                continue;
+            }
+
+            let node_id = self.node_id(&config_params.ip);
+            for neighbour in self.southbound.retrieve_lldp(config_params.clone()) {
+                let neighbour_key = if is_bridge(&neighbour) {
+                    match management_ip(&neighbour) {
+                        Some(ip) => {
+                            queue.push_back(SSHConfigurationParams {
+                                ip: ip.clone(),
+                                // the neighbour might be of another vendor and use other
+                                // credentials
+                                credentials: None,
+                                profile: None,
+                                ..config_params.clone()
+                            });
+                            ip
+                        }
+                        None => {
+                            eprintln!(
+                                "[Topology] bridge {} advertises no management address, skipping it...",
+                                neighbour.system_name
+                            );
+                            continue;
+                        }
+                    }
+                } else {
+                    canonical_mac(&neighbour.chassis_id)
+                };
+
+                links.push(Link {
+                    node_id,
+                    port_name: neighbour.local_port_name.clone(),
+                    neighbour_key,
+                    neighbour,
+                });
+            }
+
+            nodes.push(NodeInformation {
+                id: node_id,
+                mac_addresses_interfaces: ports
+                    .iter()
+                    .map(|p| canonical_mac(&p.mac_address))
+                    .collect(),
+                endstation: NodeType::Bridge,
+                configuration_params: Some(config_params),
+                ports,
+            });
+        }
+
+        let mut connections: Vec<Connection> = Vec::new();
+        for link in links {
+            let neighbour_id = self.node_id(&link.neighbour_key);
+
+            let neighbour_port_name = match nodes.iter().find(|n| n.id == neighbour_id) {
+                // the bridge wasnt reachable
+                None if is_bridge(&link.neighbour) => continue,
+
+                // the link of the end station is only seen from the bridge
+                None => {
+                    let end_station = end_station_from_lldp(neighbour_id, &link.neighbour);
+                    let port_name = end_station.ports[0].name.clone();
+                    nodes.push(end_station);
+                    port_name
+                }
+                Some(node) if node.endstation == NodeType::EndStation => node.ports[0].name.clone(),
+                Some(node) => remote_port_name(node, &link.neighbour),
+            };
+
+            let connection = Connection {
+                id: connections.len() as u32,
+                a: ConnectionInterface {
+                    node_id: link.node_id,
+                    port_name: link.port_name,
+                },
+                b: ConnectionInterface {
+                    node_id: neighbour_id,
+                    port_name: neighbour_port_name,
+                },
+            };
+
+            // links between bridges are seen from both sides
+            if !connections.iter().any(|c| c.connects_same(&connection)) {
+                connections.push(connection);
+            }
+        }
+
+        let mut topology = Topology {
+            nodes,
+            connections,
+            paths: None,
+        };
+
+        let paths = PathComputation::new(&topology, self.metric).compute_all_paths();
+        topology.paths = Some(paths);
+        topology
+    }
+
+    /// returns the node_id of the node with the provided management ip or chassis-id
+    fn node_id(&self, key: &str) -> u32 {
+        let mut node_ids = self.node_ids.lock().unwrap();
+        let next_id = node_ids.len() as u32 + 1;
+        *node_ids.entry(key.to_string()).or_insert(next_id)
+    }
+}
+
+impl TopologyAdapterInterface for LldpTopology {
+    fn get_topology(&self) -> Topology {
+        return self.discovery.topology.read().unwrap().clone();
+    }
+
+    fn get_node_information(&self, id: u32) -> Option<NodeInformation> {
+        let topology = self.discovery.topology.read().unwrap();
+        return topology.nodes.iter().find(|x| x.id == id).cloned();
+    }
+
+    fn get_path_metric(&self) -> PathMetric {
+        self.discovery.metric
+    }
+
+    fn run(&self) {
+        let discovery = self.discovery.clone();
+        let cnc = self.cnc.clone();
+
+        thread::spawn(move || loop {
+            let changed = discovery.refresh();
+
+            // the cnc stopped
+            let cnc = match cnc.upgrade() {
+                Some(cnc) => cnc,
+                None => break,
+            };
+
+            if changed {
+                cnc.notify_topology_changed();
+            }
+            drop(cnc);
+
+            thread::sleep(discovery.refresh_interval);
+        });
+    }
+
+    fn set_cnc_ref(&mut self, cnc: Weak<Cnc>) {
+        self.cnc = cnc;
+    }
+}
+
+fn is_bridge(neighbour: &RemoteSystemsData) -> bool {
+    neighbour
+        .system_capabilities_enabled
+        .split_whitespace()
+        .any(|c| BRIDGE_CAPABILITIES.contains(&c))
+}
+
+/// the first ip of the advertised management addresses.
+///
+/// The address is either provided as text or as the octets in hexadecimal, e.g. "0A-02-00-01".
+fn management_ip(neighbour: &RemoteSystemsData) -> Option<String> {
+    for management_address in neighbour.management_address.iter() {
+        if let Ok(ip) = management_address.address.parse::<IpAddr>() {
+            return Some(ip.to_string());
+        }
+
+        let octets: Result<Vec<u8>, _> = management_address
+            .address
+            .split(|c| c == '-' || c == ':')
+            .map(|o| u8::from_str_radix(o, 16))
+            .collect();
+
+        if let Ok(octets) = octets {
+            if octets.len() == 4 {
+                let octets: [u8; 4] = [octets[0], octets[1], octets[2], octets[3]];
+                return Some(IpAddr::from(octets).to_string());
+            }
+        }
+    }
+    None
+}
+
+/// the end station as seen by the bridge it is connected to
+fn end_station_from_lldp(id: u32, neighbour: &RemoteSystemsData) -> NodeInformation {
+    let mut mac_addresses_interfaces = vec![canonical_mac(&neighbour.chassis_id)];
+    let port_name = if neighbour.port_id_subtype == "mac-address" {
+        let port_mac = canonical_mac(&neighbour.port_id);
+        if !mac_addresses_interfaces.contains(&port_mac) {
+            mac_addresses_interfaces.push(port_mac);
+        }
+
+        if neighbour.port_desc.is_empty() {
+            neighbour.port_id.clone()
+        } else {
+            neighbour.port_desc.clone()
+        }
+    } else {
+        neighbour.port_id.clone()
+    };
+
+    NodeInformation {
+        id,
+        mac_addresses_interfaces,
+        endstation: NodeType::EndStation,
+        configuration_params: None,
+        ports: vec![Port {
+            name: port_name,
+            mac_address: canonical_mac(&neighbour.chassis_id),
+            delays: Vec::new(),
+            tick_granularity: 0,
+            port_number: 0,
+            supported_list_max: 0,
+            supported_cycle_max: (0, 0),
+        }],
+    }
+}
+
+/// name of the port of a discovered bridge. The port-id is either its name or its mac-address.
+fn remote_port_name(bridge: &NodeInformation, neighbour: &RemoteSystemsData) -> String {
+    let port_mac = canonical_mac(&neighbour.port_id);
+
+    match bridge
+        .ports
+        .iter()
+        .find(|p| canonical_mac(&p.mac_address) == port_mac)
+    {
+        Some(port) => port.name.clone(),
+        None => neighbour.port_id.clone(),
+    }
+}
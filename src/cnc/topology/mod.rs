@@ -3,10 +3,17 @@ use super::types::topology::{
     Topology,
 };
 use super::{Cnc, CNC_NOT_PRESENT};
+use std::collections::HashSet;
 use std::sync::{RwLock, Weak};
 use std::thread;
 use std::time::Duration;
 
+mod lldp;
+mod path_computation;
+
+pub use self::lldp::LldpTopology;
+pub use self::path_computation::{PathComputation, PathMetric};
+
 pub trait TopologyControllerInterface {
     /// on a detected topology change, this gets called.
     /// Any actions the cnc should take have to be called in here...
@@ -20,6 +27,9 @@ pub trait TopologyAdapterInterface {
     /// returns information about a specific node
     fn get_node_information(&self, id: u32) -> Option<NodeInformation>;
 
+    /// returns the metric the paths of the topology are computed with
+    fn get_path_metric(&self) -> PathMetric;
+
     /// running this component continously
     ///
     /// possibly in a new Thread
@@ -45,6 +55,87 @@ impl Topology {
         }
         return None;
     }
+
+    /// compares this topology with a newer one.
+    ///
+    /// A node is changed if it was added, removed or any of its information differs.
+    /// A connection is changed if it is only present in one of the topologies.
+    pub fn diff(&self, new_topology: &Topology) -> TopologyChanges {
+        let mut changes = TopologyChanges {
+            nodes: HashSet::new(),
+            connections: Vec::new(),
+        };
+
+        for node in self.nodes.iter() {
+            match new_topology.nodes.iter().find(|n| n.id == node.id) {
+                Some(new_node) if new_node == node => {}
+                _ => {
+                    changes.nodes.insert(node.id);
+                }
+            }
+        }
+
+        for new_node in new_topology.nodes.iter() {
+            if !self.nodes.iter().any(|n| n.id == new_node.id) {
+                changes.nodes.insert(new_node.id);
+            }
+        }
+
+        for connection in self.connections.iter() {
+            if !new_topology
+                .connections
+                .iter()
+                .any(|c| c.connects_same(connection))
+            {
+                changes.connections.push(connection.clone());
+            }
+        }
+
+        for new_connection in new_topology.connections.iter() {
+            if !self
+                .connections
+                .iter()
+                .any(|c| c.connects_same(new_connection))
+            {
+                changes.connections.push(new_connection.clone());
+            }
+        }
+
+        changes
+    }
+}
+
+/// the mac-addresses are reported in different notations. This is the notation used for comparisons.
+pub(crate) fn canonical_mac(mac_address: &str) -> String {
+    mac_address.replace(':', "-").to_lowercase()
+}
+
+impl Connection {
+    /// true if both connections link the same interfaces. The id and direction are ignored.
+    pub fn connects_same(&self, other: &Connection) -> bool {
+        (self.a == other.a && self.b == other.b) || (self.a == other.b && self.b == other.a)
+    }
+
+    /// true if the provided port is one end of this connection
+    pub fn is_attached_to(&self, node_id: u32, port_name: &str) -> bool {
+        (self.a.node_id == node_id && self.a.port_name == port_name)
+            || (self.b.node_id == node_id && self.b.port_name == port_name)
+    }
+}
+
+/// Result of Topology::diff
+pub struct TopologyChanges {
+    /// ids of all added, removed or modified nodes
+    pub nodes: HashSet<u32>,
+
+    /// all added or removed connections
+    pub connections: Vec<Connection>,
+}
+
+impl TopologyChanges {
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.connections.is_empty()
+    }
 }
 
 pub struct MockTopology {
@@ -81,8 +172,8 @@ impl MockTopology {
             configuration_params: Some(SSHConfigurationParams {
                 ip: String::from("10.2.0.1"),
                 port: 830,
-                username: String::from("admin"),
-                password: String::from("admin"),
+                credentials: None,
+                profile: Some(String::from("br")),
             }),
         });
 
@@ -97,8 +188,8 @@ impl MockTopology {
             configuration_params: Some(SSHConfigurationParams {
                 ip: String::from("10.2.0.2"),
                 port: 830,
-                username: String::from(""),
-                password: String::from(""),
+                credentials: None,
+                profile: Some(String::from("br")),
             }),
         });
 
@@ -226,8 +317,8 @@ impl MockTopology {
             configuration_params: Some(SSHConfigurationParams {
                 ip: String::from("10.2.0.1"),
                 port: 830,
-                username: String::from("admin"),
-                password: String::from("admin"),
+                credentials: None,
+                profile: Some(String::from("br")),
             }),
         });
 
@@ -300,6 +391,10 @@ impl TopologyAdapterInterface for MockTopology {
         return self.topology.read().unwrap().clone();
     }
 
+    fn get_path_metric(&self) -> PathMetric {
+        PathMetric::HopCount
+    }
+
     fn set_cnc_ref(&mut self, cnc: Weak<Cnc>) {
         self.cnc = cnc;
     }
@@ -0,0 +1,383 @@
+use super::canonical_mac;
+use crate::cnc::types::topology::{Hop, NodeType, Path, Route, Topology, Tree};
+use crate::cnc::types::tsn_types::BridgePortDelays;
+use crate::cnc::types::uni_types::Stream;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// cost that is added for every bridge or link that is already used by another tree.
+/// This is higher than any delay, so shared bridges and links are only used if there is no other way.
+const SHARED_PENALTY: u64 = 1 << 40;
+
+/// weight of the links for the shortest path
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathMetric {
+    /// number of traversed links
+    HopCount,
+
+    /// summed maximum delays of the traversed bridge ports (BridgePortDelays).
+    /// Equal delays are decided by the hop count.
+    PortDelay,
+}
+
+/// a directed link from one node to its neighbour
+struct Edge {
+    to: u32,
+    egress_port: String,
+    ingress_port: String,
+    delay: u64,
+}
+
+/// additional costs of the bridges and links used by the already computed trees
+#[derive(Default)]
+struct Penalties {
+    nodes: HashMap<u32, u64>,
+
+    /// by node_id and port_name of both ends
+    links: HashMap<(u32, String), u64>,
+}
+
+impl Penalties {
+    fn add_tree(&mut self, tree: &Tree, bridges: &HashSet<u32>) {
+        let mut nodes: HashSet<u32> = HashSet::new();
+        let mut links: HashSet<(u32, String)> = HashSet::new();
+
+        for route in tree.routes.iter() {
+            for hop in route.hops.iter() {
+                if bridges.contains(&hop.node_id) {
+                    nodes.insert(hop.node_id);
+                }
+                if let Some(port) = &hop.ingress_port {
+                    links.insert((hop.node_id, port.clone()));
+                }
+                if let Some(port) = &hop.egress_port {
+                    links.insert((hop.node_id, port.clone()));
+                }
+            }
+        }
+
+        // shared inside of a tree only counts once
+        for node_id in nodes {
+            *self.nodes.entry(node_id).or_default() += SHARED_PENALTY;
+        }
+        for link in links {
+            *self.links.entry(link).or_default() += SHARED_PENALTY;
+        }
+    }
+
+    fn of_edge(&self, from: u32, edge: &Edge) -> u64 {
+        let node = self.nodes.get(&edge.to).copied().unwrap_or(0);
+        let link = self
+            .links
+            .get(&(from, edge.egress_port.clone()))
+            .copied()
+            .unwrap_or(0);
+        node + link
+    }
+}
+
+/// # Path Computation
+///
+/// Computes the routes of streams through the topology. End stations are only used as start and
+/// end of a route, frames are never forwarded by them.
+pub struct PathComputation<'a> {
+    topology: &'a Topology,
+    metric: PathMetric,
+    edges: HashMap<u32, Vec<Edge>>,
+    bridges: HashSet<u32>,
+}
+
+impl<'a> PathComputation<'a> {
+    pub fn new(topology: &'a Topology, metric: PathMetric) -> Self {
+        let mut edges: HashMap<u32, Vec<Edge>> = HashMap::new();
+
+        for connection in topology.connections.iter() {
+            let a_to_b = Edge {
+                to: connection.b.node_id,
+                egress_port: connection.a.port_name.clone(),
+                ingress_port: connection.b.port_name.clone(),
+                delay: link_delay(
+                    topology,
+                    (connection.a.node_id, &connection.a.port_name),
+                    (connection.b.node_id, &connection.b.port_name),
+                ),
+            };
+            let b_to_a = Edge {
+                to: connection.a.node_id,
+                egress_port: connection.b.port_name.clone(),
+                ingress_port: connection.a.port_name.clone(),
+                delay: link_delay(
+                    topology,
+                    (connection.b.node_id, &connection.b.port_name),
+                    (connection.a.node_id, &connection.a.port_name),
+                ),
+            };
+
+            edges.entry(connection.a.node_id).or_default().push(a_to_b);
+            edges.entry(connection.b.node_id).or_default().push(b_to_a);
+        }
+
+        let bridges: HashSet<u32> = topology
+            .nodes
+            .iter()
+            .filter(|n| n.endstation == NodeType::Bridge)
+            .map(|n| n.id)
+            .collect();
+
+        Self {
+            topology,
+            metric,
+            edges,
+            bridges,
+        }
+    }
+
+    /// returns the id of the end station with the provided mac-address
+    pub fn find_end_station(&self, mac_address: &str) -> Option<u32> {
+        let mac_address = canonical_mac(mac_address);
+
+        self.topology
+            .nodes
+            .iter()
+            .filter(|n| n.endstation == NodeType::EndStation)
+            .find(|n| {
+                n.mac_addresses_interfaces
+                    .iter()
+                    .any(|m| canonical_mac(m) == mac_address)
+            })
+            .map(|n| n.id)
+    }
+
+    /// computes the routes from the talker to each listener of the stream.
+    /// The routes are in the same order as the listeners.
+    ///
+    /// If any end station is not in the topology or not reachable, None is returned.
+    pub fn compute_routes(&self, stream: &Stream) -> Option<Vec<Route>> {
+        let mut trees = self.compute_trees(stream, 1)?;
+        Some(trees.remove(0).routes)
+    }
+
+    /// computes up to num_trees maximally disjoint trees from the talker to all listeners.
+    ///
+    /// Each further tree avoids the bridges and links of the previous trees as far as the topology
+    /// allows. If a further tree can't reach a listener on a new route, no more redundancy is
+    /// possible and only the trees computed so far are returned.
+    ///
+    /// If any end station is not in the topology or not reachable, None is returned.
+    pub fn compute_trees(&self, stream: &Stream, num_trees: u8) -> Option<Vec<Tree>> {
+        let (talker, listeners) = self.find_end_stations(stream)?;
+        self.disjoint_trees(talker, &listeners, num_trees)
+    }
+
+    /// computes up to num_trees maximally disjoint trees between the provided nodes.
+    /// See compute_trees(...)
+    pub fn disjoint_trees(
+        &self,
+        talker: u32,
+        listeners: &[u32],
+        num_trees: u8,
+    ) -> Option<Vec<Tree>> {
+        let mut penalties = Penalties::default();
+        let mut trees: Vec<Tree> = Vec::new();
+
+        // zero is interpreted as one
+        for _ in 0..num_trees.max(1) {
+            let mut routes: Vec<Route> = Vec::new();
+            for listener in listeners.iter() {
+                match self.search(talker, *listener, &penalties) {
+                    Some(route) => routes.push(route),
+                    None => {
+                        eprintln!(
+                            "[PathComputation] listener ({}) is not reachable from talker ({})",
+                            listener, talker
+                        );
+                        return None;
+                    }
+                }
+            }
+
+            let tree = Tree { routes };
+            let adds_redundancy = trees.iter().all(|t| {
+                t.routes
+                    .iter()
+                    .zip(tree.routes.iter())
+                    .all(|(previous, route)| previous.hops != route.hops)
+            });
+
+            if !adds_redundancy {
+                break;
+            }
+
+            penalties.add_tree(&tree, &self.bridges);
+            trees.push(tree);
+        }
+
+        Some(trees)
+    }
+
+    /// node_ids of the talker and all listeners of the stream
+    fn find_end_stations(&self, stream: &Stream) -> Option<(u32, Vec<u32>)> {
+        let talker_interface = stream.talker.group_talker.end_station_interfaces.first()?;
+        let talker = match self.find_end_station(&talker_interface.mac_address) {
+            Some(talker) => talker,
+            None => {
+                eprintln!(
+                    "[PathComputation] talker {} of stream {} is not in the topology",
+                    talker_interface.mac_address, stream.stream_id
+                );
+                return None;
+            }
+        };
+
+        let mut listeners: Vec<u32> = Vec::new();
+        for listener in stream.listener.iter() {
+            let listener_interface = listener.group_listener.end_station_interfaces.first()?;
+
+            match self.find_end_station(&listener_interface.mac_address) {
+                Some(listener) => listeners.push(listener),
+                None => {
+                    eprintln!(
+                        "[PathComputation] listener {} of stream {} is not in the topology",
+                        listener_interface.mac_address, stream.stream_id
+                    );
+                    return None;
+                }
+            }
+        }
+
+        Some((talker, listeners))
+    }
+
+    /// shortest route between two nodes by the metric. Other end stations are not traversed.
+    pub fn shortest_path(&self, from: u32, to: u32) -> Option<Route> {
+        self.search(from, to, &Penalties::default())
+    }
+
+    /// dijkstra with the penalties added to the costs
+    fn search(&self, from: u32, to: u32, penalties: &Penalties) -> Option<Route> {
+        // (cost, hops, node) -> the heap pops the cheapest first
+        let mut queue: BinaryHeap<Reverse<(u64, u32, u32)>> = BinaryHeap::new();
+        let mut best: HashMap<u32, (u64, u32)> = HashMap::new();
+        let mut previous: HashMap<u32, (u32, &Edge)> = HashMap::new();
+
+        best.insert(from, (0, 0));
+        queue.push(Reverse((0, 0, from)));
+
+        while let Some(Reverse((cost, hops, node_id))) = queue.pop() {
+            if node_id == to {
+                break;
+            }
+
+            if best.get(&node_id).map_or(false, |b| *b < (cost, hops)) {
+                continue;
+            }
+
+            if node_id != from && !self.bridges.contains(&node_id) {
+                continue;
+            }
+
+            for edge in self.edges.get(&node_id).into_iter().flatten() {
+                let penalty = penalties.of_edge(node_id, edge);
+                let next = match self.metric {
+                    PathMetric::HopCount => (cost + 1 + penalty, hops + 1),
+                    PathMetric::PortDelay => (cost + edge.delay + penalty, hops + 1),
+                };
+
+                if best.get(&edge.to).map_or(true, |b| next < *b) {
+                    best.insert(edge.to, next);
+                    previous.insert(edge.to, (node_id, edge));
+                    queue.push(Reverse((next.0, next.1, edge.to)));
+                }
+            }
+        }
+
+        if from != to && !previous.contains_key(&to) {
+            return None;
+        }
+
+        // walk back from the listener
+        let mut hops: Vec<Hop> = vec![Hop {
+            node_id: to,
+            ingress_port: None,
+            egress_port: None,
+        }];
+        let mut delay: u64 = 0;
+        let mut node_id = to;
+
+        while let Some((from_id, edge)) = previous.get(&node_id) {
+            hops.last_mut().unwrap().ingress_port = Some(edge.ingress_port.clone());
+            hops.push(Hop {
+                node_id: *from_id,
+                ingress_port: None,
+                egress_port: Some(edge.egress_port.clone()),
+            });
+            delay += edge.delay;
+            node_id = *from_id;
+        }
+        hops.reverse();
+
+        Some(Route { hops, delay })
+    }
+
+    /// the shortest paths between all pairs of end stations as used in Topology.paths
+    pub fn compute_all_paths(&self) -> Vec<Path> {
+        let end_stations: Vec<u32> = self
+            .topology
+            .nodes
+            .iter()
+            .filter(|n| n.endstation == NodeType::EndStation)
+            .map(|n| n.id)
+            .collect();
+
+        let mut paths: Vec<Path> = Vec::new();
+        for (i, a) in end_stations.iter().enumerate() {
+            for b in end_stations.iter().skip(i + 1) {
+                if let Some(route) = self.shortest_path(*a, *b) {
+                    paths.push(Path {
+                        node_a_id: *a,
+                        node_b_id: *b,
+                        hops: route
+                            .hops
+                            .iter()
+                            .filter(|h| self.bridges.contains(&h.node_id))
+                            .map(|h| h.node_id)
+                            .collect(),
+                    });
+                }
+            }
+        }
+
+        paths
+    }
+}
+
+/// delay from receiving a frame on the ingress port of a bridge until it is transmitted on the
+/// egress port of the next bridge. End stations have no delays.
+fn link_delay(topology: &Topology, egress: (u32, &String), ingress: (u32, &String)) -> u64 {
+    let egress_delay = port_delays(topology, egress.0, egress.1)
+        .map(|d| d.independent_rly_delay_max + d.independent_tx_delay_max)
+        .max()
+        .unwrap_or(0);
+
+    let ingress_delay = port_delays(topology, ingress.0, ingress.1)
+        .map(|d| d.independent_rx_delay_max + d.dependent_rx_delay_max)
+        .max()
+        .unwrap_or(0);
+
+    egress_delay + ingress_delay
+}
+
+/// the delays of a port for all supported port speeds
+fn port_delays<'t>(
+    topology: &'t Topology,
+    node_id: u32,
+    port_name: &'t String,
+) -> impl Iterator<Item = &'t BridgePortDelays> {
+    topology
+        .nodes
+        .iter()
+        .filter(move |n| n.id == node_id)
+        .flat_map(|n| n.ports.iter())
+        .filter(move |p| &p.name == port_name)
+        .flat_map(|p| p.delays.iter())
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// control element of a port in the port map of a static filtering entry
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum StaticFilteringControl {
+    /// Forwarded, independently of any dynamic filtering information
+    /// held by the FDB.
+    Forward,
+
+    /// Filtered, independently of any dynamic filtering information.
+    Filter,
+
+    /// Forwarded or filtered on the basis of dynamic filtering
+    /// information, or on the basis of the default Group filtering
+    /// behavior for the outbound Port if no dynamic filtering
+    /// information is present specifically for the MAC address.
+    ForwardFilter,
+}
+
+/// A static filtering entry of the filtering database (FDB) of a
+/// bridge. It specifies whether frames with the destination MAC
+/// address and VID are forwarded on the port.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StaticFilteringEntry {
+    /// The destination MAC address (unicast, multicast, broadcast) of
+    /// the frames.
+    pub address: String,
+
+    /// The VLAN identifier to which this entry applies.
+    pub vid: u16,
+
+    /// The control element of the port.
+    pub control_element: StaticFilteringControl,
+}
+
+/// Specifies whether frames are VLAN-tagged or untagged when
+/// transmitted on the port.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum VlanTransmitted {
+    Tagged,
+    Untagged,
+}
+
+/// A static VLAN registration entry of the filtering database (FDB)
+/// of a bridge. The port is a fixed member of the VLAN.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VlanRegistrationEntry {
+    /// The VLAN identifier to which this entry applies.
+    pub vid: u16,
+
+    /// Whether frames of the VLAN are VLAN-tagged or untagged when
+    /// transmitted on the port.
+    pub vlan_transmitted: VlanTransmitted,
+}
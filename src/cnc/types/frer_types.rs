@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// Specifies which frames are identified by their VLAN tag.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum VlanTagIdentification {
+    /// A frame must have a VLAN tag to be recognized as belonging to
+    /// the Stream.
+    Tagged,
+
+    /// A frame must be untagged, or have a VLAN tag with a VLAN ID = 0
+    /// to be recognized as belonging to the Stream.
+    Priority,
+
+    /// A frame is recognized as belonging to the Stream whether tagged
+    /// or not.
+    All,
+}
+
+/// Null Stream identification. Frames are identified by their
+/// destination MAC address and VLAN.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NullStreamIdentification {
+    /// The destination MAC address of the frames.
+    pub destination_mac: String,
+
+    /// Specifies whether the frames have to be VLAN tagged.
+    pub tagged: VlanTagIdentification,
+
+    /// The VLAN ID of the frames. Ignored unless tagged is Tagged.
+    pub vlan: u16,
+}
+
+/// IP Stream identification. Frames are identified by their IP
+/// header and the ports of the transport protocol in addition to the
+/// destination MAC address and VLAN.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IpStreamIdentification {
+    /// The destination MAC address of the frames. None matches any
+    /// destination MAC address.
+    pub destination_mac: Option<String>,
+
+    /// Specifies whether the frames have to be VLAN tagged.
+    pub tagged: VlanTagIdentification,
+
+    /// The VLAN ID of the frames. Ignored unless tagged is Tagged.
+    pub vlan: u16,
+
+    /// The source IP address of the frames.
+    pub ip_source: IpAddr,
+
+    /// The destination IP address of the frames.
+    pub ip_destination: IpAddr,
+
+    /// The DSCP of the frames.
+    pub dscp: u8,
+
+    /// The IP protocol number of the transport protocol, e.g. 6 for
+    /// TCP or 17 for UDP.
+    pub next_protocol: u16,
+
+    /// The source port of the transport protocol. 0 matches any port.
+    pub source_port: u16,
+
+    /// The destination port of the transport protocol. 0 matches any
+    /// port.
+    pub destination_port: u16,
+}
+
+/// The method used to identify the frames of a Stream.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum StreamIdentificationType {
+    NullStream(NullStreamIdentification),
+    IpStream(IpStreamIdentification),
+}
+
+/// An entry of the stream identity table of a bridge. The frames
+/// received on the in-facing input ports that match the identification
+/// are assigned to the stream handle.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StreamIdentityEntry {
+    /// The index of the entry. Unique per bridge.
+    pub index: u32,
+
+    /// The stream handle, that is used by the FRER and PSFP functions
+    /// of the bridge to refer to the Stream.
+    pub handle: u32,
+
+    /// The ports on which the identification is placed on the in-facing
+    /// side of the relay for received frames.
+    pub in_facing_input_port_list: Vec<String>,
+
+    /// The method and parameters of the identification.
+    pub identification: StreamIdentificationType,
+}
+
+/// The algorithm used by a sequence recovery function to discard
+/// duplicate frames.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum SequenceRecoveryAlgorithm {
+    /// The sequence numbers within the history length are remembered,
+    /// so frames can be received out of order.
+    Vector,
+
+    /// Only the last sequence number is remembered. This requires the
+    /// frames to be received in order.
+    Match,
+}
+
+/// An entry of the sequence generation table. The function assigns
+/// sequence numbers to the frames of the streams.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SequenceGenerationEntry {
+    /// The index of the entry. Unique per bridge.
+    pub index: u32,
+
+    /// The stream handles of the streams the function applies to.
+    pub stream_list: Vec<u32>,
+
+    /// If true, the function is placed on the out-facing side of the
+    /// port, otherwise on the in-facing side.
+    pub direction_out_facing: bool,
+}
+
+/// An entry of the sequence recovery table. The function discards
+/// duplicate frames of the streams, that are transmitted on the
+/// ports.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SequenceRecoveryEntry {
+    /// The index of the entry. Unique per bridge.
+    pub index: u32,
+
+    /// The stream handles of the streams the function applies to.
+    pub stream_list: Vec<u32>,
+
+    /// The ports on which the function is placed.
+    pub port_list: Vec<String>,
+
+    /// If true, the function is placed on the out-facing side of the
+    /// ports, otherwise on the in-facing side.
+    pub direction_out_facing: bool,
+
+    /// The algorithm to discard duplicate frames.
+    pub algorithm: SequenceRecoveryAlgorithm,
+
+    /// The number of sequence numbers the vector recovery algorithm
+    /// remembers.
+    pub history_length: u32,
+
+    /// The time in ms after which the function is reset, if no frame
+    /// was received.
+    pub reset_timeout: u32,
+
+    /// If true, frames without a sequence number are accepted.
+    pub take_no_sequence: bool,
+
+    /// If true, the function is an individual recovery function for
+    /// each stream in the stream list.
+    pub individual_recovery: bool,
+}
+
+/// An entry of the sequence identification table of a port. The
+/// function encodes (active) or decodes (passive) the sequence number
+/// of the frames in a redundancy tag (R-TAG).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SequenceIdentificationEntry {
+    /// The stream handles of the streams the function applies to.
+    pub stream_list: Vec<u32>,
+
+    /// If true, the function is placed on the out-facing side of the
+    /// port, otherwise on the in-facing side.
+    pub direction_out_facing: bool,
+
+    /// If true, the sequence number is encoded into transmitted frames,
+    /// otherwise it is decoded from received frames.
+    pub active: bool,
+}
+
+/// The FRER functions of a bridge, that are configured with a port.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct FrerConfiguration {
+    pub sequence_generation: Vec<SequenceGenerationEntry>,
+    pub sequence_recovery: Vec<SequenceRecoveryEntry>,
+    pub sequence_identification: Vec<SequenceIdentificationEntry>,
+}
@@ -3,6 +3,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize, Clone)]
 /// Information about a particular physical network connection.
 pub struct RemoteSystemsData {
+    /// Name of the local port on which the information of the remote system was received.
+    #[serde(default)]
+    pub local_port_name: String,
     /// A TimeFilter for this entry.
     pub time_mark: u32,
     /// Represents an arbitrary local integer value used to identify a remote system.
@@ -36,6 +39,7 @@ pub struct RemoteSystemsData {
 impl RemoteSystemsData {
     pub fn new() -> RemoteSystemsData {
         RemoteSystemsData {
+            local_port_name: String::new(),
             time_mark: 0,
             remote_index: 0,
             chassis_id_subtype: String::new(),
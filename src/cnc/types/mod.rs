@@ -1,6 +1,7 @@
 use self::scheduling::Schedule;
 use self::tsn_types::{GroupInterfaceId, StreamIdTypeUpper};
 use self::uni_types::Domain;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 /// Rust-Types for YANG-Models
@@ -53,27 +54,45 @@ pub mod uni_types;
 
 pub mod lldp_types;
 
+/// Rust-Types for YANG-Models
+///
+/// https://github.com/YangModels/yang/blob/main/standard/ieee/published/802.1/ieee802-dot1cb-stream-identification.yang
+/// https://github.com/YangModels/yang/blob/main/standard/ieee/published/802.1/ieee802-dot1cb-frer.yang
+///
+/// # Description
+/// Stream identification and Frame Replication and Elimination for
+/// Reliability (FRER) of IEEE Std 802.1CB.
+pub mod frer_types;
+
+/// Rust-Types for YANG-Models
+///
+/// https://github.com/YangModels/yang/blob/main/standard/ieee/published/802.1/ieee802-dot1q-bridge.yang
+///
+/// # Description
+/// Static entries of the filtering database of IEEE Std 802.1Q Bridges.
+pub mod bridge_types;
+
 pub mod topology {
-    #[derive(Clone)]
+    #[derive(Clone, PartialEq)]
     pub enum NodeType {
         Bridge,
         EndStation,
     }
 
-    #[derive(Clone)]
+    #[derive(Clone, PartialEq)]
     pub struct ConnectionInterface {
         pub node_id: u32,
         pub port_name: String,
     }
 
-    #[derive(Clone)]
+    #[derive(Clone, PartialEq)]
     pub struct Connection {
         pub id: u32,
         pub a: ConnectionInterface,
         pub b: ConnectionInterface,
     }
 
-    #[derive(Clone)]
+    #[derive(Clone, PartialEq)]
     pub struct NodeInformation {
         pub id: u32,
         pub mac_addresses_interfaces: Vec<String>,
@@ -82,30 +101,77 @@ pub mod topology {
         pub ports: Vec<Port>,
     }
 
-    #[derive(Debug, Clone)]
+    /// a bridge reported that the operational state of one of its ports changed
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct LinkEvent {
+        pub node_id: u32,
+        pub port_name: String,
+        pub up: bool,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
     pub struct SSHConfigurationParams {
         pub ip: String,
         pub port: u16,
-        pub username: String,
-        pub password: String,
+
+        /// name of the credentials in the credential file of the southbound. If None, they're
+        /// resolved by the ip.
+        pub credentials: Option<String>,
+
+        /// name of the device profile of the southbound. If None, it's detected by the device.
+        pub profile: Option<String>,
     }
 
-    #[derive(Clone, Debug)]
+    #[derive(Clone, Debug, PartialEq)]
     pub struct Port {
         pub name: String,
         pub mac_address: String,
         pub delays: Vec<super::tsn_types::BridgePortDelays>,
         pub tick_granularity: u32,
+
+        /// number of the port in the bridge component. Zero if the port doesn't report it.
+        pub port_number: u32,
+
+        /// limits of the gate control list. Zero if the port doesn't report them.
+        pub supported_list_max: u32,
+        pub supported_cycle_max: super::sched_types::RationalGrouping,
     }
 
-    #[derive(Clone)]
+    #[derive(Clone, PartialEq)]
     pub struct Path {
         pub node_a_id: u32,
         pub node_b_id: u32,
         pub hops: Vec<u32>,
     }
 
-    #[derive(Clone)]
+    /// a node on a route. Frames are received on the ingress_port and transmitted on the egress_port.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Hop {
+        pub node_id: u32,
+
+        /// None on the talker
+        pub ingress_port: Option<String>,
+
+        /// None on the listener
+        pub egress_port: Option<String>,
+    }
+
+    /// route of a stream from the talker to one listener, both included in the hops
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Route {
+        pub hops: Vec<Hop>,
+
+        /// summed maximum delays of all traversed bridge ports in ns
+        pub delay: u64,
+    }
+
+    /// routes of a stream from the talker to all of its listeners. The routes are in the order of the listeners.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Tree {
+        pub routes: Vec<Route>,
+    }
+
+    #[derive(Clone, PartialEq)]
     pub struct Topology {
         pub nodes: Vec<NodeInformation>,
         pub connections: Vec<Connection>,
@@ -115,6 +181,7 @@ pub mod topology {
 
 pub mod scheduling {
     use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
 
     #[derive(Serialize, Deserialize, Clone, Debug)]
     pub struct Config {
@@ -127,13 +194,87 @@ pub mod scheduling {
     pub struct PortConfiguration {
         pub name: String,
         pub mac_address: String,
+
+        /// number of the port in the bridge component, that refers to the port in the filtering
+        /// database. Zero if the port doesn't report it.
+        #[serde(default)]
+        pub port_number: u32,
+
         pub config: super::sched_types::ConfigurableGateParameterTableEntry,
+
+        /// frame preemption status per traffic class. Empty if the port isn't configured for
+        /// frame preemption.
+        #[serde(default)]
+        pub frame_preemption: Vec<super::sched_types::FramePreemptionStatusEntry>,
+
+        /// credit-based shaper parameters per traffic class. Empty if the port has no
+        /// credit-based shaped streams.
+        #[serde(default)]
+        pub fqtss: Vec<super::sched_types::FqtssTrafficClassEntry>,
+
+        /// per-stream filtering and policing of the streams received on the port. Empty if no
+        /// stream is received on the port.
+        #[serde(default)]
+        pub psfp: Vec<super::sched_types::PsfpEntry>,
+
+        /// stream identification of the streams received on the port. Empty if no stream is
+        /// received on the port.
+        #[serde(default)]
+        pub stream_identification: Vec<super::frer_types::StreamIdentityEntry>,
+
+        /// FRER functions of the streams, that are replicated or eliminated at the port.
+        #[serde(default)]
+        pub frer: super::frer_types::FrerConfiguration,
+
+        /// static filtering entries of the streams transmitted on the port. Empty if no stream is
+        /// transmitted on the port.
+        #[serde(default)]
+        pub static_filtering: Vec<super::bridge_types::StaticFilteringEntry>,
+
+        /// VLANs of the streams, that the port is a member of. Streams without VLAN ID use the
+        /// default VLAN and don't need a registration.
+        #[serde(default)]
+        pub vlan_registration: Vec<super::bridge_types::VlanRegistrationEntry>,
+
+        /// static filtering entries that the CNC configured on the port before, but no stream
+        /// needs anymore. They are reset to the dynamic behaviour, other entries aren't touched.
+        #[serde(default)]
+        pub released_static_filtering: Vec<super::bridge_types::StaticFilteringEntry>,
+
+        /// VLAN registrations that the CNC configured on the port before, but no stream needs
+        /// anymore. They are reset to the normal registration.
+        #[serde(default)]
+        pub released_vlan_registration: Vec<super::bridge_types::VlanRegistrationEntry>,
     }
 
     #[derive(Clone, Debug)]
     pub struct Schedule {
         pub configs: Vec<Config>,
+
+        /// the maximally disjoint trees of each stream by stream_id.
+        /// If there is more than one tree, the stream has to be configured for IEEE 802.1CB.
+        pub trees: HashMap<String, Vec<super::topology::Tree>>,
+    }
+}
+
+pub mod allocation {
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    /// network-side resources, that the CNC assigned to a stream. In the network, they replace the
+    /// VLAN tag and destination MAC address of the data-frame-specification of the talker.
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct StreamResources {
+        /// None if the end stations of the stream aren't vlan-tag-capable
+        pub vlan_tag: Option<super::tsn_types::GroupIeee802VlanTag>,
+
+        /// None if the end stations of the stream don't support Active Destination MAC and VLAN
+        /// Stream identification
+        pub destination_mac: Option<String>,
     }
+
+    /// resources of the streams of a domain by stream_id
+    pub type DomainResources = HashMap<String, StreamResources>;
 }
 
 pub mod computation {
@@ -155,6 +296,9 @@ pub struct FailedInterface {
     pub interface: GroupInterfaceId,
     pub node_id: u32,
     pub affected_streams: HashSet<StreamIdTypeUpper>,
+
+    /// failure-code of the affected streams
+    pub failure_code: u32,
 }
 
 pub struct ComputationResult {
@@ -163,6 +307,35 @@ pub struct ComputationResult {
     pub failed_streams: Vec<FailedStream>,
 }
 
+/// failure-codes of Table 46-15 of IEEE Std 802.1Q-2022 that are used by the CNC
+pub mod failure_codes {
+    /// Insufficient bandwidth
+    pub const INSUFFICIENT_BANDWIDTH: u32 = 1;
+
+    /// Insufficient Bridge resources. This is also used if the requested num-seamless-trees
+    /// can't be configured in the topology, or if they split at a talker without FRER.
+    pub const INSUFFICIENT_BRIDGE_RESOURCES: u32 = 2;
+
+    /// MaxLatency exceeded
+    pub const MAX_LATENCY_EXCEEDED: u32 = 21;
+
+    // IEEE 802.1Qcc has no failure-codes for the configuration of the bridges, so the CNC uses
+    // its own ones outside of the standard range (Table 46-8).
+
+    /// The configuration couldn't be deployed on a bridge, e.g. it was refused or timed out.
+    pub const CONFIGURATION_FAILED: u32 = 128;
+
+    /// The configuration was deployed, but the bridge doesn't operate with it.
+    pub const CONFIGURATION_NOT_IN_EFFECT: u32 = 129;
+
+    /// The configuration on a bridge was changed afterwards and differs from the stored one.
+    pub const CONFIGURATION_DRIFTED: u32 = 130;
+
+    /// The bridge doesn't provide the optional yang-module of a function the configuration needs,
+    /// e.g. PSFP or FRER.
+    pub const FUNCTION_NOT_SUPPORTED: u32 = 131;
+}
+
 pub struct FailedStream {
     pub stream_id: StreamIdTypeUpper,
     pub cuc_id: String,
@@ -170,8 +343,13 @@ pub struct FailedStream {
     pub failure_code: u32,
 }
 
+/// the requested part of a stream-entry of the tsn-uni tree
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct StreamRequest {
     pub stream_id: StreamIdTypeUpper,
     pub talker: tsn_types::GroupTalker,
+
+    #[serde(rename = "listener")]
     pub listeners: Vec<tsn_types::GroupListener>,
 }
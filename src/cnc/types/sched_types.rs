@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 /// A GateControlEntry consists of an operation name, followed by up to 2
 /// parameters associated with the operation. The first parameter is a
 /// gateStatesValue; the second parameter is a timeIntervalValue
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct GateControlEntry {
     /// The name (type) of the operation for this entry.
     pub operation_name: GateControlOperation,
@@ -24,7 +24,7 @@ pub struct GateControlEntry {
     pub gate_state_value: u8,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum GateControlOperation {
     /// Operation to set the gate states.
     SetGateStates,
@@ -36,6 +36,198 @@ pub enum GateControlOperation {
     SetAndReleaseMAC,
 }
 
+/// framePreemptionAdminStatus of a traffic class (IEEE 802.1Qbu)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum FramePreemptionStatus {
+    /// Frames queued for the priority are to be transmitted using the
+    /// express service for the Port.
+    Express,
+
+    /// Frames queued for the priority are to be transmitted using the
+    /// preemptable service for the Port and preemption is enabled for
+    /// the Port.
+    Preemptable,
+}
+
+/// An entry of the framePreemptionStatusTable. The table consists of
+/// one framePreemptionAdminStatus per traffic class.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FramePreemptionStatusEntry {
+    /// Traffic class
+    pub traffic_class: u8,
+
+    /// The value of the framePreemptionAdminStatus parameter for the
+    /// traffic class. The default value is express.
+    pub frame_preemption_status: FramePreemptionStatus,
+}
+
+/// transmission selection algorithm of a traffic class (Table 8-6 of
+/// IEEE Std 802.1Q-2022)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TransmissionSelectionAlgorithm {
+    /// Strict priority algorithm
+    StrictPriority,
+
+    /// Credit-based shaper algorithm (IEEE 802.1Qav)
+    CreditBasedShaper,
+}
+
+/// The FQTSS (forwarding and queuing enhancements for time-sensitive
+/// streams) parameters of a traffic class. This combines the entries
+/// of the bandwidth availability parameter table and the transmission
+/// selection algorithm table of the Port.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FqtssTrafficClassEntry {
+    /// Traffic class
+    pub traffic_class: u8,
+
+    /// The transmission selection algorithm assigned to the traffic
+    /// class.
+    pub transmission_selection_algorithm: TransmissionSelectionAlgorithm,
+
+    /// The administrative idle slope of the credit-based shaper in bits
+    /// per second. This is the bandwidth reserved for the traffic class.
+    pub admin_idle_slope: u64,
+}
+
+/// state of a stream gate (IEEE 802.1Qci)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum StreamGateState {
+    /// Frames of the stream are permitted to pass the gate.
+    Open,
+
+    /// Frames of the stream are discarded.
+    Closed,
+}
+
+/// A stream gate control entry consists of the set-gate-and-ipv
+/// operation and its parameters.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StreamGateControlEntry {
+    /// The desired state of the stream gate.
+    pub gate_state_value: StreamGateState,
+
+    /// The internal priority value (IPV) to be associated with frames
+    /// that pass the gate. None keeps the priority of the frame.
+    pub ipv_spec: Option<u8>,
+
+    /// timeIntervalValue is a 32-bit unsigned integer, representing a
+    /// number of nanoseconds. After timeIntervalValue nanoseconds have
+    /// elapsed since the completion of the previous entry in the gate
+    /// control list, control passes to the next entry.
+    pub time_interval_value: u32,
+
+    /// The maximum number of MSDU octets that are permitted to pass the
+    /// gate during the time interval. None for no limit.
+    pub interval_octet_max: Option<u32>,
+}
+
+/// An entry of the stream filter instance table of a bridge. The
+/// stream filter assigns the frames of a stream to a stream gate and a
+/// flow meter.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StreamFilterInstance {
+    /// The index of the stream filter instance. Unique per bridge.
+    pub stream_filter_instance_id: u32,
+
+    /// The stream handle assigned by the stream identification
+    /// function. None matches frames of all streams (wildcard).
+    pub stream_handle: Option<u32>,
+
+    /// The priority of the frames to match. None matches all
+    /// priorities (wildcard).
+    pub priority_spec: Option<u8>,
+
+    /// The maximum SDU size in octets. Larger frames are discarded. A
+    /// value of 0 disables the filter.
+    pub max_sdu_size: u32,
+
+    /// If true, the stream is blocked permanently after a frame
+    /// exceeded the max_sdu_size.
+    pub stream_blocked_due_to_oversize_frame_enabled: bool,
+
+    /// The stream gate instance the frames are passed to.
+    pub stream_gate_ref: u32,
+
+    /// The flow meter instance the frames are passed to. None if the
+    /// frames aren't metered.
+    pub flow_meter_instance_id: Option<u32>,
+}
+
+/// An entry of the stream gate instance table of a bridge.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StreamGateInstance {
+    /// The index of the stream gate instance. Unique per bridge.
+    pub stream_gate_instance_id: u32,
+
+    /// The GateEnabled parameter determines whether the stream gate is
+    /// controlled by the admin_control_list (true) or stays in the
+    /// admin_gate_states (false).
+    pub gate_enable: bool,
+
+    /// The administrative value of the initial gate state.
+    pub admin_gate_states: StreamGateState,
+
+    /// The administrative value of the initial IPV. None keeps the
+    /// priority of the frame.
+    pub admin_ipv: Option<u8>,
+
+    /// AdminControlList is the administrative value of the gate control
+    /// list for the stream gate.
+    pub admin_control_list: Vec<StreamGateControlEntry>,
+
+    /// AdminCycleTime specifies the administrative value of the gating
+    /// cycle time for the stream gate as rational number of seconds.
+    pub admin_cycle_time: RationalGrouping,
+
+    /// The administrative value of the base time at which gating cycles
+    /// begin, expressed as an IEEE 1588 precision time protocol (PTP)
+    /// timescale.
+    pub admin_base_time: PtpTimeGrouping,
+
+    /// The ConfigChange parameter signals the start of a configuration
+    /// change when it is set to TRUE.
+    pub config_change: bool,
+}
+
+/// An entry of the flow meter instance table of a bridge. The flow
+/// meter is a token bucket according to the bandwidth profile of MEF
+/// 10.3.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FlowMeterInstance {
+    /// The index of the flow meter instance. Unique per bridge.
+    pub flow_meter_instance_id: u32,
+
+    /// The committed information rate (CIR) in bits per second.
+    pub committed_information_rate: u64,
+
+    /// The committed burst size (CBS) in octets.
+    pub committed_burst_size: u32,
+
+    /// The excess information rate (EIR) in bits per second.
+    pub excess_information_rate: u64,
+
+    /// The excess burst size (EBS) in octets.
+    pub excess_burst_size: u32,
+
+    /// If true, frames that exceed the committed rate are discarded
+    /// instead of being marked yellow.
+    pub drop_on_yellow: bool,
+
+    /// If true, all frames are discarded after a frame was marked red.
+    pub mark_all_frames_red_enable: bool,
+}
+
+/// Per-stream filtering and policing (IEEE 802.1Qci) of a stream on a
+/// bridge. The instances belong to the bridge component, but are
+/// configured with the ingress port the stream is received on.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PsfpEntry {
+    pub stream_filter: StreamFilterInstance,
+    pub stream_gate: StreamGateInstance,
+    pub flow_meter: FlowMeterInstance,
+}
+
 /// A list containing a set of max SDU parameters, one for each
 /// traffic class. All writable objects in this table must be
 /// persistent over power up restart/reboot.
@@ -86,7 +278,11 @@ pub type SchedParameters = Vec<GateParameterTableEntry>;
 /// traffic scheduling. For a given Port, an entry in the table exists.
 /// All writable objects in this table must be persistent over power up
 /// restart/reboot.
+///
+/// The southbound reads the operational values to verify that a
+/// deployed configuration is in effect.
 #[allow(dead_code)]
+#[derive(Clone, Debug, Default)]
 pub struct GateParameterTableEntry {
     /// A list containing a set of max SDU parameters, one for each
     /// traffic class. All writable objects in this table must be
@@ -96,7 +292,7 @@ pub struct GateParameterTableEntry {
     /// The GateEnabled parameter determines whether traffic scheduling
     /// is active (true) or inactive (false). The value must be retained
     /// across reinitializations of the management system.
-    gate_enable: bool,
+    pub gate_enable: bool,
 
     /// AdminGateStates is the administrative value of the initial gate
     /// states for the Port. The bits of the octet represent the gate
@@ -122,7 +318,7 @@ pub struct GateParameterTableEntry {
 
     /// OperControlList is the operational value of the gate control list
     /// for the Port.
-    oper_control_list: Vec<GateControlEntry>,
+    pub oper_control_list: Vec<GateControlEntry>,
 
     /// AdminCycleTime specifies the administrative value of the gating
     /// cycle time for the Port. AdminCycleTime is a rational number of
@@ -134,7 +330,7 @@ pub struct GateParameterTableEntry {
     /// OperCycleTime specifies the operational value of the gating cycle
     /// time for the Port. OperCycleTime is a rational number of seconds,
     /// defined by an integer numerator and an integer denominator.
-    oper_cycle_time: RationalGrouping,
+    pub oper_cycle_time: RationalGrouping,
 
     /// An unsigned integer number of nanoseconds, defining the maximum
     /// amount of time by which the gating cycle for the Port is permitted
@@ -158,7 +354,7 @@ pub struct GateParameterTableEntry {
     /// The operational value of the base time at which gating cycles
     /// begin, expressed as an IEEE 1588 precision time protocol (PTP)
     /// timescale.
-    oper_base_time: PtpTimeScale,
+    pub oper_base_time: PtpTimeGrouping,
 
     /// The ConfigChange parameter signals the start of a configuration
     /// change when it is set to TRUE, indicating that the administrative
@@ -182,12 +378,12 @@ pub struct GateParameterTableEntry {
     /// The value of the ConfigPending state machine variable. The value
     /// is TRUE if a configuration change is in progress but has not yet
     /// completed.
-    config_pending: bool,
+    pub config_pending: bool,
 
     /// A counter of the number of times that a re-configuration of the
     /// traffic schedule has been requested with the old schedule still
     /// running and the requested base time was in the past.
-    config_change_error: u64,
+    pub config_change_error: u64,
 
     /// The maximum value supported by this Port for the
     /// AdminControlListLength and OperControlListLength parameters. It is
@@ -60,6 +60,7 @@ pub type StreamIdTypeUpper = String;
 /// point of attachment (interface) in a station (end station or
 /// Bridge).
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupInterfaceId {
     /// mac-address is the unique individual MAC address (IEEE Std 802) of
     /// the interface in the station (end station or Bridge). This MAC
@@ -125,6 +126,7 @@ pub struct GroupInterfaceId {
 /// If inconsistency arises between this specification and IEEE Std
 /// 802.1CB, IEEE Std 802.1CB takes precedence.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupIeee802MacAddress {
     /// Destination MAC address.
     ///
@@ -133,6 +135,7 @@ pub struct GroupIeee802MacAddress {
     ///
     /// The string uses the hexadecimal representation specified in IEEE
     /// Std 802 (i.e. canonical format).
+    #[serde(rename = "destination-mac-address")]
     pub destination_mac_adress: String,
 
     /// Source MAC address.
@@ -142,6 +145,7 @@ pub struct GroupIeee802MacAddress {
     ///
     /// The string uses the hexadecimal representation specified in IEEE
     /// Std 802 (i.e. canonical format).
+    #[serde(rename = "source-mac-address")]
     pub source_mac_adress: String,
 }
 
@@ -156,6 +160,7 @@ pub struct GroupIeee802MacAddress {
 /// If inconsistency arises between this specification and IEEE Std
 /// 802.1CB, IEEE Std 802.1CB takes precedence.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupIeee802VlanTag {
     /// Priority Code Point (PCP) field.
     ///
@@ -178,14 +183,17 @@ pub struct GroupIeee802VlanTag {
 /// If inconsistency arises between this specification and IEEE Std
 /// 802.1CB, IEEE Std 802.1CB takes precedence.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupIpv4Tuple {
     /// Source IPv4 address.
     ///
     /// An address of all 0's specifies that the IP source address is
     /// ignored for purposes of Stream identification.
+    #[serde(rename = "source-ip-address")]
     pub source_ip_adress: std::net::Ipv4Addr,
 
     /// Destination IPv4 address.
+    #[serde(rename = "destination-ip-address")]
     pub destination_ip_adress: std::net::Ipv4Addr,
 
     /// Differentiated services code point, DSCP (RFC2474).
@@ -219,14 +227,17 @@ pub struct GroupIpv4Tuple {
 /// If inconsistency arises between this specification and IEEE Std
 /// 802.1CB, IEEE Std 802.1CB takes precedence.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupIpv6Tuple {
     /// Source IPv6 address.
     ///
     /// An address of all 0's specifies that the IP source address is
     /// ignored for purposes of Stream identification.
+    #[serde(rename = "source-ip-address")]
     pub source_ip_adress: std::net::Ipv6Addr,
 
     /// Destination IPv6 address.
+    #[serde(rename = "destination-ip-address")]
     pub destination_ip_adress: std::net::Ipv6Addr,
 
     /// Differentiated services code point, DSCP (RFC2474).
@@ -258,6 +269,7 @@ pub struct GroupIpv6Tuple {
 /// The network (e.g. CNC) will merge all user-to-network-requirements
 /// for a Stream to ensure that all requirements are met.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupUserToNetworkRequirements {
     /// num-seamless-trees specifies the number of trees that the
     /// network will configure to deliver seamless redundancy for the
@@ -310,6 +322,7 @@ pub struct GroupUserToNetworkRequirements {
     /// If group-user-to-network-requirements is not provided by the
     /// Talker or Listener, the network shall use the default value of
     /// one for this leaf.
+    #[serde(rename = "num-seamless-trees")]
     pub num_seemless_trees: u8,
 
     /// Maximum latency from Talker to Listener(s) for a single frame
@@ -390,6 +403,7 @@ pub struct GroupUserToNetworkRequirements {
 /// end-station-interfaces is intended for network capabilities that
 /// span multiple interfaces (e.g. seamless redundancy).
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupInterfaceCapabilities {
     /// When vlan-tag-capable is true, the interface supports the
     /// ability to tag/untag frames using a Customer VLAN Tag (C-TAG of
@@ -468,6 +482,7 @@ pub struct GroupInterfaceCapabilities {
 /// configuration value. Each container name acts as the case
 /// name for the choice.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub enum ConfigValue {
     /// Source and destination MAC addresses that apply to the
     /// network side of the user/network boundary.
@@ -544,6 +559,7 @@ pub enum ConfigValue {
     TimeAwareOffset(u32),
 }
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct ConfigListElement {
     /// This index is provided in order to provide a unique key per
     /// list entry. The value of index for each entry shall be
@@ -553,13 +569,18 @@ pub struct ConfigListElement {
     /// One of the following choices is provided for each
     /// configuration value. Each container name acts as the case
     /// name for the choice.
+    #[serde(flatten)]
     pub config_value: ConfigValue,
 }
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct InterfaceListElement {
+    /// The keys of the list entry, they are serialized first.
+    #[serde(flatten)]
+    pub group_interface_id: GroupInterfaceId,
+
     /// List of configuration values for the interface.
     pub config_list: Vec<ConfigListElement>,
-    pub group_interface_id: GroupInterfaceId,
 }
 
 /// This YANG grouping provides configuration of interfaces in the
@@ -568,6 +589,7 @@ pub struct InterfaceListElement {
 /// capabilities of the interface as provided in
 /// interface-capabilities.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupInterfaceConfiguration {
     /// A distinct configuration is provided for each interface in the
     /// Talker/Listener (even if multiple interfaces use the same
@@ -589,6 +611,7 @@ pub struct GroupInterfaceConfiguration {
 /// of Stream resource configuration, and it is unrelated to the
 /// Streams data.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct StreamRankContainer {
     /// The Rank is used by the network to decide which Streams can
     /// and cannot exist when TSN resources reach their limit. If a
@@ -623,6 +646,7 @@ pub struct StreamRankContainer {
 /// the user knows. Each container name acts as the case name for
 /// the choice.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub enum DataFrameSpecificationElementType {
     /// IEEE 802 MAC addresses.
     Ieee802MacAddresses(GroupIeee802MacAddress),
@@ -658,11 +682,14 @@ pub enum DataFrameSpecificationElementType {
 /// transformation is performed in the Talker and Listeners of this
 /// Stream (46.2.2 of IEEE Std 802.1Q-2022).
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct DataFrameSpecificationElement {
     /// This index is provided in order to provide a unique key per
     /// list entry. The value of index for each entry shall be unique
     /// (but not necessarily contiguous).
     pub index: u8,
+
+    #[serde(flatten)]
     pub field: DataFrameSpecificationElementType,
 }
 
@@ -690,6 +717,7 @@ pub struct DataFrameSpecificationElement {
 /// interval where N is the smallest integer for which the
 /// relation StartOfNextInterval >= CurrentTime would be TRUE.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct TrafficSpecificationInterval {
     /// intervals numerator.
     pub numerator: u32,
@@ -714,6 +742,7 @@ pub struct TrafficSpecificationInterval {
 /// Talker, the time-aware container is intended to support
 /// alternate implementations of scheduling.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct TimeAwareContainer {
     /// earliest-transmit-offset specifies the earliest offset
     /// within each interval at which the Talker is capable of
@@ -781,6 +810,7 @@ pub struct TimeAwareContainer {
 /// to the network. The network uses this traffic spec to allocate
 /// resources and adjust queue parameters in Bridges.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct TrafficSpecificationContainer {
     /// This interval specifies the period of time in which the
     /// traffic specification cannot be exceeded. The traffic
@@ -861,6 +891,7 @@ pub struct TrafficSpecificationContainer {
 /// In the fully centralized model of TSN configuration, this grouping
 /// originates from the CUC, and is delivered to the CNC.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupTalker {
     /// Rank of this Stream's configuration relative to other Streams
     /// in the network. This rank is used to determine success/failure
@@ -937,6 +968,7 @@ pub struct GroupTalker {
 /// In the fully centralized model of TSN configuration, this grouping
 /// originates from the CUC, and is delivered to the CNC.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupListener {
     pub index: u32,
 
@@ -976,6 +1008,7 @@ pub struct GroupListener {
 }
 /// This is an enumeration for the status of the Streams Talker.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub enum TalkerStatus {
     /// No Talker detected.
     None = 0,
@@ -990,6 +1023,7 @@ pub enum TalkerStatus {
 /// This is an enumeration for the status of the Streams
 /// Listener(s).
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub enum ListenerStatus {
     /// No Listener detected.
     None = 0,
@@ -1008,6 +1042,7 @@ pub enum ListenerStatus {
 /// status-info provides information regarding the status of a
 /// Streams configuration in the network.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct StatusInfoContainer {
     /// This is an enumeration for the status of the Streams Talker.
     pub talker_status: TalkerStatus,
@@ -1039,6 +1074,7 @@ pub struct StatusInfoContainer {
 /// group-status-talker-listener - list for Listeners, using
 /// group-status-talker-listener
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupStatusStream {
     /// status-info provides information regarding the status of a
     /// Streams configuration in the network.
@@ -1064,6 +1100,7 @@ pub struct GroupStatusStream {
 /// In the fully centralized model of TSN configuration, this grouping
 /// originates from the CNC, and is delivered to the CUC.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct GroupStatusTalkerListener {
     /// accumulated-latency provides the worst-case maximum latency
     /// that a single frame of the Stream can encounter along its
@@ -1117,7 +1154,7 @@ pub struct GroupStatusTalkerListener {
 /// This packet is only viable for the specific b&r switch used in this paper. Since this Object is not (yet) present in the official IEEE Standard for TSN.
 ///
 /// A list containing a set of the bridge port delays for every available port speed.
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct BridgePortDelays {
     /// Bridge port speed in Megabits per second (Mb/s).
     pub port_speed: u32,
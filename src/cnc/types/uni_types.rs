@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 /// Top-level container for the TSN UNI module.
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
 pub struct TsnUni {
     /// List of Configuration Domains.
     ///
@@ -18,6 +19,7 @@ pub struct TsnUni {
 /// Domain they are located in and can be used to restrict access to
 /// CUCs, e.g., by using standard mechanism as described in RFC 8341.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct Domain {
     /// The Domain ID is a unique identifier of a Configuration
     /// Domain. It is used to identify the Configuration Domain a CUC
@@ -46,6 +48,7 @@ pub struct Domain {
 /// Streams, e.g., by using standard mechanisms as described in RFC
 /// 8341.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct Cuc {
     /// The CUC ID is a unique identifier of a CUC. It is used to
     /// identify the CUC that a Stream belongs to, i.e., that
@@ -67,6 +70,7 @@ pub struct Cuc {
 /// The stream-status indicates what status the Stream has in
 /// the CNC.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub enum StreamStatus {
     /// The Stream has been requested but has not yet been
     /// configured by the CNC.
@@ -91,6 +95,7 @@ pub enum StreamStatus {
 /// to the CNC, while the configuration originates from the CNC
 /// and is delivered to the CUC.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct Stream {
     /// The Stream ID is a unique identifier of a Stream request
     /// and corresponding configuration. It is used to associate a
@@ -111,6 +116,8 @@ pub struct Stream {
     /// requirements from the network - TSN capabilities of the
     /// Listener’s interface(s).
     pub listener: Vec<Listener>,
+
+    #[serde(flatten)]
     pub group_status_stream: tsn_types::GroupStatusStream,
 }
 
@@ -119,8 +126,12 @@ pub struct Stream {
 /// the network - TSN capabilities of the Talker’s
 /// interface(s).
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct Talker {
+    #[serde(flatten)]
     pub group_talker: tsn_types::GroupTalker,
+
+    #[serde(flatten)]
     pub group_status_talker_listener: tsn_types::GroupStatusTalkerListener,
 }
 
@@ -128,11 +139,14 @@ pub struct Talker {
 /// requirements from the network - TSN capabilities of the
 /// Listener’s interface(s).
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub struct Listener {
-    /// This index is provided in order to provide a unique key
-    /// per list entry.
-    pub index: u32,
+    /// The index of the group-listener is the key of the list
+    /// entry.
+    #[serde(flatten)]
     pub group_listener: tsn_types::GroupListener,
+
+    #[serde(flatten)]
     pub group_status_talker_listener: tsn_types::GroupStatusTalkerListener,
 }
 
@@ -158,12 +172,16 @@ pub struct Listener {
 /// that are in the domain provided by domain-id and are associated
 /// with the CUC provided by cuc-id.
 pub mod compute_streams {
+    use serde::{Deserialize, Serialize};
+
     /// List of Configuration Domains.
     ///
     /// This list exists so CUCs can be associated with the
     /// Configuration Domain they are located in.
     pub type Input = Vec<Domain>;
 
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
     pub struct Domain {
         /// A unique identifier of a Configuration Domain. It is used to
         /// identify the Configuration Domain a CUC belongs to.
@@ -176,6 +194,8 @@ pub mod compute_streams {
         pub cuc: Vec<CucElement>,
     }
 
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
     pub struct CucElement {
         /// A unique identifier of a CNC. It is used to identify the
         /// CUC that a Streams belong to, i.e., that requested the
@@ -4,19 +4,56 @@ pub mod cnc;
 mod tests {
     use crate::cnc::northbound::{
         MockComputeStreamAdapter, MockInsertStreamAdapter, MockRemoveStreamAdapter,
+        NetconfServerAdapter, NotificationEncoding, NotificationKind, NotificationSubscriptions,
+        RestconfAdapter, UNI_YANG_MODULES,
+    };
+    use crate::cnc::scheduling::{
+        MockTSNScheduler, ResourcePool, SchedulerAdapterInterface, TasScheduler,
+    };
+    use crate::cnc::southbound::client::{DatastoreType, NetconfClient, NetconfClientError};
+    use crate::cnc::southbound::credentials::{CredentialStore, SSHAuthentication};
+    use crate::cnc::southbound::notifications::notification_content;
+    use crate::cnc::southbound::profiles::{DeviceProfile, DEFAULT_PROFILE, PROFILE_DIR};
+    use crate::cnc::southbound::types::{
+        ConnectionError, ModuleCapability, NetconfConnection, YangContexts, YangPaths, YANG_MODULES,
+    };
+    use crate::cnc::southbound::verification::{
+        diff_gate_parameters, verify_gate_parameters, GateVerification,
+    };
+    use crate::cnc::southbound::{
+        deploy_schedule, init_yang_ctx, run_on_nodes_with_timeout, shared_server_yang_ctx,
+        NetconfAdapter, SessionPool, SouthboundAdapterInterface,
     };
-    use crate::cnc::scheduling::MockTSNScheduler;
-    use crate::cnc::southbound::{NetconfAdapter, SouthboundAdapterInterface};
     use crate::cnc::storage::{FileStorage, StorageAdapterInterface};
-    use crate::cnc::topology::MockTopology;
-    use crate::cnc::types::topology::SSHConfigurationParams;
+    use crate::cnc::topology::{
+        LldpTopology, MockTopology, PathComputation, PathMetric, TopologyAdapterInterface,
+    };
+    use crate::cnc::types::bridge_types::{StaticFilteringControl, VlanTransmitted};
+    use crate::cnc::types::failure_codes::{
+        CONFIGURATION_FAILED, INSUFFICIENT_BANDWIDTH, INSUFFICIENT_BRIDGE_RESOURCES,
+    };
+    use crate::cnc::types::frer_types::StreamIdentificationType;
+    use crate::cnc::types::notification_types;
+    use crate::cnc::types::sched_types::{
+        ConfigurableGateParameterTableEntry, FramePreemptionStatus, GateControlEntry,
+        GateControlOperation, GateParameterTableEntry, PsfpEntry, StreamGateState,
+    };
+    use crate::cnc::types::topology::{
+        Connection, ConnectionInterface, Port, SSHConfigurationParams, Tree,
+    };
+    use crate::cnc::types::tsn_types::{ConfigValue, DataFrameSpecificationElementType};
     use crate::cnc::types::uni_types::compute_streams::{CucElement, Domain};
-    use crate::cnc::types::uni_types::StreamStatus;
+    use crate::cnc::types::uni_types::{self, StreamStatus};
     use crate::cnc::Cnc;
     use serial_test::serial;
+    use std::collections::HashMap;
     use std::fs::File;
     use std::io::prelude::*;
-    use std::sync::Arc;
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use yang2::data::{DataFormat, DataParserFlags, DataTree, DataValidationFlags};
 
     #[test]
     #[serial]
@@ -27,7 +64,7 @@ mod tests {
         let domain: String = String::from("test-domain-id");
 
         // This does only work for the preimplemented Filestorage
-        let bspstorage = r#"[{"domain_id":"test-domain-id","cnc_enabled":true,"cuc":[{"cuc_id":"test-cuc-id","stream":[]}]}]"#;
+        let bspstorage = r#"[{"domain-id":"test-domain-id","cnc-enabled":true,"cuc":[{"cuc-id":"test-cuc-id","stream":[]}]}]"#;
         let mut file = File::create("domain_storage.json").expect("couldnt create file");
         file.write_all(bspstorage.as_bytes())
             .expect("couldnt write to file");
@@ -76,6 +113,22 @@ mod tests {
         drop(storage);
     }
 
+    /// inserts the test streams and reads their domain from the storage, as the CNC does before a
+    /// computation.
+    fn stored_domains() -> Vec<uni_types::Domain> {
+        insert_streams();
+
+        let storage = FileStorage::new();
+        storage.configure_storage();
+        storage.get_streams_in_domain(Domain {
+            domain_id: String::from("test-domain-id"),
+            cuc: vec![CucElement {
+                cuc_id: String::from("test-cuc-id"),
+                stream_list: None,
+            }],
+        })
+    }
+
     #[test]
     #[serial]
     /// This test inserts three streams, removes one and checks if the removed stream is removed correctly.
@@ -196,6 +249,107 @@ mod tests {
         drop(storage);
     }
 
+    #[test]
+    /// This test computes the routes in the mock topology and compares them with the handwritten paths.
+    fn path_computation() {
+        let topology = MockTopology::new_failing().get_topology();
+        let path_computation = PathComputation::new(&topology, PathMetric::HopCount);
+
+        let talker = path_computation.find_end_station("00:00:00:00:00:01");
+        let listener = path_computation.find_end_station("00-00-00-00-00-03");
+        assert_eq!(talker, Some(10));
+        assert_eq!(listener, Some(12));
+
+        // [10] eth0 -> sw0p2 (1) sw0p4 -> sw0p2 (2) sw0p3 -> eth0 [12]
+        let route = path_computation
+            .shortest_path(10, 12)
+            .expect("has to be reachable");
+        let hops: Vec<(u32, Option<&str>, Option<&str>)> = route
+            .hops
+            .iter()
+            .map(|h| {
+                (
+                    h.node_id,
+                    h.ingress_port.as_deref(),
+                    h.egress_port.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            hops,
+            vec![
+                (10, None, Some("eth0")),
+                (1, Some("sw0p2"), Some("sw0p4")),
+                (2, Some("sw0p2"), Some("sw0p3")),
+                (12, Some("eth0"), None),
+            ]
+        );
+
+        let paths = path_computation.compute_all_paths();
+        for expected in topology.paths.as_ref().unwrap().iter() {
+            let path = paths
+                .iter()
+                .find(|p| p.node_a_id == expected.node_a_id && p.node_b_id == expected.node_b_id)
+                .expect("has to be in here");
+            assert_eq!(path.hops, expected.hops);
+        }
+    }
+
+    #[test]
+    /// This test adds a second link from [10] to (2) and checks the disjoint trees from [10] to [12].
+    fn disjoint_trees() {
+        let mut topology = MockTopology::new_failing().get_topology();
+        topology.connections.push(Connection {
+            id: 4,
+            a: ConnectionInterface {
+                node_id: 10,
+                port_name: String::from("eth1"),
+            },
+            b: ConnectionInterface {
+                node_id: 2,
+                port_name: String::from("sw0p4"),
+            },
+        });
+        let path_computation = PathComputation::new(&topology, PathMetric::HopCount);
+
+        let trees = path_computation
+            .disjoint_trees(10, &[12], 3)
+            .expect("has to be reachable");
+
+        // the link from (2) to [12] is shared by both trees. A third tree isn't possible.
+        assert_eq!(trees.len(), 2);
+        let bridges =
+            |tree: &Tree| -> Vec<u32> { tree.routes[0].hops.iter().map(|h| h.node_id).collect() };
+        assert_eq!(bridges(&trees[0]), vec![10, 2, 12]);
+        assert_eq!(bridges(&trees[1]), vec![10, 1, 2, 12]);
+
+        let single = path_computation
+            .disjoint_trees(10, &[11], 2)
+            .expect("has to be reachable");
+        assert_eq!(single.len(), 1);
+    }
+
+    #[test]
+    /// This test compares the two mock topologies. The failing one has the additional bridge (2) and end station [12].
+    fn topology_diff() {
+        let old_topology = MockTopology::new_functioning().get_topology();
+        let new_topology = MockTopology::new_failing().get_topology();
+
+        let changes = old_topology.diff(&new_topology);
+        assert_eq!(changes.nodes.len(), 2);
+        assert!(changes.nodes.contains(&2));
+        assert!(changes.nodes.contains(&12));
+
+        // connections (1) --- (2) and (2) --- [12]
+        assert_eq!(changes.connections.len(), 2);
+        assert!(changes
+            .connections
+            .iter()
+            .all(|c| c.is_attached_to(2, "sw0p2") || c.is_attached_to(2, "sw0p3")));
+
+        assert!(old_topology.diff(&old_topology).is_empty());
+    }
+
     #[test]
     #[serial]
     /// This test inserts three streams, and tests the functionallity of the get_streams_in_domain function.
@@ -222,46 +376,1959 @@ mod tests {
     }
 
     #[test]
-    /// This test checks if the topology is correctly retrieved.
-    /// You have to manually check if the fetched data is correct.
-    fn lldp_neighbours() {
-        let config = SSHConfigurationParams {
-            username: String::from("admin"),
-            password: String::from("admin"),
-            ip: String::from("10.2.0.1"),
-            port: 830,
-        };
+    #[serial]
+    /// This test inserts three streams and schedules them with the TasScheduler in the mock topology.
+    /// All streams have to be placed and every gate control list has to fill the whole cycle.
+    fn tas_schedule() {
+        // create precondition
+        let domains = stored_domains();
 
-        let sb = NetconfAdapter::new();
-        let neighbours = sb.retrieve_lldp(config);
-        // atleast this machine should appear since we communicate with the switch.
-        // this assumes this machine has lldpd installed
-        assert!(neighbours.len() > 0);
+        let topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 0);
+        assert!(result.schedule.configs.len() > 0);
 
-        println!("Check manually if the neighbours are correct");
-        for neighbour in neighbours.iter() {
-            println!("{:?}", neighbour);
+        for config in result.schedule.configs.iter() {
+            // only bridges are configured
+            assert!(config.node_id < 10);
+
+            // ingress ports without scheduled streams only police the streams
+            let gate_params = &config.port.config;
+            if !gate_params.gate_enable {
+                assert!(config.port.psfp.len() > 0);
+                continue;
+            }
+
+            // 1 ms is the hyperperiod of the mock streams
+            assert_eq!(gate_params.admin_cycle_time, (1, 1000));
+            let cycle_time = 1_000_000;
+            let gcl_time: u32 = gate_params
+                .admin_control_list
+                .iter()
+                .map(|e| e.time_interval_value)
+                .sum();
+            assert_eq!(gcl_time, cycle_time);
+        }
+
+        for stream in result.domains[0].cuc[0].stream.iter() {
+            let max_latency = stream
+                .talker
+                .group_talker
+                .user_to_network_requirements
+                .max_latency;
+            for listener in stream.listener.iter() {
+                let latency = listener.group_status_talker_listener.accumulated_latency;
+                assert!(latency != 0 && latency <= max_latency);
+            }
         }
     }
 
     #[test]
-    /// This test checks if the port capabilities are correctly retrieved.
-    /// You have to manually check if the fetched data is correct.
-    fn port_capabilities() {
-        let config = SSHConfigurationParams {
-            username: String::from("admin"),
-            password: String::from("admin"),
-            ip: String::from("10.2.0.1"),
-            port: 830,
-        };
+    #[serial]
+    /// This test stores the configs of a schedule. Every port of a node has its own config.
+    /// Afterwards the configs of one node are removed, the others stay stored.
+    fn config_storage() {
+        // create precondition
+        let domains = stored_domains();
 
-        let sb = NetconfAdapter::new();
-        let delays = sb.retrieve_station_capibilities(config);
-        assert!(delays.len() > 0);
+        let storage = FileStorage::new();
+        storage.configure_storage();
 
-        println!("Check manuall if the data is correct");
-        for delay in delays.iter() {
-            println!("{:?}", delay);
+        let topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        let configs = &result.schedule.configs;
+        assert!(configs.iter().any(|c| configs
+            .iter()
+            .any(|o| o.node_id == c.node_id && o.port.name != c.port.name)));
+
+        storage.set_configs(configs);
+        assert!(storage.get_all_configs().len() >= configs.len());
+        for config in configs.iter() {
+            let stored = storage
+                .get_config(config.node_id, &config.port.name)
+                .unwrap();
+            assert_eq!(stored.port.name, config.port.name);
+        }
+
+        let (removed, kept): (Vec<_>, Vec<_>) =
+            configs.iter().cloned().partition(|c| c.node_id == 1);
+        assert!(!removed.is_empty() && !kept.is_empty());
+        storage.remove_configs(&removed);
+        for config in removed.iter() {
+            assert!(storage
+                .get_config(config.node_id, &config.port.name)
+                .is_none());
+        }
+        for config in kept.iter() {
+            assert!(storage
+                .get_config(config.node_id, &config.port.name)
+                .is_some());
+        }
+        drop(storage);
+    }
+
+    #[test]
+    #[serial]
+    /// This test computes the streams for bridges that can't be configured. The configs of the
+    /// failed deployment aren't stored, so the drift check doesn't compare the bridges with them.
+    fn failed_deployment_configs() {
+        // create precondition
+        insert_streams();
+        let mut file = File::create("config_storage.json").expect("couldnt create file");
+        file.write_all(b"{}").expect("couldnt write to file");
+
+        Cnc::run(
+            0,
+            String::from("test-domain-id"),
+            Arc::new(MockComputeStreamAdapter::new(String::from("test-cuc-id"))),
+            Arc::new(NetconfAdapter::new()),
+            Arc::new(FileStorage::new()),
+            Arc::new(MockTopology::new_failing()),
+            Arc::new(TasScheduler::new()),
+        );
+
+        let storage = FileStorage::new();
+        storage.configure_storage();
+        assert!(storage.get_all_configs().is_empty());
+        drop(storage);
+    }
+
+    #[test]
+    #[serial]
+    /// This test stores the stream handles and loads them again, so the streams keep their handles
+    /// in the PSFP and FRER functions of the bridges after a restart of the CNC.
+    fn stream_handle_storage() {
+        // create precondition
+        insert_streams();
+
+        let storage = FileStorage::new();
+        storage.configure_storage();
+        let handles: HashMap<String, u32> = [
+            (String::from("00-00-00-00-00-01:00-01"), 1),
+            (String::from("00-00-00-00-00-01:00-02"), 3),
+        ]
+        .into_iter()
+        .collect();
+        storage.set_stream_handles(&handles);
+        drop(storage);
+
+        let storage = FileStorage::new();
+        storage.configure_storage();
+        assert_eq!(storage.get_stream_handles(), handles);
+
+        storage.set_stream_handles(&HashMap::new());
+        drop(storage);
+    }
+
+    #[test]
+    #[serial]
+    /// This test releases the resources of a failed stream. Its stream handle is removed from the
+    /// storage, while the handles of the other streams are kept.
+    fn release_stream_resources() {
+        // create precondition
+        insert_streams();
+
+        let storage = FileStorage::new();
+        storage.configure_storage();
+        let handles: HashMap<String, u32> = [
+            (String::from("00-00-00-00-00-01:00-01"), 1),
+            (String::from("00-00-00-00-00-01:00-02"), 2),
+        ]
+        .into_iter()
+        .collect();
+        storage.set_stream_handles(&handles);
+
+        storage.release_stream_resources(
+            &String::from("test-domain-id"),
+            &vec![String::from("00-00-00-00-00-01:00-01")],
+        );
+        let remaining = storage.get_stream_handles();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining.get("00-00-00-00-00-01:00-02"), Some(&2));
+
+        storage.set_stream_handles(&HashMap::new());
+        drop(storage);
+    }
+
+    #[test]
+    #[serial]
+    /// This test limits the supported-cycle-max of bridge (1) below the stream intervals.
+    /// All streams cross the bridge, so all of them have to fail.
+    ///
+    /// Afterwards two streams with intervals of 3 s and 2 s share the egress ports towards
+    /// 00-00-00-00-00-03. Their hyperperiod of 6 s doesn't fit into the 32 bit time-interval-values
+    /// of the gate control list, so the second one has to fail.
+    fn tas_cycle_limits() {
+        // create precondition
+        let mut domains = stored_domains();
+
+        let mut topology = MockTopology::new_failing().get_topology();
+        let port_names: Vec<String> = topology
+            .connections
+            .iter()
+            .filter(|c| c.a.node_id == 1 || c.b.node_id == 1)
+            .map(|c| if c.a.node_id == 1 { &c.a } else { &c.b })
+            .map(|i| i.port_name.clone())
+            .collect();
+        let bridge = topology.nodes.iter_mut().find(|n| n.id == 1).unwrap();
+        for name in port_names {
+            bridge.ports.push(Port {
+                name,
+                mac_address: String::new(),
+                delays: Vec::new(),
+                tick_granularity: 0,
+                port_number: 0,
+                supported_list_max: 0,
+                supported_cycle_max: (1, 2000),
+            });
+        }
+
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 3);
+        assert!(result
+            .failed_streams
+            .iter()
+            .all(|f| f.failure_code == INSUFFICIENT_BRIDGE_RESOURCES));
+
+        // hyperperiod over 4.29 s
+        let long_streams = [
+            (String::from("00-00-00-00-00-01:00-02"), 3),
+            (String::from("00-00-00-00-00-02:00-03"), 2),
+        ];
+        for stream in domains[0].cuc[0].stream.iter_mut() {
+            if let Some((_, seconds)) = long_streams.iter().find(|(id, _)| *id == stream.stream_id)
+            {
+                let interval = &mut stream.talker.group_talker.traffic_specification.interval;
+                interval.numerator = *seconds;
+                interval.denominator = 1;
+            }
+        }
+
+        let topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 1);
+        assert!(long_streams
+            .iter()
+            .any(|(id, _)| *id == result.failed_streams[0].stream_id));
+        assert_eq!(
+            result.failed_streams[0].failure_code,
+            INSUFFICIENT_BRIDGE_RESOURCES
+        );
+        for config in result.schedule.configs.iter() {
+            if !config.port.config.gate_enable {
+                continue; // ingress port without scheduled streams
+            }
+
+            let (numerator, denominator) = config.port.config.admin_cycle_time;
+            assert!(numerator as u64 * 1_000_000_000 / denominator as u64 <= u32::MAX as u64);
+        }
+    }
+
+    #[test]
+    #[serial]
+    /// This test gives the first stream an interval of 5 s. The time-interval-values of its stream
+    /// gates don't fit into 32 bit ns, so it has to fail instead of being configured with truncated
+    /// values.
+    fn stream_gate_limits() {
+        // create precondition
+        let mut domains = stored_domains();
+
+        let stream = &mut domains[0].cuc[0].stream[0];
+        let interval = &mut stream.talker.group_talker.traffic_specification.interval;
+        interval.numerator = 5;
+        interval.denominator = 1;
+        let stream_id = stream.stream_id.clone();
+
+        let topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 1);
+        assert_eq!(result.failed_streams[0].stream_id, stream_id);
+        assert_eq!(
+            result.failed_streams[0].failure_code,
+            INSUFFICIENT_BRIDGE_RESOURCES
+        );
+    }
+
+    #[test]
+    #[serial]
+    /// This test schedules the mock streams with frame preemption. The traffic class of the streams
+    /// (PCP 6) has to be express and the MAC has to be held and released in the gate control lists.
+    fn tas_frame_preemption() {
+        // create precondition
+        let domains = stored_domains();
+
+        let topology = MockTopology::new_failing().get_topology();
+        let result = TasScheduler::new_with_frame_preemption().compute_schedule(
+            &topology,
+            PathMetric::HopCount,
+            &domains,
+        );
+        assert_eq!(result.failed_streams.len(), 0);
+
+        for config in result.schedule.configs.iter() {
+            if !config.port.config.gate_enable {
+                continue; // ingress port without scheduled streams
+            }
+
+            let frame_preemption = &config.port.frame_preemption;
+            assert_eq!(frame_preemption.len(), 8);
+            for entry in frame_preemption.iter() {
+                let expected = match entry.traffic_class {
+                    6 => FramePreemptionStatus::Express,
+                    _ => FramePreemptionStatus::Preemptable,
+                };
+                assert_eq!(entry.frame_preemption_status, expected);
+            }
+
+            let operations: Vec<&GateControlOperation> = config
+                .port
+                .config
+                .admin_control_list
+                .iter()
+                .map(|e| &e.operation_name)
+                .collect();
+            assert!(operations.contains(&&GateControlOperation::SetAndHoldMAC));
+            assert!(operations.contains(&&GateControlOperation::SetAndReleaseMAC));
+            assert!(!operations.contains(&&GateControlOperation::SetGateStates));
+        }
+    }
+
+    #[test]
+    #[serial]
+    /// This test shapes the mock streams with the credit-based shaper. Every port has to reserve the
+    /// bandwidth of all streams crossing it. With 100 full-sized frames per ms, the port speed is
+    /// exceeded and all streams have to fail.
+    fn cbs_admission() {
+        // create precondition
+        let mut domains = stored_domains();
+
+        for stream in domains[0].cuc[0].stream.iter_mut() {
+            stream
+                .talker
+                .group_talker
+                .traffic_specification
+                .transmission_selection = 1;
+        }
+
+        // (1 byte + 42 bytes overhead) * 8 bit every 1 ms
+        let idle_slope: u64 = 43 * 8 * 1000;
+
+        let topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 0);
+        assert!(result.schedule.configs.len() > 0);
+
+        for config in result.schedule.configs.iter() {
+            assert!(!config.port.config.gate_enable);
+            if config.port.fqtss.is_empty() {
+                assert!(config.port.psfp.len() > 0);
+                continue; // ingress port
+            }
+
+            // streams that are transmitted on the port
+            let egress_streams = result
+                .schedule
+                .trees
+                .values()
+                .filter(|trees| {
+                    trees
+                        .iter()
+                        .flat_map(|t| t.routes.iter())
+                        .flat_map(|r| r.hops.iter())
+                        .any(|h| {
+                            h.node_id == config.node_id
+                                && h.egress_port.as_ref() == Some(&config.port.name)
+                        })
+                })
+                .count();
+
+            assert_eq!(config.port.fqtss.len(), 1);
+            assert_eq!(config.port.fqtss[0].traffic_class, 6);
+            assert_eq!(
+                config.port.fqtss[0].admin_idle_slope,
+                idle_slope * egress_streams as u64
+            );
+        }
+
+        for stream in domains[0].cuc[0].stream.iter_mut() {
+            let traffic_specification = &mut stream.talker.group_talker.traffic_specification;
+            traffic_specification.max_frames_per_interval = 100;
+            traffic_specification.max_frame_size = 1500;
+        }
+
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 3);
+        assert!(result
+            .failed_streams
+            .iter()
+            .all(|f| f.failure_code == INSUFFICIENT_BANDWIDTH));
+    }
+
+    #[test]
+    #[serial]
+    /// This test checks the per-stream filtering and policing of the mock streams. Every bridge
+    /// on the route of a stream has to meter its frames and only open the stream gate within
+    /// the cycle, when the frames are received.
+    fn psfp_policing() {
+        // create precondition
+        let domains = stored_domains();
+
+        let topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 0);
+
+        // every bridge on the route of a stream polices it once
+        let mut expected_entries = 0;
+        for trees in result.schedule.trees.values() {
+            let mut bridges: Vec<u32> = trees
+                .iter()
+                .flat_map(|t| t.routes.iter())
+                .flat_map(|r| r.hops.iter())
+                .map(|h| h.node_id)
+                .filter(|id| *id < 10)
+                .collect();
+            bridges.sort();
+            bridges.dedup();
+            expected_entries += bridges.len();
+        }
+
+        let entries: Vec<&PsfpEntry> = result
+            .schedule
+            .configs
+            .iter()
+            .flat_map(|c| c.port.psfp.iter())
+            .collect();
+        assert_eq!(entries.len(), expected_entries);
+
+        for entry in entries {
+            let filter = &entry.stream_filter;
+            assert_eq!(filter.stream_handle, Some(filter.stream_filter_instance_id));
+            assert_eq!(
+                filter.stream_gate_ref,
+                entry.stream_gate.stream_gate_instance_id
+            );
+            assert_eq!(filter.max_sdu_size, 1);
+
+            // one frame of 1 byte + 42 bytes overhead every 1 ms
+            assert_eq!(entry.flow_meter.committed_information_rate, 43 * 8 * 1000);
+            assert_eq!(entry.flow_meter.committed_burst_size, 43);
+
+            let gate = &entry.stream_gate;
+            assert!(gate.gate_enable);
+            assert_eq!(gate.admin_cycle_time, (1, 1000));
+            let gcl_time: u32 = gate
+                .admin_control_list
+                .iter()
+                .map(|e| e.time_interval_value)
+                .sum();
+            assert_eq!(gcl_time, 1_000_000);
+            assert!(gate
+                .admin_control_list
+                .iter()
+                .any(|e| e.gate_state_value == StreamGateState::Open));
+            assert!(gate
+                .admin_control_list
+                .iter()
+                .any(|e| e.gate_state_value == StreamGateState::Closed));
+        }
+    }
+
+    #[test]
+    #[serial]
+    /// This test adds a second link between (1) and (2) and requests two trees for all mock streams.
+    /// The streams to [12] are replicated on (1) and the duplicates are discarded on (2). The
+    /// stream from [10] to [11] can't have two trees and fails.
+    fn frer_replication() {
+        // create precondition
+        let mut domains = stored_domains();
+
+        for stream in domains[0].cuc[0].stream.iter_mut() {
+            stream
+                .talker
+                .group_talker
+                .user_to_network_requirements
+                .num_seemless_trees = 2;
+        }
+
+        let mut topology = MockTopology::new_failing().get_topology();
+        topology.connections.push(Connection {
+            id: 4,
+            a: ConnectionInterface {
+                node_id: 1,
+                port_name: String::from("sw0p5"),
+            },
+            b: ConnectionInterface {
+                node_id: 2,
+                port_name: String::from("sw0p5"),
+            },
+        });
+
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 1);
+        assert_eq!(
+            result.failed_streams[0].stream_id,
+            "00-00-00-00-00-01:00-01"
+        );
+
+        let replicated = ["sw0p4", "sw0p5"];
+        let (mut generation, mut recovery, mut encode, mut decode) = (0, 0, 0, 0);
+        for config in result.schedule.configs.iter() {
+            let frer = &config.port.frer;
+            generation += frer.sequence_generation.len();
+            recovery += frer.sequence_recovery.len();
+
+            if frer.sequence_generation.len() > 0 {
+                assert_eq!(config.node_id, 1);
+            }
+            for entry in frer.sequence_recovery.iter() {
+                assert_eq!(config.node_id, 2);
+                assert_eq!(entry.port_list, vec![String::from("sw0p3")]);
+            }
+            for entry in frer.sequence_identification.iter() {
+                assert!(replicated.contains(&config.port.name.as_str()));
+                match entry.active {
+                    true => encode += 1,
+                    false => decode += 1,
+                }
+            }
+
+            for entry in config.port.stream_identification.iter() {
+                match &entry.identification {
+                    StreamIdentificationType::NullStream(x) => {
+                        assert_eq!(x.destination_mac, "00-00-00-00-00-03")
+                    }
+                    _ => panic!("the mock streams don't have an IP tuple"),
+                }
+                if config.node_id == 2 {
+                    assert_eq!(entry.in_facing_input_port_list, replicated);
+                }
+            }
+        }
+
+        // two streams, each replicated on two ports
+        assert_eq!(generation, 2);
+        assert_eq!(recovery, 2);
+        assert_eq!(encode, 4);
+        assert_eq!(decode, 4);
+    }
+
+    #[test]
+    #[serial]
+    /// This test adds a second link from [10] to (2) and requests two trees for all mock streams, so
+    /// the trees of the streams from [10] already split at the talker. Only the stream to [12],
+    /// whose talker supports FRER, is replicated. The stream to [11] fails, as does the stream from
+    /// [11], which can't have two trees.
+    fn frer_talker_replication() {
+        // create precondition
+        let mut domains = stored_domains();
+
+        for stream in domains[0].cuc[0].stream.iter_mut() {
+            stream
+                .talker
+                .group_talker
+                .user_to_network_requirements
+                .num_seemless_trees = 2;
+        }
+        let replicating = &mut domains[0].cuc[0].stream[1];
+        replicating
+            .talker
+            .group_talker
+            .interface_capabilities
+            .cb_sequence_type_list = vec![1];
+        let replicating_id = replicating.stream_id.clone();
+
+        let mut topology = MockTopology::new_failing().get_topology();
+        topology.connections.push(Connection {
+            id: 4,
+            a: ConnectionInterface {
+                node_id: 10,
+                port_name: String::from("eth1"),
+            },
+            b: ConnectionInterface {
+                node_id: 2,
+                port_name: String::from("sw0p5"),
+            },
+        });
+
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 2);
+        assert!(result
+            .failed_streams
+            .iter()
+            .all(|f| f.stream_id != replicating_id
+                && f.failure_code == INSUFFICIENT_BRIDGE_RESOURCES));
+    }
+
+    #[test]
+    #[serial]
+    /// This test removes the MAC addresses from the data-frame-specification of the stream from
+    /// [10] to [12]. Without destination MAC address or IP tuple the bridges can't identify it, so
+    /// it has to fail instead of being placed without stream identification.
+    fn unidentified_stream() {
+        // create precondition
+        let mut domains = stored_domains();
+
+        let stream = domains[0].cuc[0]
+            .stream
+            .iter_mut()
+            .find(|s| s.stream_id == "00-00-00-00-00-01:00-02")
+            .unwrap();
+        stream
+            .talker
+            .group_talker
+            .data_frame_specification
+            .retain(|spec| {
+                !matches!(
+                    spec.field,
+                    DataFrameSpecificationElementType::Ieee802MacAddresses(_)
+                )
+            });
+        let stream_id = stream.stream_id.clone();
+
+        let topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 1);
+        assert_eq!(result.failed_streams[0].stream_id, stream_id);
+        assert_eq!(
+            result.failed_streams[0].failure_code,
+            INSUFFICIENT_BRIDGE_RESOURCES
+        );
+        assert!(result
+            .schedule
+            .configs
+            .iter()
+            .all(|c| !c.affected_streams.contains(&stream_id)));
+    }
+
+    #[test]
+    #[serial]
+    /// This test assigns VLAN 10 to the stream from [10] to [12]. Every bridge egress port along the
+    /// trees has to forward the destination MAC addresses of its streams and the ports of (1) and
+    /// (2) on the route to [12] have to be members of VLAN 10.
+    fn static_forwarding() {
+        // create precondition
+        let mut domains = stored_domains();
+
+        let vlan_stream = domains[0].cuc[0]
+            .stream
+            .iter_mut()
+            .find(|s| s.stream_id == "00-00-00-00-00-01:00-02")
+            .unwrap();
+        for spec in vlan_stream
+            .talker
+            .group_talker
+            .data_frame_specification
+            .iter_mut()
+        {
+            if let DataFrameSpecificationElementType::Ieee802VlanTag(tag) = &mut spec.field {
+                tag.vlan_id = 10;
+            }
         }
+
+        let topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        assert_eq!(result.failed_streams.len(), 0);
+
+        for (stream_id, trees) in result.schedule.trees.iter() {
+            let stream = domains[0].cuc[0]
+                .stream
+                .iter()
+                .find(|s| &s.stream_id == stream_id)
+                .unwrap();
+            let destination_mac = stream
+                .talker
+                .group_talker
+                .data_frame_specification
+                .iter()
+                .find_map(|spec| match &spec.field {
+                    DataFrameSpecificationElementType::Ieee802MacAddresses(x) => {
+                        Some(x.destination_mac_adress.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap();
+            let vid = match stream_id.as_str() {
+                "00-00-00-00-00-01:00-02" => 10,
+                _ => 1, // default VLAN
+            };
+
+            for hop in trees
+                .iter()
+                .flat_map(|t| t.routes.iter())
+                .flat_map(|r| r.hops.iter())
+                .filter(|h| h.node_id < 10)
+            {
+                let egress_port = hop.egress_port.as_ref().unwrap();
+                let config = result
+                    .schedule
+                    .configs
+                    .iter()
+                    .find(|c| c.node_id == hop.node_id && &c.port.name == egress_port)
+                    .unwrap();
+                assert!(config.affected_streams.contains(stream_id));
+                assert!(config.port.static_filtering.iter().any(|e| {
+                    e.address == destination_mac
+                        && e.vid == vid
+                        && e.control_element == StaticFilteringControl::Forward
+                }));
+            }
+        }
+
+        let mut members: Vec<(u32, String)> = Vec::new();
+        for config in result.schedule.configs.iter() {
+            for entry in config.port.vlan_registration.iter() {
+                assert_eq!(entry.vid, 10);
+                assert_eq!(entry.vlan_transmitted, VlanTransmitted::Tagged);
+                members.push((config.node_id, config.port.name.clone()));
+            }
+        }
+        members.sort();
+        let expected: Vec<(u32, String)> = [(1, "sw0p2"), (1, "sw0p4"), (2, "sw0p2"), (2, "sw0p3")]
+            .iter()
+            .map(|(node_id, port)| (*node_id, String::from(*port)))
+            .collect();
+        assert_eq!(members, expected);
+    }
+
+    #[test]
+    #[serial]
+    /// This test lets the CNC assign the VLAN tags from a pool with only two VLAN IDs, so the last of
+    /// the three streams fails. The end stations of the first stream support Active Destination MAC
+    /// and VLAN Stream identification and get a destination MAC address of the MAAP range.
+    fn resource_allocation() {
+        // create precondition
+        let mut domains = stored_domains();
+
+        let mac_stream = &mut domains[0].cuc[0].stream[0];
+        mac_stream
+            .talker
+            .group_talker
+            .interface_capabilities
+            .cb_stream_iden_type_list = vec![0x0080_C203];
+        for listener in mac_stream.listener.iter_mut() {
+            listener
+                .group_listener
+                .interface_capabilities
+                .cb_stream_iden_type_list = vec![0x0080_C203];
+        }
+        let mac_stream_id = mac_stream.stream_id.clone();
+        let last_stream_id = domains[0].cuc[0].stream.last().unwrap().stream_id.clone();
+
+        let mut scheduler = TasScheduler::new();
+        scheduler.set_resource_pool(ResourcePool {
+            vlan_ids: 100..=101,
+            ..ResourcePool::default()
+        });
+        let topology = MockTopology::new_failing().get_topology();
+        let result = scheduler.compute_schedule(&topology, PathMetric::HopCount, &domains);
+
+        assert_eq!(result.failed_streams.len(), 1);
+        assert_eq!(result.failed_streams[0].stream_id, last_stream_id);
+        assert_eq!(
+            result.failed_streams[0].failure_code,
+            INSUFFICIENT_BRIDGE_RESOURCES
+        );
+
+        let mut vlan_ids: Vec<u16> = Vec::new();
+        for stream in result.domains[0].cuc[0].stream.iter() {
+            if stream.stream_id == last_stream_id {
+                continue;
+            }
+
+            let talker_configs = &stream
+                .talker
+                .group_status_talker_listener
+                .interface_configuration
+                .interface_list[0]
+                .config_list;
+            let vlan_tag = talker_configs
+                .iter()
+                .find_map(|c| match &c.config_value {
+                    ConfigValue::Ieee802VlanTag(x) => Some(x.clone()),
+                    _ => None,
+                })
+                .unwrap();
+            assert!((100..=101).contains(&vlan_tag.vlan_id));
+            assert_eq!(vlan_tag.priority_code_point, 5); // rank 1
+            vlan_ids.push(vlan_tag.vlan_id);
+            assert!(talker_configs
+                .iter()
+                .any(|c| matches!(c.config_value, ConfigValue::TimeAwareOffset(_))));
+
+            let destination_mac = talker_configs
+                .iter()
+                .find_map(|c| match &c.config_value {
+                    ConfigValue::Ieee802MacAddresses(x) => Some(x.destination_mac_adress.clone()),
+                    _ => None,
+                })
+                .unwrap();
+            if stream.stream_id == mac_stream_id {
+                assert_eq!(destination_mac, "91-E0-F0-00-00-00");
+            } else {
+                assert!(!destination_mac.starts_with("91-E0-F0"));
+            }
+
+            // the listeners get the same tag, but no time-aware-offset
+            for listener in stream.listener.iter() {
+                let listener_configs = &listener
+                    .group_status_talker_listener
+                    .interface_configuration
+                    .interface_list[0]
+                    .config_list;
+                assert!(listener_configs.iter().any(|c| matches!(
+                    &c.config_value,
+                    ConfigValue::Ieee802VlanTag(x) if x.vlan_id == vlan_tag.vlan_id
+                )));
+                assert!(!listener_configs
+                    .iter()
+                    .any(|c| matches!(c.config_value, ConfigValue::TimeAwareOffset(_))));
+            }
+
+            // the bridges forward the stream in the assigned VLAN
+            for hop in result.schedule.trees[&stream.stream_id]
+                .iter()
+                .flat_map(|t| t.routes.iter())
+                .flat_map(|r| r.hops.iter())
+                .filter(|h| h.node_id < 10)
+            {
+                let egress_port = hop.egress_port.as_ref().unwrap();
+                let config = result
+                    .schedule
+                    .configs
+                    .iter()
+                    .find(|c| c.node_id == hop.node_id && &c.port.name == egress_port)
+                    .unwrap();
+                assert!(config
+                    .port
+                    .static_filtering
+                    .iter()
+                    .any(|e| e.address == destination_mac && e.vid == vlan_tag.vlan_id));
+            }
+        }
+        vlan_ids.sort();
+        assert_eq!(vlan_ids, vec![100, 101]);
+    }
+
+    #[test]
+    /// This test checks if the topology is correctly retrieved.
+    /// You have to manually check if the fetched data is correct.
+    fn lldp_neighbours() {
+        let config = SSHConfigurationParams {
+            credentials: None,
+            profile: None,
+            ip: String::from("10.2.0.1"),
+            port: 830,
+        };
+
+        let sb = NetconfAdapter::new();
+        let neighbours = sb.retrieve_lldp(config);
+        // atleast this machine should appear since we communicate with the switch.
+        // this assumes this machine has lldpd installed
+        assert!(neighbours.len() > 0);
+
+        println!("Check manually if the neighbours are correct");
+        for neighbour in neighbours.iter() {
+            println!("{:?}", neighbour);
+        }
+    }
+
+    #[test]
+    /// This test checks if the port capabilities are correctly retrieved.
+    /// You have to manually check if the fetched data is correct.
+    fn port_capabilities() {
+        let config = SSHConfigurationParams {
+            credentials: None,
+            profile: None,
+            ip: String::from("10.2.0.1"),
+            port: 830,
+        };
+
+        let sb = NetconfAdapter::new();
+        let delays = sb.retrieve_station_capibilities(config);
+        assert!(delays.len() > 0);
+
+        println!("Check manuall if the data is correct");
+        for delay in delays.iter() {
+            println!("{:?}", delay);
+        }
+    }
+
+    #[test]
+    /// This test parses the capabilities of a <hello>-Message. With the default profile, the
+    /// advertised revision of ieee802-dot1q-sched is used with the features needed by the CNC and
+    /// missing models of the CNC are added. The B&R profile pins the revisions of its models
+    /// instead.
+    fn yang_capabilities() {
+        assert_eq!(
+            ModuleCapability::parse("urn:ietf:params:netconf:base:1.1"),
+            None
+        );
+        assert_eq!(
+            ModuleCapability::parse(
+                "urn:ietf:params:xml:ns:yang:ietf-interfaces?module=ietf-interfaces&amp;revision=2018-02-20&amp;features=if-mib,arbitrary-names"
+            ),
+            Some(ModuleCapability {
+                name: String::from("ietf-interfaces"),
+                revision: Some(String::from("2018-02-20")),
+                features: vec![String::from("if-mib"), String::from("arbitrary-names")],
+            })
+        );
+        // the name and revision end up in the file name of the downloaded model
+        assert_eq!(
+            ModuleCapability::parse("urn:example?module=../../etc/passwd&revision=2018-02-20"),
+            None
+        );
+        assert_eq!(
+            ModuleCapability::parse("urn:example?module=example&revision=2018-02-20/../../x"),
+            None
+        );
+
+        let capabilities = vec![
+            String::from("urn:ietf:params:netconf:base:1.1"),
+            String::from("urn:ietf:params:xml:ns:yang:ietf-interfaces?module=ietf-interfaces&revision=2018-02-20&features=if-mib"),
+            String::from("urn:ieee:std:802.1Q:yang:ieee802-dot1q-sched?module=ieee802-dot1q-sched&revision=2021-04-09"),
+            String::from("urn:example:vendor?module=vendor-system"),
+        ];
+        let profiles = DeviceProfile::load_profiles(PROFILE_DIR);
+        let profile = DeviceProfile::select(&profiles, None, &capabilities);
+        assert_eq!(profile.name, DEFAULT_PROFILE);
+        let yang_modules = profile.yang_modules(&capabilities);
+
+        for module in YANG_MODULES {
+            assert_eq!(
+                yang_modules
+                    .iter()
+                    .filter(|m| m.name == module.name)
+                    .count(),
+                1
+            );
+        }
+        let sched = yang_modules
+            .iter()
+            .find(|m| m.name == "ieee802-dot1q-sched")
+            .unwrap();
+        assert_eq!(sched.revision, Some(String::from("2021-04-09")));
+        assert_eq!(sched.features, vec![String::from("scheduled-traffic")]);
+        let vendor = yang_modules
+            .iter()
+            .find(|m| m.name == "vendor-system")
+            .unwrap();
+        assert_eq!(vendor.revision, None);
+
+        let profile = DeviceProfile::select(&profiles, Some(&String::from("br")), &capabilities);
+        assert_eq!(profile.name, "br");
+        let yang_modules = profile.yang_modules(&capabilities);
+        let sched = yang_modules
+            .iter()
+            .find(|m| m.name == "ieee802-dot1q-sched")
+            .unwrap();
+        assert_eq!(sched.revision, Some(String::from("2018-09-11")));
+
+        // an unknown profile falls back to the detection
+        let profile =
+            DeviceProfile::select(&profiles, Some(&String::from("unknown")), &capabilities);
+        assert_eq!(profile.name, DEFAULT_PROFILE);
+    }
+
+    #[test]
+    /// This test parses a device profile that only provides the paths that differ from the default
+    /// ones.
+    fn device_profiles() {
+        let profile = DeviceProfile::from_json(
+            r#"{
+                "name": "example",
+                "namespace": "urn:example:vendor",
+                "modules": [{ "name": "ietf-interfaces", "revision": "2018-02-20" }],
+                "params": { "gate_enabled": "example-gate-enabled" }
+            }"#,
+        )
+        .expect("valid profile");
+
+        assert_eq!(profile.namespace, Some(String::from("urn:example:vendor")));
+        assert!(profile.needs_module("ietf-interfaces"));
+        assert!(!profile.needs_module("ieee802-dot1q-sched"));
+        assert!(!profile.pin_revisions);
+        assert_eq!(profile.params.gate_enabled, "example-gate-enabled");
+
+        let default_profile = DeviceProfile::default();
+        assert_eq!(
+            profile.params.admin_control_list,
+            default_profile.params.admin_control_list
+        );
+        assert_eq!(
+            profile.filters.remote_systems_data,
+            default_profile.filters.remote_systems_data
+        );
+
+        let capabilities = vec![String::from(
+            "urn:example:vendor?module=example-system&revision=2024-01-01",
+        )];
+        let profiles = vec![default_profile, profile];
+        let selected = DeviceProfile::select(&profiles, None, &capabilities);
+        assert_eq!(selected.name, "example");
+    }
+
+    #[test]
+    /// This test compares deployed gate-parameters with the operational ones read back from a
+    /// bridge.
+    fn gate_verification() {
+        let config = ConfigurableGateParameterTableEntry {
+            gate_enable: true,
+            admin_gate_states: 255,
+            admin_control_list: vec![
+                GateControlEntry {
+                    operation_name: GateControlOperation::SetGateStates,
+                    time_interval_value: 20000,
+                    gate_state_value: 1,
+                },
+                GateControlEntry {
+                    operation_name: GateControlOperation::SetGateStates,
+                    time_interval_value: 980000,
+                    gate_state_value: 254,
+                },
+            ],
+            admin_cycle_time: (1, 1000),
+            admin_cycle_time_extension: 0,
+            admin_base_time: (0, 0),
+            config_change: true,
+        };
+
+        let mut oper = GateParameterTableEntry::default();
+        oper.gate_enable = true;
+        oper.oper_control_list = config.admin_control_list.clone();
+        oper.oper_cycle_time = (1000000, 1000000000);
+        oper.oper_base_time = (0, 0);
+        assert_eq!(
+            verify_gate_parameters(&config, &oper),
+            GateVerification::Applied
+        );
+
+        oper.config_pending = true;
+        assert_eq!(
+            verify_gate_parameters(&config, &oper),
+            GateVerification::Pending
+        );
+
+        oper.config_pending = false;
+        oper.oper_control_list.pop();
+        assert!(matches!(
+            verify_gate_parameters(&config, &oper),
+            GateVerification::Mismatch(_)
+        ));
+
+        oper.oper_control_list = config.admin_control_list.clone();
+        oper.oper_cycle_time = (1, 2000);
+        assert!(matches!(
+            verify_gate_parameters(&config, &oper),
+            GateVerification::Mismatch(_)
+        ));
+
+        // the admin-base-time is in the past, so the bridge starts with a later cycle
+        oper.oper_cycle_time = (1, 1000);
+        oper.oper_base_time = (1700000000, 2000000);
+        assert_eq!(
+            verify_gate_parameters(&config, &oper),
+            GateVerification::Applied
+        );
+
+        oper.oper_base_time = (1700000000, 2500000);
+        assert!(matches!(
+            verify_gate_parameters(&config, &oper),
+            GateVerification::Mismatch(_)
+        ));
+    }
+
+    #[test]
+    /// This test compares stored gate-parameters with the ones configured on a bridge to detect
+    /// drift.
+    fn gate_parameter_drift() {
+        let expected = ConfigurableGateParameterTableEntry {
+            gate_enable: true,
+            admin_gate_states: 255,
+            admin_control_list: vec![GateControlEntry {
+                operation_name: GateControlOperation::SetGateStates,
+                time_interval_value: 1000000,
+                gate_state_value: 255,
+            }],
+            admin_cycle_time: (1, 1000),
+            admin_cycle_time_extension: 0,
+            admin_base_time: (0, 0),
+            config_change: true,
+        };
+
+        // config-change is only a trigger and the cycle-time is compared as rational
+        let mut actual = expected.clone();
+        actual.config_change = false;
+        actual.admin_cycle_time = (1000000, 1000000000);
+        assert!(diff_gate_parameters(&expected, &actual).is_empty());
+
+        actual.admin_control_list[0].gate_state_value = 1;
+        actual.admin_base_time = (10, 0);
+        assert_eq!(diff_gate_parameters(&expected, &actual).len(), 2);
+
+        // disabled gates don't drift in their other parameters
+        let mut disabled = expected.clone();
+        disabled.gate_enable = false;
+        actual.gate_enable = false;
+        assert!(diff_gate_parameters(&disabled, &actual).is_empty());
+        assert_eq!(diff_gate_parameters(&expected, &actual).len(), 3);
+    }
+
+    #[test]
+    /// This test strips the envelope of NETCONF notifications.
+    fn notification_envelope() {
+        let notification = r#"<notification xmlns="urn:ietf:params:xml:ns:netconf:notification:1.0">
+                <eventTime>2024-01-01T00:00:00Z</eventTime>
+                <netconf-config-change xmlns="urn:ietf:params:xml:ns:yang:ietf-netconf-notifications">
+                    <changed-by><username>admin</username></changed-by>
+                </netconf-config-change>
+            </notification>"#;
+
+        let content = notification_content(notification).unwrap();
+        assert!(content.starts_with("<netconf-config-change"));
+        assert!(content.ends_with("</netconf-config-change>"));
+
+        assert_eq!(notification_content("<notification/>"), None);
+    }
+
+    #[test]
+    /// This test resolves the credentials of bridges by their name, their ip and the default entry
+    /// of the credential file.
+    fn credential_store() {
+        let store = CredentialStore::from_json(
+            r#"{
+                "known_hosts": "./assets/known_hosts",
+                "default": { "username": "admin", "password": "admin" },
+                "nodes": {
+                    "10.2.0.2": { "username": "cnc", "key_file": "./assets/keys/cnc" },
+                    "lab": { "username": "lab", "password": "secret" }
+                }
+            }"#,
+        )
+        .expect("valid credential file");
+
+        let mut config_params = SSHConfigurationParams {
+            ip: String::from("10.2.0.2"),
+            port: 830,
+            credentials: None,
+            profile: None,
+        };
+        let credentials = store.credentials(&config_params).unwrap();
+        assert_eq!(credentials.username, "cnc");
+        assert_eq!(
+            credentials.authentication(),
+            Ok(SSHAuthentication::PublicKey {
+                key_file: "./assets/keys/cnc".into(),
+                passphrase: None,
+            })
+        );
+
+        config_params.credentials = Some(String::from("lab"));
+        let credentials = store.credentials(&config_params).unwrap();
+        assert_eq!(
+            credentials.authentication(),
+            Ok(SSHAuthentication::Password(String::from("secret")))
+        );
+
+        // without an entry, the default is used if the environment doesn't provide credentials
+        if std::env::var("CNC_SSH_USERNAME").is_err() {
+            config_params.ip = String::from("10.2.0.1");
+            config_params.credentials = None;
+            let credentials = store.credentials(&config_params).unwrap();
+            assert_eq!(credentials.username, "admin");
+        }
+    }
+
+    #[test]
+    /// This test discovers the topology starting at the bridge.
+    /// You have to manually check if the discovered nodes and connections are correct.
+    fn lldp_topology() {
+        let config = SSHConfigurationParams {
+            credentials: None,
+            profile: None,
+            ip: String::from("10.2.0.1"),
+            port: 830,
+        };
+
+        let topology = LldpTopology::new(
+            vec![config],
+            Arc::new(NetconfAdapter::new()),
+            Duration::from_secs(30),
+        );
+        assert!(topology.refresh());
+
+        // atleast the bridge and this machine should be discovered
+        let discovered = topology.get_topology();
+        assert!(discovered.nodes.len() > 1);
+        assert!(discovered.connections.len() > 0);
+
+        // nothing changed in between
+        assert!(!topology.refresh());
+
+        println!("Check manually if the topology is correct");
+        for connection in discovered.connections.iter() {
+            println!(
+                "({}) {} --- {} ({})",
+                connection.a.node_id,
+                connection.a.port_name,
+                connection.b.port_name,
+                connection.b.node_id
+            );
+        }
+    }
+
+    /// sends a single HTTP request to the RESTCONF-Northbound and returns status and body
+    fn restconf_request(address: &str, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(address).expect("couldnt connect to RESTCONF server");
+        let request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: {address}\r\nContent-Type: application/yang-data+json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream
+            .write_all(request.as_bytes())
+            .expect("couldnt send request");
+
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .expect("couldnt read response");
+
+        let status: u16 = response
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .expect("no status in response");
+        let body = match response.split_once("\r\n\r\n") {
+            Some((_headers, body)) => body.to_string(),
+            None => String::new(),
+        };
+
+        (status, body)
+    }
+
+    #[test]
+    #[serial]
+    /// This test runs the CNC with the RESTCONF-Northbound, inserts three streams via HTTP and reads them back.
+    fn restconf_interface() {
+        // Configuration for CNC
+        let id: u32 = 0;
+        let domain: String = String::from("test-domain-id");
+        let address: &str = "127.0.0.1:8040";
+        let cuc_path = "/restconf/data/ieee802-dot1q-tsn-config-uni:tsn-uni/domain=test-domain-id/cuc=test-cuc-id";
+
+        // This does only work for the preimplemented Filestorage
+        let bspstorage = r#"[{"domain-id":"test-domain-id","cnc-enabled":true,"cuc":[{"cuc-id":"test-cuc-id","stream":[]}]}]"#;
+        let mut file = File::create("domain_storage.json").expect("couldnt create file");
+        file.write_all(bspstorage.as_bytes())
+            .expect("couldnt write to file");
+
+        // Create needed Components
+        let northbound = RestconfAdapter::new(String::from(address));
+        let southbound = NetconfAdapter::new();
+        let storage = FileStorage::new();
+        let topology = MockTopology::new_functioning();
+        let scheduler = MockTSNScheduler::new();
+
+        thread::spawn(move || {
+            Cnc::run(
+                id,
+                domain,
+                Arc::new(northbound),
+                Arc::new(southbound),
+                Arc::new(storage),
+                Arc::new(topology),
+                Arc::new(scheduler),
+            );
+        });
+
+        // wait for the server to be ready
+        thread::sleep(Duration::from_secs(1));
+
+        // insert streams with the member names of the yang-model
+        let requested_streams =
+            serde_json::json!(MockInsertStreamAdapter::get_example_add_stream());
+        let body = serde_json::json!({ "ieee802-dot1q-tsn-config-uni:stream": requested_streams });
+        assert!(body.to_string().contains("\"num-seamless-trees\""));
+        assert!(!body.to_string().contains("group-talker"));
+        let (status, _) = restconf_request(address, "POST", cuc_path, &body.to_string());
+        assert_eq!(status, 201);
+
+        // read streams
+        let (status, response) = restconf_request(address, "GET", cuc_path, "");
+        assert_eq!(status, 200);
+        let response: serde_json::Value = serde_json::from_str(&response).expect("invalid json");
+        let streams = response["ieee802-dot1q-tsn-config-uni:cuc"][0]["stream"]
+            .as_array()
+            .expect("no streams in response");
+        assert_eq!(streams.len(), 3);
+        assert!(streams[0]["stream-id"].is_string());
+
+        // the requested and the returned streams are valid instances of the yang-model
+        let yang_ctx = init_yang_ctx(&UNI_YANG_MODULES.to_vec());
+        let cuc = &response["ieee802-dot1q-tsn-config-uni:cuc"][0];
+        let requested = serde_json::json!({ "cuc-id": "test-cuc-id", "stream": requested_streams });
+        for cuc in [cuc, &requested] {
+            let tsn_uni = serde_json::json!({
+                "ieee802-dot1q-tsn-config-uni:tsn-uni": {
+                    "domain": [{ "domain-id": "test-domain-id", "cnc-enabled": true, "cuc": [cuc] }]
+                }
+            });
+            DataTree::parse_string(
+                &yang_ctx,
+                &tsn_uni.to_string(),
+                DataFormat::JSON,
+                DataParserFlags::STRICT | DataParserFlags::NO_VALIDATION,
+                DataValidationFlags::empty(),
+            )
+            .expect("the streams don't match the yang-model");
+        }
+
+        // rpc
+        let (status, response) = restconf_request(
+            address,
+            "POST",
+            "/restconf/operations/ieee802-dot1q-tsn-config-uni:request-domain-id",
+            r#"{"ieee802-dot1q-tsn-config-uni:input":{"cuc-id":"test-cuc-id"}}"#,
+        );
+        assert_eq!(status, 200);
+        let response: serde_json::Value = serde_json::from_str(&response).expect("invalid json");
+        assert_eq!(
+            response["ieee802-dot1q-tsn-config-uni:output"]["domain-id"],
+            "test-domain-id"
+        );
+
+        // a too big body is rejected before it is received
+        let mut stream = TcpStream::connect(address).expect("couldnt connect to RESTCONF server");
+        stream
+            .write_all(
+                format!("POST {cuc_path} HTTP/1.1\r\nContent-Length: 1073741824\r\n\r\n")
+                    .as_bytes(),
+            )
+            .expect("couldnt send request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .expect("couldnt read response");
+        assert!(response.starts_with("HTTP/1.1 413"));
+    }
+
+    /// sends a message with the NETCONF 1.0 framing and returns the next received message
+    fn netconf_exchange(stream: &mut TcpStream, message: &str) -> String {
+        stream
+            .write_all(format!("{message}]]>]]>").as_bytes())
+            .expect("couldnt send message");
+        netconf_receive(stream)
+    }
+
+    fn netconf_receive(stream: &mut TcpStream) -> String {
+        let mut received: Vec<u8> = Vec::new();
+        let mut byte = [0u8; 1];
+        while !received.ends_with(b"]]>]]>") {
+            stream.read_exact(&mut byte).expect("couldnt read message");
+            received.push(byte[0]);
+        }
+
+        String::from_utf8_lossy(&received[..received.len() - 6]).to_string()
+    }
+
+    #[test]
+    #[serial]
+    /// This test runs the CNC with the NETCONF-Northbound, inserts a stream via <edit-config> and requests the domain-id.
+    fn netconf_server_interface() {
+        // Configuration for CNC
+        let id: u32 = 0;
+        let domain: String = String::from("test-domain-id");
+        let address: &str = "127.0.0.1:8830";
+
+        // This does only work for the preimplemented Filestorage
+        let bspstorage = r#"[{"domain-id":"test-domain-id","cnc-enabled":true,"cuc":[{"cuc-id":"test-cuc-id","stream":[]}]}]"#;
+        let mut file = File::create("domain_storage.json").expect("couldnt create file");
+        file.write_all(bspstorage.as_bytes())
+            .expect("couldnt write to file");
+
+        // Create needed Components
+        let northbound = NetconfServerAdapter::new(String::from(address));
+        let southbound = NetconfAdapter::new();
+        let storage = FileStorage::new();
+        let topology = MockTopology::new_functioning();
+        let scheduler = MockTSNScheduler::new();
+
+        thread::spawn(move || {
+            Cnc::run(
+                id,
+                domain,
+                Arc::new(northbound),
+                Arc::new(southbound),
+                Arc::new(storage),
+                Arc::new(topology),
+                Arc::new(scheduler),
+            );
+        });
+
+        // wait for the server to be ready
+        thread::sleep(Duration::from_secs(1));
+
+        let mut stream = TcpStream::connect(address).expect("couldnt connect to NETCONF server");
+        let hello = netconf_receive(&mut stream);
+        assert!(hello.contains("ieee802-dot1q-tsn-config-uni"));
+
+        stream
+            .write_all(
+                br#"<hello xmlns="urn:ietf:params:xml:ns:netconf:base:1.0"><capabilities><capability>urn:ietf:params:netconf:base:1.0</capability></capabilities></hello>]]>]]>"#,
+            )
+            .expect("couldnt send hello");
+
+        // insert stream
+        let edit_config = r#"<rpc message-id="1" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0">
+            <edit-config>
+                <target><running/></target>
+                <config>
+                    <tsn-uni xmlns="urn:ieee:std:802.1Q:yang:ieee802-dot1q-tsn-config-uni">
+                        <domain>
+                            <domain-id>test-domain-id</domain-id>
+                            <cuc>
+                                <cuc-id>test-cuc-id</cuc-id>
+                                <stream>
+                                    <stream-id>00-00-00-00-00-01:00-01</stream-id>
+                                    <talker>
+                                        <end-station-interfaces>
+                                            <mac-address>00-00-00-00-00-01</mac-address>
+                                            <interface-name>eth0</interface-name>
+                                        </end-station-interfaces>
+                                        <traffic-specification>
+                                            <interval>
+                                                <numerator>1000000</numerator>
+                                                <denominator>1000000000</denominator>
+                                            </interval>
+                                            <max-frames-per-interval>1</max-frames-per-interval>
+                                            <max-frame-size>1</max-frame-size>
+                                        </traffic-specification>
+                                    </talker>
+                                    <listener>
+                                        <index>0</index>
+                                        <end-station-interfaces>
+                                            <mac-address>00-00-00-00-00-02</mac-address>
+                                            <interface-name>eth1</interface-name>
+                                        </end-station-interfaces>
+                                    </listener>
+                                </stream>
+                            </cuc>
+                        </domain>
+                    </tsn-uni>
+                </config>
+            </edit-config>
+        </rpc>"#;
+        let reply = netconf_exchange(&mut stream, edit_config);
+        assert!(reply.contains("<ok/>"));
+
+        let storage = FileStorage::new();
+        storage.configure_storage();
+        let domain = storage.get_streams_in_domain(Domain {
+            domain_id: String::from("test-domain-id"),
+            cuc: vec![CucElement {
+                cuc_id: String::from("test-cuc-id"),
+                stream_list: None,
+            }],
+        });
+        assert_eq!(domain[0].cuc[0].stream.len(), 1);
+
+        // a stream without interval is rejected
+        let interval = r#"<interval>
+                                                <numerator>1000000</numerator>
+                                                <denominator>1000000000</denominator>
+                                            </interval>"#;
+        let edit_config = edit_config
+            .replace(interval, "")
+            .replace("00-00-00-00-00-01:00-01", "00-00-00-00-00-01:00-02");
+        let reply = netconf_exchange(&mut stream, &edit_config);
+        assert!(reply.contains("missing-element"));
+
+        let storage = FileStorage::new();
+        storage.configure_storage();
+        let domain = storage.get_streams_in_domain(Domain {
+            domain_id: String::from("test-domain-id"),
+            cuc: vec![CucElement {
+                cuc_id: String::from("test-cuc-id"),
+                stream_list: None,
+            }],
+        });
+        assert_eq!(domain[0].cuc[0].stream.len(), 1);
+
+        // rpc
+        let request_domain_id = r#"<rpc message-id="2" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0">
+            <request-domain-id xmlns="urn:ieee:std:802.1Q:yang:ieee802-dot1q-tsn-config-uni">
+                <cuc-id>test-cuc-id</cuc-id>
+            </request-domain-id>
+        </rpc>"#;
+        let reply = netconf_exchange(&mut stream, request_domain_id);
+        assert!(reply.contains("test-domain-id"));
+
+        let close_session = r#"<rpc message-id="3" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0"><close-session/></rpc>"#;
+        let reply = netconf_exchange(&mut stream, close_session);
+        assert!(reply.contains("<ok/>"));
+
+        // a session that sends a message without end is closed
+        let mut stream = TcpStream::connect(address).expect("couldnt connect to NETCONF server");
+        netconf_receive(&mut stream);
+        let _ = stream.write_all(&vec![b'a'; 5 * 1024 * 1024]);
+        let mut byte = [0u8; 1];
+        assert!(!matches!(stream.read(&mut byte), Ok(read) if read > 0));
+    }
+
+    #[test]
+    #[serial]
+    /// This test publishes a notification while one CUC is disconnected and another never subscribed and checks if both get delivered after subscribing.
+    fn notification_subscriptions() {
+        let subscriptions = NotificationSubscriptions::new();
+
+        let (session, receiver) = subscriptions.subscribe("test-cuc-id");
+        drop(receiver);
+        subscriptions.disconnect("test-cuc-id", session, None);
+
+        subscriptions.publish(
+            NotificationKind::ComputeStreamsCompleted,
+            vec![notification_types::Domain {
+                domain_id: String::from("test-domain-id"),
+                cucs: vec![
+                    notification_types::Cuc {
+                        cuc_id: String::from("test-cuc-id"),
+                        streams: vec![notification_types::Stream {
+                            stream_id: String::from("00-00-00-00-00-01:00-01"),
+                            failure_code: 0,
+                        }],
+                    },
+                    notification_types::Cuc {
+                        cuc_id: String::from("other-cuc-id"),
+                        streams: vec![notification_types::Stream {
+                            stream_id: String::from("00-00-00-00-00-01:00-02"),
+                            failure_code: 1,
+                        }],
+                    },
+                ],
+            }],
+        );
+
+        // the buffered notification gets delivered and contains only the streams of this cuc
+        let (_, receiver) = subscriptions.subscribe("test-cuc-id");
+        let mut notification = receiver.try_recv().expect("notification wasnt buffered");
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(notification.cuc.streams.len(), 1);
+
+        // a cuc that subscribes for the first time gets the notifications published before
+        let (_, other_receiver) = subscriptions.subscribe("other-cuc-id");
+        let other_notification = other_receiver
+            .try_recv()
+            .expect("notification wasnt buffered");
+        assert_eq!(
+            other_notification.cuc.streams[0].stream_id,
+            "00-00-00-00-00-01:00-02"
+        );
+
+        let json = subscriptions
+            .encode(&notification, NotificationEncoding::Json)
+            .expect("couldnt encode notification");
+        let json: serde_json::Value = serde_json::from_str(&json).expect("invalid json");
+        let content = &json["ietf-restconf:notification"];
+        assert!(content["eventTime"].is_string());
+        assert_eq!(
+            content["ieee802-dot1q-tsn-config-uni:compute-streams-completed"]["domain"][0]["cuc"]
+                [0]["stream"][0]["stream-id"],
+            "00-00-00-00-00-01:00-01"
+        );
+
+        let xml = subscriptions
+            .encode(&notification, NotificationEncoding::Xml)
+            .expect("couldnt encode notification");
+        assert!(xml.contains("<compute-streams-completed"));
+        assert!(xml.contains("<failure-code>0</failure-code>"));
+
+        // ids with quotes are quoted with the other kind, ids with both kinds can't be encoded
+        notification.cuc.cuc_id = String::from("cuc-'1'");
+        let json = subscriptions
+            .encode(&notification, NotificationEncoding::Json)
+            .expect("couldnt encode notification");
+        assert!(json.contains("cuc-'1'"));
+
+        notification.cuc.cuc_id = String::from("cuc-'1\"");
+        assert!(subscriptions
+            .encode(&notification, NotificationEncoding::Xml)
+            .is_err());
+    }
+
+    #[test]
+    #[serial]
+    /// This test starts the CNC without a blocking call and stops it again with the returned handle.
+    fn shutdown_handle() {
+        // Configuration for CNC
+        let id: u32 = 0;
+        let domain: String = String::from("test-domain-id");
+
+        // Create needed Components
+        let northbound = NetconfServerAdapter::new(String::from("127.0.0.1:8831"));
+        let southbound = NetconfAdapter::new();
+        let storage = FileStorage::new();
+        let topology = MockTopology::new_functioning();
+        let scheduler = MockTSNScheduler::new();
+
+        let handle = Cnc::start(
+            id,
+            domain.clone(),
+            Arc::new(northbound),
+            Arc::new(southbound),
+            Arc::new(storage),
+            Arc::new(topology),
+            Arc::new(scheduler),
+        );
+        assert_eq!(handle.cnc().domain, domain);
+
+        // this returns as soon as the main loop stopped
+        handle.shutdown();
+        handle.join();
+    }
+
+    #[test]
+    /// This test shares the yang-context of netconf-servers with the same models and device
+    /// profile, independent of the order in which the models are advertised. A context whose
+    /// models of the profile can't be loaded returns an error and isn't shared.
+    fn shared_yang_contexts() {
+        let yang_contexts: YangContexts = Mutex::new(HashMap::new());
+        let interfaces = ModuleCapability {
+            name: String::from("ietf-interfaces"),
+            revision: Some(String::from("2018-02-20")),
+            features: vec![],
+        };
+        let sched = ModuleCapability {
+            name: String::from("ieee802-dot1q-sched"),
+            revision: None,
+            features: vec![String::from("scheduled-traffic")],
+        };
+        let profile = DeviceProfile::default();
+
+        let ctx = shared_server_yang_ctx(
+            &yang_contexts,
+            &vec![interfaces.clone(), sched.clone()],
+            &profile,
+        )
+        .expect("the yang-models are in ./assets/yang/");
+        let reordered = shared_server_yang_ctx(
+            &yang_contexts,
+            &vec![sched.clone(), interfaces.clone()],
+            &profile,
+        )
+        .expect("the yang-models are in ./assets/yang/");
+        assert!(Arc::ptr_eq(&ctx, &reordered));
+        assert_eq!(yang_contexts.lock().unwrap().len(), 1);
+
+        let mut other_profile = DeviceProfile::default();
+        other_profile.name = String::from("example");
+        let other = shared_server_yang_ctx(
+            &yang_contexts,
+            &vec![interfaces.clone(), sched.clone()],
+            &other_profile,
+        )
+        .expect("the yang-models are in ./assets/yang/");
+        assert!(!Arc::ptr_eq(&ctx, &other));
+        assert_eq!(yang_contexts.lock().unwrap().len(), 2);
+
+        // a model of the profile that isn't available fails the connection instead of panicking
+        let unavailable = ModuleCapability {
+            revision: Some(String::from("1999-01-01")),
+            ..interfaces
+        };
+        assert!(profile.needs_module(&unavailable.name));
+        let result = shared_server_yang_ctx(&yang_contexts, &vec![unavailable, sched], &profile);
+        assert!(matches!(result, Err(ConnectionError::YangModule(_))));
+        assert_eq!(yang_contexts.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    /// This test requests a session to a bridge that isn't reachable. The error is returned and no
+    /// session is kept in the pool.
+    fn session_pool_unreachable_bridge() {
+        let pool = SessionPool::new();
+        let config_params = SSHConfigurationParams {
+            ip: String::from("127.0.0.1"),
+            port: 1,
+            credentials: None,
+            profile: None,
+        };
+
+        assert!(pool.session(&config_params).is_err());
+        assert!(pool.session(&config_params).is_err());
+    }
+
+    #[test]
+    /// This test runs tasks on three nodes, where the second one takes longer than the timeout. Its
+    /// output is missing in the result and passed to abandon(...) when the task finishes.
+    fn node_timeout() {
+        static ABANDONED: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+        fn task(node_id: u32, delay: Duration) -> u32 {
+            thread::sleep(delay);
+            node_id
+        }
+        fn abandon(node_id: u32, output: u32) {
+            assert_eq!(node_id, output);
+            ABANDONED.lock().unwrap().push(node_id);
+        }
+
+        let inputs = vec![
+            (1, Duration::from_millis(10)),
+            (2, Duration::from_millis(500)),
+            (3, Duration::from_millis(10)),
+        ];
+        let outputs = run_on_nodes_with_timeout(inputs, Duration::from_millis(200), task, abandon);
+        assert_eq!(outputs, vec![(1, Some(1)), (2, None), (3, Some(3))]);
+        assert!(ABANDONED.lock().unwrap().is_empty());
+
+        // the timed out task still finishes and is cleaned up
+        thread::sleep(Duration::from_millis(500));
+        assert_eq!(*ABANDONED.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    #[serial]
+    /// This test deploys a schedule to bridges that can't be configured. Nothing is committed and
+    /// every interface of the schedule is reported as failed.
+    fn deployment_rollback() {
+        // create precondition
+        let domains = stored_domains();
+
+        let mut topology = MockTopology::new_failing().get_topology();
+        let result =
+            TasScheduler::new().compute_schedule(&topology, PathMetric::HopCount, &domains);
+        let configs = &result.schedule.configs;
+        assert!(configs.iter().any(|c| c.node_id == 1));
+        assert!(configs.iter().any(|c| c.node_id == 2));
+
+        // the bridges aren't reachable
+        for node in topology.nodes.iter_mut() {
+            if let Some(config_params) = node.configuration_params.as_mut() {
+                config_params.ip = String::from("127.0.0.1");
+                config_params.port = 1;
+            }
+        }
+        let sessions = SessionPool::new();
+        let failed = deploy_schedule(&sessions, &topology, &result.schedule);
+        assert_eq!(failed.interfaces.len(), configs.len());
+        assert!(failed
+            .interfaces
+            .iter()
+            .all(|i| i.failure_code == CONFIGURATION_FAILED));
+
+        // a bridge can't be configured at all, so the others aren't configured either
+        topology
+            .nodes
+            .iter_mut()
+            .find(|n| n.id == 2)
+            .unwrap()
+            .configuration_params = None;
+        let failed = deploy_schedule(&sessions, &topology, &result.schedule);
+        assert_eq!(failed.interfaces.len(), configs.len());
+        for config in configs.iter() {
+            assert!(failed.interfaces.iter().any(|i| i.node_id == config.node_id
+                && i.interface.interface_name == config.port.name
+                && i.failure_code == CONFIGURATION_FAILED));
+        }
+    }
+
+    #[test]
+    #[serial]
+    /// This test deploys a schedule to two scripted bridges. Both candidates are locked, edited and
+    /// committed with <confirmed/>, but the commit of the second bridge fails. The commit of the
+    /// first bridge is cancelled, the candidate of the second one is discarded and both are
+    /// unlocked again.
+    fn deployment_cancel_commit() {
+        // create precondition
+        let domains = stored_domains();
+
+        let mut topology = MockTopology::new_failing().get_topology();
+        let mut schedule = TasScheduler::new()
+            .compute_schedule(&topology, PathMetric::HopCount, &domains)
+            .schedule;
+
+        // one port per bridge, with only the gate-parameters, so no optional yang-model is needed
+        let mut node_ids: Vec<u32> = Vec::new();
+        schedule.configs.retain(|c| {
+            let first = [1, 2].contains(&c.node_id) && !node_ids.contains(&c.node_id);
+            node_ids.push(c.node_id);
+            first
+        });
+        assert_eq!(schedule.configs.len(), 2);
+        for config in schedule.configs.iter_mut() {
+            config.port.fqtss.clear();
+            config.port.psfp.clear();
+            config.port.stream_identification.clear();
+            config.port.frer = Default::default();
+            config.port.static_filtering.clear();
+            config.port.vlan_registration.clear();
+        }
+
+        const OK: &str = r#"<rpc-reply message-id="{id}" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0"><ok/></rpc-reply>"#;
+        const DATA: &str = r#"<rpc-reply message-id="{id}" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0"><data/></rpc-reply>"#;
+        const ERROR: &str = r#"<rpc-reply message-id="{id}" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0"><rpc-error><error-tag>operation-failed</error-tag><error-message>commit failed</error-message></rpc-error></rpc-reply>"#;
+        let capabilities = [
+            "urn:ietf:params:netconf:base:1.0",
+            "urn:ietf:params:netconf:capability:candidate:1.0",
+            "urn:ietf:params:netconf:capability:confirmed-commit:1.1",
+        ];
+
+        // lock, discard-changes, get-config, edit-config, get, commit, roll back and unlock
+        let (address_1, peer_1) =
+            scripted_netconf_peer(&capabilities, vec![OK, OK, DATA, OK, DATA, OK, OK, OK]);
+        let (address_2, peer_2) =
+            scripted_netconf_peer(&capabilities, vec![OK, OK, DATA, OK, DATA, ERROR, OK, OK]);
+
+        let sessions = SessionPool::new();
+        for (node_id, address) in [(1, &address_1), (2, &address_2)] {
+            let (ip, port) = address.split_once(':').unwrap();
+            let config_params = SSHConfigurationParams {
+                ip: ip.to_string(),
+                port: port.parse().unwrap(),
+                credentials: None,
+                profile: None,
+            };
+            sessions.insert_session(config_params.clone(), scripted_netconf_connection(address));
+            topology
+                .nodes
+                .iter_mut()
+                .find(|n| n.id == node_id)
+                .unwrap()
+                .configuration_params = Some(config_params);
+        }
+
+        let failed = deploy_schedule(&sessions, &topology, &schedule);
+        assert_eq!(failed.interfaces.len(), 2);
+        assert!(failed
+            .interfaces
+            .iter()
+            .all(|i| i.failure_code == CONFIGURATION_FAILED));
+
+        let rpcs_1 = peer_1.join().expect("peer of the first bridge failed");
+        let rpcs_2 = peer_2.join().expect("peer of the second bridge failed");
+        for rpcs in [&rpcs_1, &rpcs_2] {
+            assert!(rpcs[0].contains("<lock><target><candidate/></target></lock>"));
+            assert!(rpcs[1].contains("<discard-changes/>"));
+            assert!(rpcs[3].contains("<edit-config><target><candidate/></target>"));
+            assert!(rpcs[5]
+                .contains("<commit><confirmed/><confirm-timeout>120</confirm-timeout></commit>"));
+            assert!(rpcs[7].contains("<unlock><target><candidate/></target></unlock>"));
+        }
+
+        // the first bridge was committed, the second one wasn't
+        assert!(rpcs_1[6].contains("<cancel-commit/>"));
+        assert!(rpcs_2[6].contains("<discard-changes/>"));
+    }
+
+    /// a session to the scripted peer at the address, with the yang-models and paths of the
+    /// default device profile
+    fn scripted_netconf_connection(address: &str) -> NetconfConnection {
+        let stream = TcpStream::connect(address).expect("couldnt connect to peer");
+        let socket = stream.try_clone().expect("couldnt clone the socket");
+        let mut netconf_client = NetconfClient::from_transport(stream);
+        let hello = netconf_client.connect().expect("no hello received");
+        netconf_client.send_hello().expect("couldnt send hello");
+
+        NetconfConnection {
+            netconf_client,
+            yang_ctx: init_yang_ctx(&YANG_MODULES.to_vec()),
+            yang_paths: YangPaths::load_paths(&DeviceProfile::default()),
+            capabilities: hello.capabilities,
+            socket,
+        }
+    }
+
+    /// runs a NETCONF 1.0 peer on a free port, that answers the rpcs of one session with the
+    /// provided replies in order. "{id}" in a reply is replaced with the message-id of the rpc.
+    /// Returns the address and the received rpcs, once all replies are sent.
+    fn scripted_netconf_peer(
+        capabilities: &[&str],
+        replies: Vec<&'static str>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("couldnt bind peer");
+        let address = listener.local_addr().unwrap().to_string();
+        let capabilities: String = capabilities
+            .iter()
+            .map(|c| format!("<capability>{c}</capability>"))
+            .collect();
+
+        let peer = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("no client connected");
+            let hello = format!("<hello xmlns=\"urn:ietf:params:xml:ns:netconf:base:1.0\"><capabilities>{capabilities}</capabilities><session-id>1</session-id></hello>");
+            let client_hello = netconf_exchange(&mut stream, &hello);
+            assert!(client_hello.contains("<hello"));
+
+            let mut rpcs = Vec::new();
+            for reply in replies {
+                let rpc = netconf_receive(&mut stream);
+                let start = rpc.find("message-id=\"").unwrap() + "message-id=\"".len();
+                let id = &rpc[start..start + rpc[start..].find('"').unwrap()];
+                let reply = reply.replace("{id}", id);
+                stream
+                    .write_all(format!("{reply}]]>]]>").as_bytes())
+                    .expect("couldnt send reply");
+                rpcs.push(rpc);
+            }
+            rpcs
+        });
+
+        (address, peer)
+    }
+
+    #[test]
+    /// This test runs the NETCONF-client of the southbound against a scripted peer and checks the
+    /// sent rpcs as well as the parsing of <data> and <rpc-error> replies.
+    fn netconf_client_rpcs() {
+        let (address, peer) = scripted_netconf_peer(
+            &[
+                "urn:ietf:params:netconf:base:1.0",
+                "urn:ietf:params:netconf:capability:candidate:1.0",
+            ],
+            vec![
+                r#"<rpc-reply message-id="{id}" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0"><data><interfaces><data>1</data></interfaces></data></rpc-reply>"#,
+                r#"<rpc-reply message-id="{id}" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0"><rpc-error><error-tag>lock-denied</error-tag><error-message>locked by session 2</error-message></rpc-error></rpc-reply>"#,
+                r#"<rpc-reply message-id="{id}" xmlns="urn:ietf:params:xml:ns:netconf:base:1.0"><ok/></rpc-reply>"#,
+            ],
+        );
+
+        let stream = TcpStream::connect(&address).expect("couldnt connect to peer");
+        let mut client = NetconfClient::from_transport(stream);
+        let hello = client.connect().expect("no hello received");
+        assert_eq!(hello.session_id, Some(1));
+        assert_eq!(hello.capabilities.len(), 2);
+        client.send_hello().expect("couldnt send hello");
+
+        let reply = client.get(Some("<interfaces/>")).expect("<get> failed");
+        assert_eq!(
+            reply.data.as_deref(),
+            Some("<interfaces><data>1</data></interfaces>")
+        );
+
+        match client.lock(DatastoreType::Candidate) {
+            Err(NetconfClientError::Rpc(error)) => {
+                assert_eq!(error, "lock-denied: locked by session 2")
+            }
+            other => panic!("expected an rpc-error, got {other:?}"),
+        }
+
+        let reply = client.commit_confirmed(120).expect("<commit> failed");
+        assert!(reply.data.is_none());
+
+        let rpcs = peer.join().unwrap();
+        assert!(rpcs[0].contains(r#"<get><filter type="subtree"><interfaces/></filter></get>"#));
+        assert!(rpcs[1].contains("<lock><target><candidate/></target></lock>"));
+        assert!(rpcs[2].contains("<confirmed/><confirm-timeout>120</confirm-timeout>"));
     }
 }